@@ -20,6 +20,8 @@ pub struct BlameParams {
 	pub file_path: String,
 	/// blame at a specific revision
 	pub commit_id: Option<CommitId>,
+	/// commits to skip when attributing lines
+	pub ignore_revs: Vec<CommitId>,
 }
 
 struct Request<R, A>(R, Option<A>);
@@ -151,6 +153,7 @@ impl AsyncBlame {
 			repo_path,
 			&params.file_path,
 			params.commit_id,
+			&params.ignore_revs,
 		)?;
 
 		let mut notify = false;
@@ -81,6 +81,10 @@ pub enum Error {
 	#[error("rayon error: {0}")]
 	ThreadPool(#[from] rayon_core::ThreadPoolBuildError),
 
+	///
+	#[error("regex error: {0}")]
+	Regex(#[from] regex::Error),
+
 	///
 	#[error("git hook error: {0}")]
 	Hooks(#[from] git2_hooks::HooksError),
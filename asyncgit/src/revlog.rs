@@ -1,11 +1,14 @@
 use crate::{
 	error::Result,
 	sync::{
-		repo, CommitId, LogWalker, RepoPath, SharedCommitFilterFn,
+		repo, CommitGraph, CommitId, GraphRow, LogRefs, LogWalker,
+		LogWalkerOptions, RepoPath, RevisionRange,
+		SharedCommitFilterFn, SharedWalkStartFn,
 	},
 	AsyncGitNotification, Error,
 };
 use crossbeam_channel::Sender;
+use git2::Repository;
 use scopetime::scope_time;
 use std::{
 	sync::{
@@ -31,17 +34,25 @@ pub enum FetchStatus {
 pub struct AsyncLogResult {
 	///
 	pub commits: Vec<CommitId>,
+	/// graph row per commit (only for logs not skipping commits)
+	pub graph: Vec<GraphRow>,
 	///
 	pub duration: Duration,
 }
 ///
 pub struct AsyncLog {
 	current: Arc<Mutex<AsyncLogResult>>,
-	current_head: Arc<Mutex<Option<CommitId>>>,
+	current_range: Arc<Mutex<Option<RevisionRange>>>,
+	/// `refs` resolved once if they are a revspec, which can be
+	/// expensive to resolve (`merge_base` of `a...b`)
+	revspec_range: Option<RevisionRange>,
 	sender: Sender<AsyncGitNotification>,
 	pending: Arc<AtomicBool>,
 	background: Arc<AtomicBool>,
 	filter: Option<SharedCommitFilterFn>,
+	walk_start: Option<SharedWalkStartFn>,
+	refs: LogRefs,
+	options: LogWalkerOptions,
 	partial_extract: AtomicBool,
 	repo: RepoPath,
 }
@@ -61,13 +72,18 @@ impl AsyncLog {
 			repo,
 			current: Arc::new(Mutex::new(AsyncLogResult {
 				commits: Vec::new(),
+				graph: Vec::new(),
 				duration: Duration::default(),
 			})),
-			current_head: Arc::new(Mutex::new(None)),
+			current_range: Arc::new(Mutex::new(None)),
+			revspec_range: None,
 			sender: sender.clone(),
 			pending: Arc::new(AtomicBool::new(false)),
 			background: Arc::new(AtomicBool::new(false)),
 			filter,
+			walk_start: None,
+			refs: LogRefs::default(),
+			options: LogWalkerOptions::default(),
 			partial_extract: AtomicBool::new(false),
 		}
 	}
@@ -114,6 +130,13 @@ impl AsyncLog {
 		Ok(result)
 	}
 
+	/// takes the graph rows computed so far,
+	/// see `extract_items`
+	pub fn extract_graph(&self) -> Result<Vec<GraphRow>> {
+		let graph = &mut self.current.lock()?.graph;
+		Ok(std::mem::take(graph))
+	}
+
 	///
 	pub fn get_last_duration(&self) -> Result<Duration> {
 		Ok(self.current.lock()?.duration)
@@ -130,18 +153,54 @@ impl AsyncLog {
 	}
 
 	///
-	fn current_head(&self) -> Result<Option<CommitId>> {
-		Ok(*self.current_head.lock()?)
+	pub const fn refs(&self) -> &LogRefs {
+		&self.refs
+	}
+
+	/// walk the log from `refs` instead, starts over on the next
+	/// `fetch`
+	pub fn set_refs(&mut self, refs: LogRefs) -> Result<()> {
+		if self.refs != refs {
+			self.revspec_range = match &refs {
+				LogRefs::Revspec(_) => repo(&self.repo)
+					.and_then(|repo| refs.resolve(&repo))
+					.ok(),
+				_ => None,
+			};
+			self.refs = refs;
+			*self.current_range.lock()? = None;
+		}
+		Ok(())
+	}
+
+	/// traverse the log using `options` instead, starts over on the
+	/// next `fetch`
+	pub fn set_options(
+		&mut self,
+		options: LogWalkerOptions,
+	) -> Result<()> {
+		if self.options != options {
+			self.options = options;
+			*self.current_range.lock()? = None;
+		}
+		Ok(())
+	}
+
+	/// `walk_start` is called whenever a `fetch` starts walking the
+	/// log, see [`LogWalker::on_walk_start`]
+	pub fn set_walk_start(&mut self, walk_start: SharedWalkStartFn) {
+		self.walk_start = Some(walk_start);
 	}
 
 	///
-	fn head_changed(&self) -> Result<bool> {
-		if let Ok(head) = repo(&self.repo)?.head() {
-			return Ok(
-				head.target() != self.current_head()?.map(Into::into)
-			);
+	fn range(&self) -> Option<RevisionRange> {
+		if let LogRefs::Revspec(_) = self.refs {
+			return self.revspec_range.clone();
 		}
-		Ok(false)
+
+		repo(&self.repo)
+			.and_then(|repo| self.refs.resolve(&repo))
+			.ok()
 	}
 
 	///
@@ -152,7 +211,11 @@ impl AsyncLog {
 			return Ok(FetchStatus::Pending);
 		}
 
-		if !self.head_changed()? {
+		let Some(range) = self.range() else {
+			return Ok(FetchStatus::NoChange);
+		};
+
+		if Some(&range) == self.current_range.lock()?.as_ref() {
 			return Ok(FetchStatus::NoChange);
 		}
 
@@ -165,12 +228,11 @@ impl AsyncLog {
 		let arc_pending = Arc::clone(&self.pending);
 		let arc_background = Arc::clone(&self.background);
 		let filter = self.filter.clone();
+		let walk_start = self.walk_start.clone();
+		let options = self.options;
 		let repo_path = self.repo.clone();
 
-		if let Ok(head) = repo(&self.repo)?.head() {
-			*self.current_head.lock()? =
-				head.target().map(CommitId::new);
-		}
+		*self.current_range.lock()? = Some(range.clone());
 
 		rayon_core::spawn(move || {
 			scope_time!("async::revlog");
@@ -181,6 +243,9 @@ impl AsyncLog {
 				&arc_background,
 				&sender,
 				filter,
+				walk_start,
+				&range,
+				options,
 			)
 			.expect("failed to fetch");
 
@@ -192,12 +257,16 @@ impl AsyncLog {
 		Ok(FetchStatus::Started)
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	fn fetch_helper(
 		repo_path: &RepoPath,
 		arc_current: &Arc<Mutex<AsyncLogResult>>,
 		arc_background: &Arc<AtomicBool>,
 		sender: &Sender<AsyncGitNotification>,
 		filter: Option<SharedCommitFilterFn>,
+		walk_start: Option<SharedWalkStartFn>,
+		range: &RevisionRange,
+		options: LogWalkerOptions,
 	) -> Result<()> {
 		let start_time = Instant::now();
 
@@ -205,15 +274,35 @@ impl AsyncLog {
 		entries.resize(0, CommitId::default());
 
 		let r = repo(repo_path)?;
+		// the graph only makes sense for the complete history
+		let mut graph = (filter.is_none() && !options.no_merges)
+			.then(CommitGraph::default);
 		let mut walker =
-			LogWalker::new(&r, LIMIT_COUNT)?.filter(filter);
+			LogWalker::with_range(&r, LIMIT_COUNT, range)?
+				.filter(filter)
+				.on_walk_start(walk_start)
+				.options(options);
 
 		loop {
 			entries.clear();
 			let read = walker.read(&mut entries)?;
 
+			let rows = graph
+				.as_mut()
+				.map(|graph| {
+					Self::graph_rows(
+						&r,
+						&walker,
+						graph,
+						&entries,
+						options.first_parent,
+					)
+				})
+				.transpose()?;
+
 			let mut current = arc_current.lock()?;
 			current.commits.extend(entries.iter());
+			current.graph.extend(rows.into_iter().flatten());
 			current.duration = start_time.elapsed();
 
 			if read == 0 {
@@ -236,9 +325,36 @@ impl AsyncLog {
 		Ok(())
 	}
 
+	/// lanes end at parents that are excluded from the log
+	fn graph_rows(
+		repo: &Repository,
+		walker: &LogWalker,
+		graph: &mut CommitGraph,
+		commits: &[CommitId],
+		first_parent: bool,
+	) -> Result<Vec<GraphRow>> {
+		commits
+			.iter()
+			.map(|id| {
+				let commit = repo.find_commit((*id).into())?;
+				let parents = commit
+					.parent_ids()
+					.take(if first_parent { 1 } else { usize::MAX })
+					.map(CommitId::new)
+					.filter(|parent| walker.in_range(*parent))
+					.collect::<Vec<_>>();
+
+				Ok(graph.push(*id, &parents))
+			})
+			.collect()
+	}
+
 	fn clear(&mut self) -> Result<()> {
-		self.current.lock()?.commits.clear();
-		*self.current_head.lock()? = None;
+		let mut current = self.current.lock()?;
+		current.commits.clear();
+		current.graph.clear();
+		drop(current);
+		*self.current_range.lock()? = None;
 		self.partial_extract.store(false, Ordering::Relaxed);
 		Ok(())
 	}
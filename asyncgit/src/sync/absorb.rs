@@ -0,0 +1,390 @@
+//! absorb staged hunks into the commits that last touched the
+//! changed lines
+
+use super::{
+	blame::blame_file,
+	branch::{branch_compare_upstream, get_branch_name_repo},
+	commit_fixup,
+	repository::repo,
+	CommitId, FixupKind, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	ApplyOptions, Delta, Diff, DiffLineType, Oid, Patch, Repository,
+};
+use scopetime::scope_time;
+use std::collections::{HashMap, HashSet};
+
+/// commits considered if the branch has no upstream
+const MAX_STACK_SIZE: usize = 100;
+
+/// commit a staged hunk would be absorbed into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbsorbTarget {
+	/// the only commit touching the lines of the hunk
+	Commit(CommitId, String),
+	/// lines of the hunk were last touched by multiple commits
+	Ambiguous,
+	/// the commit is already pushed upstream
+	Pushed(CommitId),
+	/// pure addition or the commit is not part of the current
+	/// branch (before a merge)
+	NotFound,
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsorbHunk {
+	///
+	pub path: String,
+	/// hunk header (`@@ -1,2 +1,3 @@`)
+	pub header: String,
+	///
+	pub target: AbsorbTarget,
+}
+
+/// preview of [`absorb`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsorbPlan {
+	/// all staged hunks in diff order
+	pub hunks: Vec<AbsorbHunk>,
+	/// tree of the index the plan was made for
+	staged: Oid,
+}
+
+impl AbsorbPlan {
+	/// amount of hunks that have a target commit
+	pub fn absorbable(&self) -> usize {
+		self.hunks
+			.iter()
+			.filter(|h| matches!(h.target, AbsorbTarget::Commit(..)))
+			.count()
+	}
+}
+
+/// commits of the current branch that may be rewritten, newest
+/// first, and whether the branch has an upstream limiting them
+pub(super) fn branch_stack(
+	repo_path: &RepoPath,
+	repo: &Repository,
+) -> Result<(Vec<CommitId>, bool)> {
+	let upstream_ahead = get_branch_name_repo(repo)
+		.and_then(|branch| {
+			branch_compare_upstream(repo_path, &branch)
+		})
+		.map(|compare| compare.ahead)
+		.ok();
+
+	let limit = upstream_ahead.unwrap_or(MAX_STACK_SIZE);
+
+	let mut stack = Vec::new();
+	let mut commit = repo.head()?.peel_to_commit()?;
+
+	while stack.len() < limit && commit.parent_count() <= 1 {
+		stack.push(commit.id().into());
+
+		match commit.parent(0) {
+			Ok(parent) => commit = parent,
+			Err(_) => break,
+		}
+	}
+
+	Ok((stack, upstream_ahead.is_some()))
+}
+
+fn staged_diff(repo: &Repository) -> Result<Diff<'_>> {
+	let head = repo.head()?.peel_to_tree()?;
+
+	Ok(repo.diff_tree_to_index(Some(&head), None, None)?)
+}
+
+/// maps every staged hunk to the commit of the current branch that
+/// last touched the lines it changes
+pub fn absorb_plan(repo_path: &RepoPath) -> Result<AbsorbPlan> {
+	scope_time!("absorb_plan");
+
+	let repo = repo(repo_path)?;
+	let staged = repo.index()?.write_tree()?;
+
+	let (stack, has_upstream) = branch_stack(repo_path, &repo)?;
+
+	let diff = staged_diff(&repo)?;
+
+	let mut blames = HashMap::new();
+	let mut hunks = Vec::new();
+
+	for idx in 0..diff.deltas().len() {
+		let Some(patch) = Patch::from_diff(&diff, idx)? else {
+			continue;
+		};
+
+		let delta = patch.delta();
+		let Some(file) = delta
+			.old_file()
+			.path()
+			.and_then(|p| p.to_str())
+			.map(String::from)
+		else {
+			continue;
+		};
+
+		for hunk_idx in 0..patch.num_hunks() {
+			let (hunk, lines) = patch.hunk(hunk_idx)?;
+
+			// lines of the commit that get changed
+			let mut deleted = Vec::new();
+			for line_idx in 0..lines {
+				let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+
+				if line.origin_value() == DiffLineType::Deletion {
+					deleted.extend(line.old_lineno());
+				}
+			}
+
+			let target = if deleted.is_empty()
+				|| delta.status() != Delta::Modified
+			{
+				AbsorbTarget::NotFound
+			} else {
+				if !blames.contains_key(&file) {
+					blames.insert(
+						file.clone(),
+						blame_file(repo_path, &file, None, &[])?,
+					);
+				}
+
+				let blame = &blames[&file];
+				let commits = deleted
+					.iter()
+					.filter_map(|lineno| {
+						blame
+							.lines
+							.get((*lineno as usize).saturating_sub(1))
+							.and_then(|(hunk, _)| hunk.as_ref())
+							.map(|hunk| hunk.commit_id)
+					})
+					.collect::<HashSet<_>>();
+
+				target_of(&repo, &commits, &stack, has_upstream)?
+			};
+
+			hunks.push(AbsorbHunk {
+				path: file.clone(),
+				header: String::from_utf8_lossy(hunk.header())
+					.trim_end()
+					.to_string(),
+				target,
+			});
+		}
+	}
+
+	Ok(AbsorbPlan { hunks, staged })
+}
+
+fn target_of(
+	repo: &Repository,
+	commits: &HashSet<CommitId>,
+	stack: &[CommitId],
+	has_upstream: bool,
+) -> Result<AbsorbTarget> {
+	let mut commits = commits.iter();
+
+	let (Some(id), None) = (commits.next(), commits.next()) else {
+		return Ok(AbsorbTarget::Ambiguous);
+	};
+
+	if stack.contains(id) {
+		let summary = repo
+			.find_commit((*id).into())?
+			.summary()
+			.unwrap_or_default()
+			.to_string();
+
+		Ok(AbsorbTarget::Commit(*id, summary))
+	} else if has_upstream {
+		Ok(AbsorbTarget::Pushed(*id))
+	} else {
+		Ok(AbsorbTarget::NotFound)
+	}
+}
+
+/// commits the hunks of `plan` that have a target as `fixup!` commits
+/// of their targets (one per target), other hunks stay staged.
+///
+/// returns the created commits, use
+/// [`autosquash`](super::autosquash) to meld them.
+pub fn absorb(
+	repo_path: &RepoPath,
+	plan: &AbsorbPlan,
+) -> Result<Vec<CommitId>> {
+	scope_time!("absorb");
+
+	let repo = repo(repo_path)?;
+	let mut index = repo.index()?;
+
+	if index.write_tree()? != plan.staged {
+		return Err(Error::Generic(String::from(
+			"staged changes changed since the absorb preview",
+		)));
+	}
+
+	let staged = repo.find_tree(plan.staged)?;
+	let head_tree = repo.head()?.peel_to_tree()?;
+	let diff = staged_diff(&repo)?;
+
+	// targets in order of their first hunk
+	let mut targets: Vec<CommitId> = Vec::new();
+	for hunk in &plan.hunks {
+		if let AbsorbTarget::Commit(id, _) = &hunk.target {
+			if !targets.contains(id) {
+				targets.push(*id);
+			}
+		}
+	}
+
+	let mut commits = Vec::with_capacity(targets.len());
+
+	let res = (|| -> Result<()> {
+		for (idx, target) in targets.iter().enumerate() {
+			// always apply to the original HEAD, so the hunk positions
+			// of the staged diff stay valid
+			let included = &targets[..=idx];
+
+			let mut hunk_idx = 0;
+			let mut opt = ApplyOptions::new();
+			opt.hunk_callback(|_hunk| {
+				let res = plan.hunks.get(hunk_idx).is_some_and(|h| {
+					matches!(&h.target, AbsorbTarget::Commit(id, _) if included.contains(id))
+				});
+				hunk_idx += 1;
+				res
+			});
+
+			let mut tree_index = repo.apply_to_tree(
+				&head_tree,
+				&diff,
+				Some(&mut opt),
+			)?;
+			let tree = tree_index.write_tree_to(&repo)?;
+
+			index.read_tree(&repo.find_tree(tree)?)?;
+			index.write()?;
+
+			commits.push(commit_fixup(
+				repo_path,
+				*target,
+				FixupKind::Fixup,
+			)?);
+		}
+
+		Ok(())
+	})();
+
+	// whatever was not absorbed stays staged
+	index.read_tree(&staged)?;
+	index.write()?;
+
+	res.map(|()| commits)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		autosquash, commit, get_commit_info, stage_add_file,
+		tests::{get_commit_ids, repo_init, write_commit_file},
+		utils::{repo_read_file, repo_write_file},
+		RebaseState,
+	};
+	use pretty_assertions::assert_eq;
+	use std::path::Path;
+
+	#[test]
+	fn test_absorb() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "1\n2\n3\n", "c1");
+		let c2 = write_commit_file(
+			&repo,
+			"a.txt",
+			"1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n",
+			"c2",
+		);
+		write_commit_file(&repo, "b.txt", "b\n", "c3");
+
+		repo_write_file(
+			&repo,
+			"a.txt",
+			"1\nx\n3\n4\n5\n6\n7\n8\n9\ny\n",
+		)
+		.unwrap();
+		repo_write_file(&repo, "c.txt", "new\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		stage_add_file(repo_path, Path::new("c.txt")).unwrap();
+
+		let plan = absorb_plan(repo_path).unwrap();
+
+		assert_eq!(plan.hunks.len(), 3);
+		assert_eq!(
+			plan.hunks[0].target,
+			AbsorbTarget::Commit(c1, String::from("c1"))
+		);
+		assert_eq!(
+			plan.hunks[1].target,
+			AbsorbTarget::Commit(c2, String::from("c2"))
+		);
+		assert_eq!(plan.hunks[2].path, "c.txt");
+		assert_eq!(plan.hunks[2].target, AbsorbTarget::NotFound);
+		assert_eq!(plan.absorbable(), 2);
+
+		let commits = absorb(repo_path, &plan).unwrap();
+
+		assert_eq!(commits.len(), 2);
+		assert_eq!(
+			get_commit_info(repo_path, &commits[0]).unwrap().message,
+			"fixup! c1"
+		);
+
+		// unabsorbed hunk is still staged
+		let plan = absorb_plan(repo_path).unwrap();
+		assert_eq!(plan.hunks.len(), 1);
+		assert_eq!(plan.hunks[0].path, "c.txt");
+
+		// fixups meld cleanly
+		commit(repo_path, "c4").unwrap();
+
+		assert_eq!(
+			autosquash(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(get_commit_ids(&repo, 10).len(), 5);
+		assert_eq!(
+			repo_read_file(&repo, "a.txt").unwrap(),
+			"1\nx\n3\n4\n5\n6\n7\n8\n9\ny\n"
+		);
+	}
+
+	#[test]
+	fn test_absorb_ambiguous() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "1\n", "c1");
+		write_commit_file(&repo, "a.txt", "1\n2\n", "c2");
+
+		repo_write_file(&repo, "a.txt", "x\ny\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		let plan = absorb_plan(repo_path).unwrap();
+
+		assert_eq!(plan.hunks.len(), 1);
+		assert_eq!(plan.hunks[0].target, AbsorbTarget::Ambiguous);
+		assert_eq!(plan.absorbable(), 0);
+		assert!(absorb(repo_path, &plan).unwrap().is_empty());
+	}
+}
@@ -0,0 +1,404 @@
+//! `fixup!`, `squash!` and `amend!` commits and folding them into
+//! their targets
+
+use super::{
+	absorb::branch_stack,
+	commit, get_commit_info,
+	rebase_interactive::{
+		rebase_interactive, rebase_interactive_plan, RebaseAction,
+		RebasePlanEntry,
+	},
+	repository::repo,
+	CommitId, RebaseState, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::Repository;
+use scopetime::scope_time;
+
+/// kind of commit to be melded into another one by [`autosquash`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixupKind {
+	/// `fixup!`: keep the message of the target
+	Fixup,
+	/// `squash!`: append the message to the one of the target
+	Squash,
+	/// `amend!`: replace the message of the target
+	Amend,
+}
+
+impl FixupKind {
+	/// subject prefix marking commits of this kind
+	pub const fn prefix(self) -> &'static str {
+		match self {
+			Self::Fixup => "fixup! ",
+			Self::Squash => "squash! ",
+			Self::Amend => "amend! ",
+		}
+	}
+
+	const fn action(self) -> RebaseAction {
+		match self {
+			Self::Fixup => RebaseAction::Fixup,
+			Self::Squash => RebaseAction::Squash,
+			Self::Amend => RebaseAction::Amend,
+		}
+	}
+
+	/// kind and target subject of a commit subject,
+	/// `None` if it is a regular commit
+	fn parse(subject: &str) -> Option<(Self, &str)> {
+		let kind = [Self::Fixup, Self::Squash, Self::Amend]
+			.into_iter()
+			.find(|kind| subject.starts_with(kind.prefix()))?;
+
+		// `fixup! fixup! x` targets `x` as well
+		let mut target = subject;
+		while let Some(rest) =
+			[Self::Fixup, Self::Squash, Self::Amend]
+				.into_iter()
+				.find_map(|kind| target.strip_prefix(kind.prefix()))
+		{
+			target = rest;
+		}
+
+		Some((kind, target))
+	}
+}
+
+/// message for a commit of `kind` targeting `target`
+pub fn fixup_message(
+	repo_path: &RepoPath,
+	target: CommitId,
+	kind: FixupKind,
+) -> Result<String> {
+	let info = get_commit_info(repo_path, &target)?;
+	let summary = info.message.lines().next().unwrap_or_default();
+
+	Ok(match kind {
+		FixupKind::Fixup => format!("{}{summary}", kind.prefix()),
+		FixupKind::Squash => {
+			format!("{}{summary}\n\n", kind.prefix())
+		}
+		FixupKind::Amend => {
+			let repo = repo(repo_path)?;
+			let commit = repo.find_commit(target.into())?;
+
+			format!(
+				"{}{summary}\n\n{}",
+				kind.prefix(),
+				commit.message().unwrap_or_default()
+			)
+		}
+	})
+}
+
+/// commits the staged changes as `kind` of `target`
+pub fn commit_fixup(
+	repo_path: &RepoPath,
+	target: CommitId,
+	kind: FixupKind,
+) -> Result<CommitId> {
+	scope_time!("commit_fixup");
+
+	{
+		let repo = repo(repo_path)?;
+		let head = repo.head()?.peel_to_commit()?;
+
+		if repo.index()?.write_tree()? == head.tree_id() {
+			return Err(Error::Generic(String::from(
+				"nothing staged to commit",
+			)));
+		}
+	}
+
+	commit(repo_path, &fixup_message(repo_path, target, kind)?)
+}
+
+/// melds all `fixup!`, `squash!` and `amend!` commits of the current
+/// branch into their targets using an interactive rebase.
+///
+/// only considers commits since the last merge commit that are not
+/// pushed upstream yet, fails if a fixup commit targets an older one.
+pub fn autosquash(repo_path: &RepoPath) -> Result<RebaseState> {
+	scope_time!("autosquash");
+
+	let base = autosquash_base(repo_path, &repo(repo_path)?)?;
+
+	let plan =
+		autosquash_plan(rebase_interactive_plan(repo_path, base)?);
+
+	rebase_interactive(repo_path, base, &plan)
+}
+
+/// parent of the oldest commit targeted by a fixup commit
+fn autosquash_base(
+	repo_path: &RepoPath,
+	repo: &Repository,
+) -> Result<CommitId> {
+	let (stack, _) = branch_stack(repo_path, repo)?;
+
+	// oldest first
+	let commits = stack
+		.iter()
+		.rev()
+		.map(|id| {
+			let commit = repo.find_commit((*id).into())?;
+			Ok((
+				*id,
+				commit.summary().unwrap_or_default().to_string(),
+			))
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	let mut oldest_target: Option<usize> = None;
+
+	for (idx, (_, summary)) in commits.iter().enumerate() {
+		if let Some((_, subject)) = FixupKind::parse(summary) {
+			let target = commits[..idx]
+				.iter()
+				.position(|(id, summary)| {
+					is_target(*id, summary, subject)
+				})
+				.ok_or_else(|| {
+					Error::Generic(format!(
+						"target of '{summary}' is not an unpushed commit of the current branch"
+					))
+				})?;
+
+			oldest_target =
+				Some(oldest_target.map_or(target, |t| t.min(target)));
+		}
+	}
+
+	let oldest_target = oldest_target.ok_or_else(|| {
+		Error::Generic(String::from(
+			"no fixup!/squash!/amend! commits to autosquash",
+		))
+	})?;
+
+	let target = repo.find_commit(commits[oldest_target].0.into())?;
+
+	Ok(target.parent_id(0).map_err(|_| Error::NoParent)?.into())
+}
+
+fn is_target(id: CommitId, summary: &str, subject: &str) -> bool {
+	const MIN_HASH_LENGTH: usize = 4;
+
+	FixupKind::parse(summary).is_none()
+		&& (summary == subject
+			|| (subject.len() >= MIN_HASH_LENGTH
+				&& id.to_string().starts_with(subject)))
+}
+
+/// moves fixup commits right behind their targets
+fn autosquash_plan(
+	plan: Vec<RebasePlanEntry>,
+) -> Vec<RebasePlanEntry> {
+	let mut res: Vec<RebasePlanEntry> =
+		Vec::with_capacity(plan.len());
+
+	for entry in plan {
+		if let Some((kind, subject)) =
+			FixupKind::parse(&entry.summary)
+		{
+			let target = res.iter().position(|e| {
+				e.action == RebaseAction::Pick
+					&& is_target(e.id, &e.summary, subject)
+			});
+
+			if let Some(target) = target {
+				let pos = res
+					.iter()
+					.skip(target + 1)
+					.position(|e| !e.action.melds())
+					.map_or(res.len(), |pos| target + 1 + pos);
+
+				res.insert(
+					pos,
+					RebasePlanEntry {
+						action: kind.action(),
+						..entry
+					},
+				);
+
+				continue;
+			}
+		}
+
+		res.push(entry);
+	}
+
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		remotes::push::push_branch,
+		repo_state, reset_repo, stage_add_file,
+		tests::{
+			get_commit_ids, repo_clone, repo_init, repo_init_bare,
+			write_commit_file,
+		},
+		utils::{repo_read_file, repo_write_file},
+		RepoState,
+	};
+	use git2::{BranchType, ResetType};
+	use pretty_assertions::assert_eq;
+	use std::path::Path;
+
+	fn messages(
+		repo_path: &RepoPath,
+		repo: &Repository,
+	) -> Vec<String> {
+		get_commit_ids(repo, 10)
+			.iter()
+			.map(|id| get_commit_info(repo_path, id).unwrap().message)
+			.collect()
+	}
+
+	fn entry(id: u8, summary: &str) -> RebasePlanEntry {
+		RebasePlanEntry {
+			action: RebaseAction::Pick,
+			id: git2::Oid::from_bytes(&[id; 20]).unwrap().into(),
+			summary: summary.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_plan() {
+		let plan = autosquash_plan(vec![
+			entry(1, "a"),
+			entry(2, "b"),
+			entry(3, "fixup! a"),
+			entry(4, "squash! fixup! a"),
+			entry(5, "amend! 0202020"),
+			entry(6, "fixup! unknown"),
+		]);
+
+		assert_eq!(
+			plan.iter()
+				.map(|e| (e.summary.as_str(), e.action))
+				.collect::<Vec<_>>(),
+			vec![
+				("a", RebaseAction::Pick),
+				("fixup! a", RebaseAction::Fixup),
+				("squash! fixup! a", RebaseAction::Squash),
+				("b", RebaseAction::Pick),
+				("amend! 0202020", RebaseAction::Amend),
+				("fixup! unknown", RebaseAction::Pick),
+			]
+		);
+	}
+
+	#[test]
+	fn test_fixup_and_autosquash() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+		write_commit_file(&repo, "c.txt", "c", "c3");
+
+		assert!(
+			commit_fixup(repo_path, c1, FixupKind::Fixup).is_err()
+		);
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		commit_fixup(repo_path, c1, FixupKind::Fixup).unwrap();
+
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		commit(
+			repo_path,
+			&fixup_message(repo_path, c2, FixupKind::Amend)
+				.unwrap()
+				.replace("\n\nc2", "\n\nc2 amended"),
+		)
+		.unwrap();
+
+		repo_write_file(&repo, "a.txt", "a3").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		commit(
+			repo_path,
+			&format!(
+				"{}squashed",
+				fixup_message(repo_path, c1, FixupKind::Squash)
+					.unwrap()
+			),
+		)
+		.unwrap();
+
+		assert_eq!(
+			messages(repo_path, &repo)[..3],
+			[
+				"squash! c1\n\nsquashed",
+				"amend! c2\n\nc2 amended",
+				"fixup! c1"
+			]
+		);
+
+		let res = autosquash(repo_path).unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			messages(repo_path, &repo),
+			vec!["c3", "c2 amended", "c1\n\nsquashed", "initial"]
+		);
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a3");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+
+		assert!(autosquash(repo_path).is_err());
+	}
+
+	#[test]
+	fn test_autosquash_keeps_pushed_commits() {
+		let (remote_dir, _remote) = repo_init_bare().unwrap();
+		let (clone_dir, repo) =
+			repo_clone(remote_dir.path().to_str().unwrap()).unwrap();
+		let repo_path: &RepoPath =
+			&clone_dir.path().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+		repo.find_branch("master", BranchType::Local)
+			.unwrap()
+			.set_upstream(Some("origin/master"))
+			.unwrap();
+
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		commit_fixup(repo_path, c1, FixupKind::Fixup).unwrap();
+
+		let head = repo.head().unwrap().target().unwrap();
+		assert!(autosquash(repo_path).is_err());
+		assert_eq!(repo.head().unwrap().target().unwrap(), head);
+
+		reset_repo(repo_path, c2, ResetType::Hard).unwrap();
+
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		commit_fixup(repo_path, c2, FixupKind::Fixup).unwrap();
+
+		assert_eq!(
+			autosquash(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(messages(repo_path, &repo), vec!["c2", "c1"]);
+		assert_eq!(
+			get_commit_ids(&repo, 10)[1],
+			c1,
+			"pushed commit must not be rewritten"
+		);
+	}
+}
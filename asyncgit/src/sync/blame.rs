@@ -5,11 +5,14 @@ use crate::{
 	error::{Error, Result},
 	sync::{get_commits_info, repository::repo},
 };
-use git2::BlameOptions;
+use git2::{
+	Blame, BlameOptions, Delta, DiffFindOptions, DiffOptions, Patch,
+	Repository,
+};
 use scopetime::scope_time;
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// A `BlameHunk` contains all the information that will be shown to the user.
 #[derive(Clone, Hash, Debug, PartialEq, Eq)]
@@ -26,6 +29,11 @@ pub struct BlameHunk {
 	pub start_line: usize,
 	///
 	pub end_line: usize,
+	/// 0-based line of `start_line` in the version of the file at
+	/// `commit_id`
+	pub orig_start_line: usize,
+	/// path of the file at `commit_id`
+	pub orig_path: String,
 }
 
 /// A `BlameFile` represents a collection of lines. This is targeted at how the
@@ -53,11 +61,368 @@ fn fixup_windows_path(path: &str) -> String {
 	}
 }
 
+/// commits listed in the file configured as `blame.ignoreRevsFile`
+pub fn blame_ignore_revs(
+	repo_path: &RepoPath,
+) -> Result<Vec<CommitId>> {
+	scope_time!("blame_ignore_revs");
+
+	let repo = repo(repo_path)?;
+
+	let Ok(file) = repo.config()?.get_path("blame.ignoreRevsFile")
+	else {
+		return Ok(Vec::new());
+	};
+
+	// relative paths are relative to the workdir
+	let file = repo
+		.workdir()
+		.map_or_else(|| file.clone(), |workdir| workdir.join(&file));
+
+	let Ok(content) = std::fs::read_to_string(file) else {
+		return Ok(Vec::new());
+	};
+
+	let revs = content
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.filter_map(|rev| {
+			repo.revparse_single(rev).ok()?.peel_to_commit().ok()
+		})
+		.map(|commit| CommitId::new(commit.id()))
+		.collect();
+
+	Ok(revs)
+}
+
+/// the parent of `commit_id`, the path of `file_path` in it and the
+/// line (0-based) that `line` was changed from. `None` for root
+/// commits and files added in `commit_id`.
 ///
+/// `file_path` and `line` refer to the version of the file at
+/// `commit_id` (see [`BlameHunk::orig_start_line`]), lines added by
+/// `commit_id` map to the closest line in the parent
+pub fn blame_parent(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+	file_path: &str,
+	line: usize,
+) -> Result<Option<(CommitId, String, usize)>> {
+	scope_time!("blame_parent");
+
+	let repo = repo(repo_path)?;
+
+	let parent = parent_line(
+		&repo,
+		commit_id,
+		Path::new(file_path),
+		line.saturating_add(1),
+	)?;
+
+	Ok(parent.map(|parent| {
+		(
+			parent.commit_id,
+			fixup_windows_path(&parent.path.to_string_lossy()),
+			parent.line - 1,
+		)
+	}))
+}
+
+/// line in the first parent of a commit, see [`parent_line`]
+struct ParentLine {
+	commit_id: CommitId,
+	/// path of the file in the parent
+	path: PathBuf,
+	/// 1-based
+	line: usize,
+	/// `false` if the line was added in the commit and `line` is
+	/// just the closest line in the parent
+	exact: bool,
+}
+
+/// maps the 1-based `line` of the file at `path` in `commit_id` to
+/// its parent, see [`FileChange`]
+fn parent_line(
+	repo: &Repository,
+	commit_id: CommitId,
+	path: &Path,
+	line: usize,
+) -> Result<Option<ParentLine>> {
+	Ok(FileChange::new(repo, commit_id, path)?
+		.map(|change| change.parent_line(line)))
+}
+
+/// how a commit changed a file compared to its first parent,
+/// following a rename of the file in the commit
+struct FileChange {
+	parent_id: CommitId,
+	/// path of the file in the parent
+	parent_path: PathBuf,
+	/// `(old_start, old_lines, new_start, new_lines)` of each hunk
+	hunks: Vec<(usize, usize, usize, usize)>,
+}
+
+impl FileChange {
+	/// `None` for root commits and files added in `commit_id`
+	fn new(
+		repo: &Repository,
+		commit_id: CommitId,
+		path: &Path,
+	) -> Result<Option<Self>> {
+		let commit = repo.find_commit(commit_id.into())?;
+		let Ok(parent) = commit.parent(0) else {
+			return Ok(None);
+		};
+
+		let parent_tree = parent.tree()?;
+		let tree = commit.tree()?;
+
+		let mut opts = DiffOptions::new();
+		opts.pathspec(path).context_lines(0);
+
+		let mut diff = repo.diff_tree_to_tree(
+			Some(&parent_tree),
+			Some(&tree),
+			Some(&mut opts),
+		)?;
+
+		// a rename only shows up when diffing the other files as well
+		if diff.deltas().any(|delta| delta.status() == Delta::Added) {
+			let mut opts = DiffOptions::new();
+			opts.context_lines(0);
+
+			diff = repo.diff_tree_to_tree(
+				Some(&parent_tree),
+				Some(&tree),
+				Some(&mut opts),
+			)?;
+			diff.find_similar(Some(
+				DiffFindOptions::new().renames(true),
+			))?;
+		}
+
+		let mut change = Self {
+			parent_id: CommitId::new(parent.id()),
+			parent_path: path.to_path_buf(),
+			hunks: Vec::new(),
+		};
+
+		let Some(idx) = diff
+			.deltas()
+			.position(|delta| delta.new_file().path() == Some(path))
+		else {
+			// file unchanged in this commit
+			return Ok(Some(change));
+		};
+
+		let delta = diff.get_delta(idx).ok_or_else(|| {
+			Error::Generic(String::from("invalid diff delta"))
+		})?;
+
+		if delta.status() == Delta::Added {
+			return Ok(None);
+		}
+
+		if let Some(old_path) = delta.old_file().path() {
+			change.parent_path = old_path.to_path_buf();
+		}
+
+		if let Some(patch) = Patch::from_diff(&diff, idx)? {
+			for hunk_idx in 0..patch.num_hunks() {
+				let (hunk, _) = patch.hunk(hunk_idx)?;
+				change.hunks.push((
+					hunk.old_start() as usize,
+					hunk.old_lines() as usize,
+					hunk.new_start() as usize,
+					hunk.new_lines() as usize,
+				));
+			}
+		}
+
+		Ok(Some(change))
+	}
+
+	/// maps the 1-based `line` to the parent by walking the hunks
+	fn parent_line(&self, line: usize) -> ParentLine {
+		let mut parent_line = ParentLine {
+			commit_id: self.parent_id,
+			path: self.parent_path.clone(),
+			line,
+			exact: true,
+		};
+
+		for &(old_start, old_lines, new_start, new_lines) in
+			&self.hunks
+		{
+			// a hunk only removing lines sits behind `new_start`
+			let new_end = new_start + new_lines.max(1);
+
+			if line >= new_end {
+				parent_line.line = (parent_line.line + old_lines)
+					.saturating_sub(new_lines);
+			} else if line >= new_start && new_lines > 0 {
+				let offset = line - new_start;
+
+				parent_line.line = if old_lines == 0 {
+					parent_line.exact = false;
+					old_start.max(1)
+				} else {
+					old_start + offset.min(old_lines - 1)
+				};
+
+				break;
+			} else {
+				break;
+			}
+		}
+
+		parent_line
+	}
+}
+
+/// attributes lines of ignored commits to the commit that changed
+/// them before, like `git blame --ignore-rev`
+struct IgnoredRevs<'a> {
+	repo: &'a Repository,
+	revs: HashSet<CommitId>,
+	changes: HashMap<(CommitId, PathBuf), Option<FileChange>>,
+	blames: HashMap<(CommitId, PathBuf), Option<Blame<'a>>>,
+}
+
+impl<'a> IgnoredRevs<'a> {
+	fn new(repo: &'a Repository, revs: &[CommitId]) -> Self {
+		Self {
+			repo,
+			revs: revs.iter().copied().collect(),
+			changes: HashMap::new(),
+			blames: HashMap::new(),
+		}
+	}
+
+	fn contains(&self, commit_id: &CommitId) -> bool {
+		self.revs.contains(commit_id)
+	}
+
+	/// `line` is 1-based in the version of `path` in `commit_id`,
+	/// lines added by an ignored commit stay attributed to it.
+	/// returns the commit with the path and line in its version.
+	fn attribute(
+		&mut self,
+		mut commit_id: CommitId,
+		mut path: PathBuf,
+		mut line: usize,
+	) -> Result<(CommitId, PathBuf, usize)> {
+		while self.contains(&commit_id) {
+			let change =
+				match self.changes.entry((commit_id, path.clone())) {
+					Entry::Occupied(entry) => entry.into_mut(),
+					Entry::Vacant(entry) => {
+						let change = FileChange::new(
+							self.repo,
+							commit_id,
+							&entry.key().1,
+						)?;
+						entry.insert(change)
+					}
+				};
+
+			let Some(parent) = change
+				.as_ref()
+				.map(|change| change.parent_line(line))
+			else {
+				break;
+			};
+
+			if !parent.exact {
+				break;
+			}
+
+			let repo = self.repo;
+			let blame = self
+				.blames
+				.entry((parent.commit_id, parent.path.clone()))
+				.or_insert_with(|| {
+					let mut opts = BlameOptions::new();
+					opts.newest_commit(parent.commit_id.into());
+					repo.blame_file(&parent.path, Some(&mut opts))
+						.ok()
+				});
+
+			let Some(hunk) =
+				blame.as_ref().and_then(|b| b.get_line(parent.line))
+			else {
+				break;
+			};
+
+			commit_id = CommitId::new(hunk.final_commit_id());
+			line = hunk.orig_start_line()
+				+ (parent.line - hunk.final_start_line());
+			path = hunk.path().map_or(parent.path, Path::to_path_buf);
+		}
+
+		Ok((commit_id, path, line))
+	}
+}
+
+/// commit a range of lines is attributed to, see [`BlameHunk`]
+struct Attribution {
+	commit_id: CommitId,
+	start_line: usize,
+	end_line: usize,
+	orig_start_line: usize,
+	orig_path: PathBuf,
+}
+
+/// attribution of the 0-based `line` that is part of `hunk`
+fn attribute(
+	hunk: &git2::BlameHunk,
+	line: usize,
+	file_path: &str,
+	ignored: &mut IgnoredRevs,
+) -> Result<Attribution> {
+	let commit_id = CommitId::new(hunk.final_commit_id());
+	let path = hunk
+		.path()
+		.map_or_else(|| file_path.into(), Path::to_path_buf);
+
+	if ignored.contains(&commit_id) {
+		let orig_line = hunk.orig_start_line()
+			+ (line + 1 - hunk.final_start_line());
+
+		let (commit_id, path, orig_line) =
+			ignored.attribute(commit_id, path, orig_line)?;
+
+		return Ok(Attribution {
+			commit_id,
+			start_line: line,
+			end_line: line + 1,
+			orig_start_line: orig_line.saturating_sub(1),
+			orig_path: path,
+		});
+	}
+
+	// Line indices in a `BlameHunk` are 1-based.
+	let start_line = hunk.final_start_line().saturating_sub(1);
+	let end_line = start_line.saturating_add(hunk.lines_in_hunk());
+
+	Ok(Attribution {
+		commit_id,
+		start_line,
+		end_line,
+		orig_start_line: hunk.orig_start_line().saturating_sub(1),
+		orig_path: path,
+	})
+}
+
+/// blame of `file_path` at `commit_id` (defaults to `HEAD`), lines
+/// changed by any of `ignore_revs` are attributed to the commit that
+/// changed them before
 pub fn blame_file(
 	repo_path: &RepoPath,
 	file_path: &str,
 	commit_id: Option<CommitId>,
+	ignore_revs: &[CommitId],
 ) -> Result<FileBlame> {
 	scope_time!("blame_file");
 
@@ -86,10 +451,31 @@ pub fn blame_file(
 		repo.blame_file(Path::new(file_path), Some(&mut opts))?;
 
 	let reader = BufReader::new(blob.content());
+	let lines: Vec<String> = reader
+		.lines()
+		.map(|line| line.unwrap_or_else(|_| String::new()))
+		.collect();
 
-	let unique_commit_ids: HashSet<_> = blame
+	let mut ignored = IgnoredRevs::new(&repo, ignore_revs);
+
+	// commit and range of lines (0-based) for each line
+	let mut attributions = Vec::with_capacity(lines.len());
+	for i in 0..lines.len() {
+		// Line indices in a `FileBlame` are 1-based.
+		let attribution = match blame.get_line(i + 1) {
+			Some(hunk) => {
+				Some(attribute(&hunk, i, file_path, &mut ignored)?)
+			}
+			None => None,
+		};
+
+		attributions.push(attribution);
+	}
+
+	let unique_commit_ids: HashSet<_> = attributions
 		.iter()
-		.map(|hunk| CommitId::new(hunk.final_commit_id()))
+		.flatten()
+		.map(|attribution| attribution.commit_id)
 		.collect();
 	let mut commit_ids = Vec::with_capacity(unique_commit_ids.len());
 	commit_ids.extend(unique_commit_ids);
@@ -100,40 +486,27 @@ pub fn blame_file(
 		.map(|commit_info| (commit_info.id, commit_info))
 		.collect();
 
-	let lines: Vec<(Option<BlameHunk>, String)> = reader
-		.lines()
-		.enumerate()
-		.map(|(i, line)| {
-			// Line indices in a `FileBlame` are 1-based.
-			let corresponding_hunk = blame.get_line(i + 1);
-
-			if let Some(hunk) = corresponding_hunk {
-				let commit_id = CommitId::new(hunk.final_commit_id());
-				// Line indices in a `BlameHunk` are 1-based.
-				let start_line =
-					hunk.final_start_line().saturating_sub(1);
-				let end_line =
-					start_line.saturating_add(hunk.lines_in_hunk());
-
-				if let Some(commit_info) =
-					unique_commit_infos.get(&commit_id)
-				{
-					let hunk = BlameHunk {
-						commit_id,
+	let lines: Vec<(Option<BlameHunk>, String)> = attributions
+		.into_iter()
+		.zip(lines)
+		.map(|(attribution, line)| {
+			let hunk = attribution.and_then(|attribution| {
+				unique_commit_infos.get(&attribution.commit_id).map(
+					|commit_info| BlameHunk {
+						commit_id: attribution.commit_id,
 						author: commit_info.author.clone(),
 						time: commit_info.time,
-						start_line,
-						end_line,
-					};
-
-					return (
-						Some(hunk),
-						line.unwrap_or_else(|_| String::new()),
-					);
-				}
-			}
+						start_line: attribution.start_line,
+						end_line: attribution.end_line,
+						orig_start_line: attribution.orig_start_line,
+						orig_path: fixup_windows_path(
+							&attribution.orig_path.to_string_lossy(),
+						),
+					},
+				)
+			});
 
-			(None, line.unwrap_or_else(|_| String::new()))
+			(hunk, line)
 		})
 		.collect();
 
@@ -151,7 +524,10 @@ mod tests {
 	use super::*;
 	use crate::{
 		error::Result,
-		sync::{commit, stage_add_file, tests::repo_init_empty},
+		sync::{
+			commit, stage_add_file, stage_addremoved,
+			tests::{repo_init_empty, write_commit_file},
+		},
 	};
 	use std::{
 		fs::{File, OpenOptions},
@@ -167,14 +543,17 @@ mod tests {
 		let repo_path: &RepoPath =
 			&root.as_os_str().to_str().unwrap().into();
 
-		assert!(matches!(blame_file(repo_path, "foo", None), Err(_)));
+		assert!(matches!(
+			blame_file(repo_path, "foo", None, &[]),
+			Err(_)
+		));
 
 		File::create(root.join(file_path))?.write_all(b"line 1\n")?;
 
 		stage_add_file(repo_path, file_path)?;
 		commit(repo_path, "first commit")?;
 
-		let blame = blame_file(repo_path, "foo", None)?;
+		let blame = blame_file(repo_path, "foo", None, &[])?;
 
 		assert!(matches!(
 			blame.lines.as_slice(),
@@ -198,7 +577,7 @@ mod tests {
 		stage_add_file(repo_path, file_path)?;
 		commit(repo_path, "second commit")?;
 
-		let blame = blame_file(repo_path, "foo", None)?;
+		let blame = blame_file(repo_path, "foo", None, &[])?;
 
 		assert!(matches!(
 			blame.lines.as_slice(),
@@ -225,14 +604,14 @@ mod tests {
 
 		file.write(b"line 3\n")?;
 
-		let blame = blame_file(repo_path, "foo", None)?;
+		let blame = blame_file(repo_path, "foo", None, &[])?;
 
 		assert_eq!(blame.lines.len(), 2);
 
 		stage_add_file(repo_path, file_path)?;
 		commit(repo_path, "third commit")?;
 
-		let blame = blame_file(repo_path, "foo", None)?;
+		let blame = blame_file(repo_path, "foo", None, &[])?;
 
 		assert_eq!(blame.lines.len(), 3);
 
@@ -257,6 +636,81 @@ mod tests {
 		stage_add_file(repo_path, file_path).unwrap();
 		commit(repo_path, "first commit").unwrap();
 
-		assert!(blame_file(repo_path, "bar\\foo", None).is_ok());
+		assert!(blame_file(repo_path, "bar\\foo", None, &[]).is_ok());
+	}
+
+	#[test]
+	fn test_blame_ignore_revs() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first =
+			write_commit_file(&repo, "foo", "a\nb\n", "first");
+		let reformat =
+			write_commit_file(&repo, "foo", "A\nb\n", "reformat");
+		let third =
+			write_commit_file(&repo, "foo", "A\nb\nc\n", "third");
+		let shift =
+			write_commit_file(&repo, "foo", "x\nA\nb\nc\n", "shift");
+
+		let commits = |blame: FileBlame| {
+			blame
+				.lines
+				.into_iter()
+				.map(|(hunk, _)| hunk.unwrap().commit_id)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			commits(blame_file(repo_path, "foo", None, &[])?),
+			vec![shift, reformat, first, third]
+		);
+		assert_eq!(
+			commits(blame_file(repo_path, "foo", None, &[reformat])?),
+			vec![shift, first, first, third]
+		);
+
+		// "A" is the second line at HEAD but the first in `reformat`
+		let blame = blame_file(repo_path, "foo", None, &[])?;
+		let hunk = blame.lines[1].0.clone().unwrap();
+		assert_eq!((hunk.start_line, hunk.orig_start_line), (1, 0));
+
+		assert_eq!(
+			blame_parent(repo_path, reformat, "foo", 0)?,
+			Some((first, String::from("foo"), 0))
+		);
+		assert_eq!(blame_parent(repo_path, first, "foo", 0)?, None);
+
+		std::fs::rename(root.join("foo"), root.join("bar"))?;
+		stage_addremoved(repo_path, Path::new("foo"))?;
+		stage_add_file(repo_path, Path::new("bar"))?;
+		let rename = commit(repo_path, "rename")?;
+
+		let blame = blame_file(repo_path, "bar", None, &[])?;
+		let hunk = blame.lines[1].0.clone().unwrap();
+		assert_eq!(hunk.commit_id, reformat);
+		assert_eq!(hunk.orig_path, "foo");
+
+		assert_eq!(
+			blame_parent(repo_path, rename, "bar", 1)?,
+			Some((shift, String::from("foo"), 1))
+		);
+
+		assert!(blame_ignore_revs(repo_path)?.is_empty());
+
+		File::create(root.join(".git-blame-ignore-revs"))?
+			.write_all(
+				format!("# reformat\n{reformat}\n").as_bytes(),
+			)?;
+		repo.config()?.set_str(
+			"blame.ignoreRevsFile",
+			".git-blame-ignore-revs",
+		)?;
+
+		assert_eq!(blame_ignore_revs(repo_path)?, vec![reformat]);
+
+		Ok(())
 	}
 }
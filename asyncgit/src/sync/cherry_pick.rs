@@ -0,0 +1,358 @@
+//! cherry-pick commits onto HEAD
+//!
+//! picking multiple commits is not supported by libgit2, so the
+//! commits still to pick are remembered in `.git/gitui-cherry-pick`
+//! (first line is the HEAD before picking, used to abort).
+
+use super::{
+	commit::signature_allow_undefined_name, repository::repo,
+	utils::get_head_repo, CommitId, RepoPath,
+};
+use crate::{
+	error::{Error, Result},
+	sync::utils::read_file,
+};
+use git2::{Commit, Oid, Repository, RepositoryState, ResetType};
+use scopetime::scope_time;
+use std::{fs, path::PathBuf};
+
+const GIT_CHERRY_PICK_HEAD_FILE: &str = "CHERRY_PICK_HEAD";
+const TODO_FILE: &str = "gitui-cherry-pick";
+
+/// cherry-picks `commits` (oldest first) onto HEAD, committing
+/// each of them. stops leaving the repo in the cherry-pick state
+/// as soon as a commit does not apply cleanly.
+///
+/// if picking fails otherwise (e.g. local changes in the way), the
+/// commits picked so far stay and the remaining ones are dropped.
+pub fn cherry_pick(
+	repo_path: &RepoPath,
+	commits: &[CommitId],
+) -> Result<()> {
+	scope_time!("cherry_pick");
+
+	let repo = repo(repo_path)?;
+
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(String::from(
+			"cannot cherry-pick while repo is not in a clean state",
+		)));
+	}
+
+	for id in commits {
+		if repo.find_commit((*id).into())?.parent_count() > 1 {
+			return Err(Error::Generic(format!(
+				"cannot cherry-pick merge commit: {}",
+				id.get_short_string()
+			)));
+		}
+	}
+
+	write_todo(&repo, get_head_repo(&repo)?, commits)?;
+
+	pick_remaining(&repo)
+}
+
+/// commits the resolved pending cherry-pick and picks the remaining commits
+pub fn continue_cherry_pick(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("continue_cherry_pick");
+
+	let repo = repo(repo_path)?;
+
+	if repo.index()?.has_conflicts() {
+		return Err(Error::Generic(String::from(
+			"resolve all conflicts before continuing",
+		)));
+	}
+
+	let commit =
+		repo.find_commit(cherry_pick_head(repo_path)?.into())?;
+
+	commit_picked(&repo, &commit)?;
+
+	pick_remaining(&repo)
+}
+
+/// resets to HEAD before the cherry-pick was started
+pub fn abort_cherry_pick(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("abort_cherry_pick");
+
+	let repo = repo(repo_path)?;
+
+	let orig_head = match read_todo(&repo)? {
+		Some((orig_head, _)) => orig_head,
+		None => get_head_repo(&repo)?,
+	};
+
+	let orig_head = repo.find_object(orig_head.into(), None)?;
+	repo.reset(&orig_head, ResetType::Hard, None)?;
+
+	repo.cleanup_state()?;
+	remove_todo(&repo)?;
+
+	Ok(())
+}
+
+/// commit currently being cherry-picked
+pub fn cherry_pick_head(repo_path: &RepoPath) -> Result<CommitId> {
+	scope_time!("cherry_pick_head");
+
+	let path =
+		repo(repo_path)?.path().join(GIT_CHERRY_PICK_HEAD_FILE);
+
+	let file_content = read_file(&path)?;
+
+	let id = Oid::from_str(file_content.trim())?;
+
+	Ok(id.into())
+}
+
+fn todo_path(repo: &Repository) -> PathBuf {
+	repo.path().join(TODO_FILE)
+}
+
+fn write_todo(
+	repo: &Repository,
+	orig_head: CommitId,
+	commits: &[CommitId],
+) -> Result<()> {
+	let content = std::iter::once(orig_head)
+		.chain(commits.iter().copied())
+		.map(|id| id.to_string())
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	fs::write(todo_path(repo), content)?;
+
+	Ok(())
+}
+
+fn read_todo(
+	repo: &Repository,
+) -> Result<Option<(CommitId, Vec<CommitId>)>> {
+	let path = todo_path(repo);
+
+	if !path.is_file() {
+		return Ok(None);
+	}
+
+	let ids = read_file(&path)?
+		.lines()
+		.map(|line| Ok(Oid::from_str(line.trim())?.into()))
+		.collect::<Result<Vec<CommitId>>>()?;
+
+	Ok(ids.split_first().map(|(head, rest)| (*head, rest.to_vec())))
+}
+
+fn remove_todo(repo: &Repository) -> Result<()> {
+	let path = todo_path(repo);
+
+	if path.is_file() {
+		fs::remove_file(path)?;
+	}
+
+	Ok(())
+}
+
+fn pick_remaining(repo: &Repository) -> Result<()> {
+	let Some((orig_head, mut todo)) = read_todo(repo)? else {
+		return Ok(());
+	};
+
+	while !todo.is_empty() {
+		let id = todo.remove(0);
+
+		write_todo(repo, orig_head, &todo)?;
+
+		let commit = repo.find_commit(id.into())?;
+
+		if let Err(e) = repo.cherrypick(&commit, None) {
+			remove_todo(repo)?;
+
+			return Err(Error::Generic(format!(
+				"cherry-pick of {} failed: {e}\nnot picked: {}",
+				id.get_short_string(),
+				std::iter::once(id)
+					.chain(todo)
+					.map(|id| id.get_short_string())
+					.collect::<Vec<_>>()
+					.join(", ")
+			)));
+		}
+
+		if repo.index()?.has_conflicts() {
+			return Ok(());
+		}
+
+		commit_picked(repo, &commit)?;
+	}
+
+	remove_todo(repo)
+}
+
+/// commits the index keeping author and message of `picked`,
+/// skips the commit if it would be empty
+fn commit_picked(repo: &Repository, picked: &Commit) -> Result<()> {
+	let tree_id = repo.index()?.write_tree()?;
+	let head = repo.head()?.peel_to_commit()?;
+
+	if tree_id != head.tree_id() {
+		let tree = repo.find_tree(tree_id)?;
+		let signature = signature_allow_undefined_name(repo)?;
+
+		repo.commit(
+			Some("HEAD"),
+			&picked.author(),
+			&signature,
+			picked.message().unwrap_or_default(),
+			&tree,
+			&[&head],
+		)?;
+	}
+
+	repo.cleanup_state()?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch, get_commit_info, repo_state,
+		stage_add_file,
+		tests::{get_commit_ids, repo_init, write_commit_file},
+		utils::{repo_read_file, repo_write_file},
+		RepoState,
+	};
+	use pretty_assertions::assert_eq;
+	use std::path::Path;
+
+	#[test]
+	fn test_cherry_pick_range() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "other").unwrap();
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+
+		checkout_branch(repo_path, "master").unwrap();
+
+		cherry_pick(repo_path, &[c1, c2]).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+		let ids = get_commit_ids(&repo, 10);
+		assert_eq!(ids.len(), 3);
+		assert_eq!(
+			get_commit_info(repo_path, &ids[0]).unwrap().message,
+			"c2"
+		);
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a");
+		assert!(!todo_path(&repo).exists());
+	}
+
+	#[test]
+	fn test_cherry_pick_conflict_continue() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "other").unwrap();
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "a.txt", "x", "conflicting");
+
+		cherry_pick(repo_path, &[c1, c2]).unwrap();
+
+		assert_eq!(
+			repo_state(repo_path).unwrap(),
+			RepoState::CherryPick
+		);
+		assert_eq!(cherry_pick_head(repo_path).unwrap(), c1);
+		assert!(continue_cherry_pick(repo_path).is_err());
+
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		continue_cherry_pick(repo_path).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+		let ids = get_commit_ids(&repo, 10);
+		assert_eq!(ids.len(), 4);
+		assert_eq!(
+			get_commit_info(repo_path, &ids[1]).unwrap().message,
+			"c1"
+		);
+	}
+
+	#[test]
+	fn test_cherry_pick_abort() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "other").unwrap();
+		let c1 = write_commit_file(&repo, "b.txt", "b", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "a", "c2");
+
+		checkout_branch(repo_path, "master").unwrap();
+		let head =
+			write_commit_file(&repo, "a.txt", "x", "conflicting");
+
+		cherry_pick(repo_path, &[c1, c2]).unwrap();
+
+		assert_eq!(
+			repo_state(repo_path).unwrap(),
+			RepoState::CherryPick
+		);
+
+		abort_cherry_pick(repo_path).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(get_head_repo(&repo).unwrap(), head);
+		assert!(!root.join("b.txt").exists());
+		assert!(!todo_path(&repo).exists());
+	}
+
+	#[test]
+	fn test_cherry_pick_failing() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "b.txt", "b", "base");
+
+		create_branch(repo_path, "other").unwrap();
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b2", "c2");
+
+		checkout_branch(repo_path, "master").unwrap();
+		repo_write_file(&repo, "b.txt", "local").unwrap();
+
+		let err = cherry_pick(repo_path, &[c1, c2]).unwrap_err();
+
+		assert!(err.to_string().contains(&c2.get_short_string()));
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert!(!todo_path(&repo).exists());
+		assert_eq!(
+			get_commit_info(
+				repo_path,
+				&get_head_repo(&repo).unwrap()
+			)
+			.unwrap()
+			.message,
+			"c1"
+		);
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "local");
+	}
+}
@@ -12,6 +12,8 @@ pub struct CommitSignature {
 	pub email: String,
 	/// time in secs since Unix epoch
 	pub time: i64,
+	/// timezone offset in minutes
+	pub offset: i32,
 }
 
 impl CommitSignature {
@@ -22,6 +24,7 @@ impl CommitSignature {
 			email: s.email().unwrap_or("").to_string(),
 
 			time: s.when().seconds(),
+			offset: s.when().offset_minutes(),
 		}
 	}
 }
@@ -107,9 +107,7 @@ pub fn get_compare_commits_diff(
 
 	let mut opts = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opts.context_lines(options.context);
-		opts.ignore_whitespace(options.ignore_whitespace);
-		opts.interhunk_lines(options.interhunk_lines);
+		options.apply(&mut opts);
 	}
 	if let Some(p) = &pathspec {
 		opts.pathspec(p.clone());
@@ -147,9 +145,7 @@ pub(crate) fn get_commit_diff<'a>(
 
 	let mut opts = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opts.context_lines(options.context);
-		opts.ignore_whitespace(options.ignore_whitespace);
-		opts.interhunk_lines(options.interhunk_lines);
+		options.apply(&mut opts);
 	}
 	if let Some(p) = &pathspec {
 		opts.pathspec(p.clone());
@@ -2,8 +2,13 @@ use super::{commit_files::get_commit_diff, CommitId};
 use crate::error::Result;
 use bitflags::bitflags;
 use fuzzy_matcher::FuzzyMatcher;
-use git2::{Diff, Repository};
-use std::sync::Arc;
+use git2::{Delta, Diff, DiffFindOptions, Repository};
+use regex::{Regex, RegexBuilder};
+use std::{
+	collections::HashMap,
+	path::Path,
+	sync::{Arc, Mutex},
+};
 
 ///
 pub type SharedCommitFilterFn = Arc<
@@ -31,9 +36,104 @@ pub fn diff_contains_file(file_path: String) -> SharedCommitFilterFn {
 	))
 }
 
+/// path a followed file had in each commit of its history,
+/// see [`diff_contains_file_follow`]
+pub type SharedFilePaths = Arc<Mutex<HashMap<CommitId, String>>>;
+
+/// called when a walk over the commits starts,
+/// see [`LogWalker::on_walk_start`](super::LogWalker::on_walk_start)
+pub type SharedWalkStartFn =
+	Arc<Box<dyn Fn() -> Result<()> + Send + Sync>>;
+
+/// like [`diff_contains_file`] but continues with the old path of the
+/// file once the commit renaming it is found (`git log --follow`)
+///
+/// expects the commits newest first, the path the file had in every
+/// matching commit is recorded in the returned map. the returned
+/// [`SharedWalkStartFn`] has to be called before every walk to start
+/// over with `file_path`.
+pub fn diff_contains_file_follow(
+	file_path: String,
+) -> (SharedCommitFilterFn, SharedWalkStartFn, SharedFilePaths) {
+	let paths = SharedFilePaths::default();
+	let path = Arc::new(Mutex::new(file_path.clone()));
+
+	let start_path = Arc::clone(&path);
+	let start_paths = Arc::clone(&paths);
+	let walk_start: SharedWalkStartFn =
+		Arc::new(Box::new(move || {
+			start_path.lock()?.clone_from(&file_path);
+			start_paths.lock()?.clear();
+			Ok(())
+		}));
+
+	let filter_paths = Arc::clone(&paths);
+	let filter: SharedCommitFilterFn = Arc::new(Box::new(
+		move |repo: &Repository,
+		      commit_id: &CommitId|
+		      -> Result<bool> {
+			let mut path = path.lock()?;
+
+			let diff = get_commit_diff(
+				repo,
+				*commit_id,
+				Some(path.clone()),
+				None,
+				None,
+			)?;
+
+			let Some(status) =
+				diff.deltas().next().map(|d| d.status())
+			else {
+				return Ok(false);
+			};
+
+			filter_paths.lock()?.insert(*commit_id, path.clone());
+
+			if status == Delta::Added {
+				if let Some(old_path) =
+					renamed_from(repo, *commit_id, &path)?
+				{
+					*path = old_path;
+				}
+			}
+
+			Ok(true)
+		},
+	));
+
+	(filter, walk_start, paths)
+}
+
+/// old path of the file at `path` if `commit_id` renamed it
+fn renamed_from(
+	repo: &Repository,
+	commit_id: CommitId,
+	path: &str,
+) -> Result<Option<String>> {
+	let mut diff =
+		get_commit_diff(repo, commit_id, None, None, None)?;
+	diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+	let old_path = diff
+		.deltas()
+		.find(|delta| {
+			delta.status() == Delta::Renamed
+				&& delta.new_file().path() == Some(Path::new(path))
+		})
+		.and_then(|delta| {
+			delta
+				.old_file()
+				.path()
+				.map(|p| p.to_string_lossy().into_owned())
+		});
+
+	Ok(old_path)
+}
+
 bitflags! {
 	///
-	#[derive(Debug, Clone, Copy)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 	pub struct SearchFields: u32 {
 		///
 		const MESSAGE_SUMMARY = 1 << 0;
@@ -43,12 +143,13 @@ bitflags! {
 		const FILENAMES = 1 << 2;
 		///
 		const AUTHORS = 1 << 3;
-		//TODO:
-		// const COMMIT_HASHES = 1 << 3;
-		// ///
-		// const DATES = 1 << 4;
-		// ///
-		// const DIFFS = 1 << 5;
+		/// commit hashes starting with the search pattern
+		const COMMIT_HASHES = 1 << 4;
+		/// commit dates within the range given as search pattern,
+		/// see [`DateRange`]
+		const DATES = 1 << 5;
+		/// added or removed lines of the diff (pickaxe)
+		const DIFFS = 1 << 6;
 	}
 }
 
@@ -66,6 +167,8 @@ bitflags! {
 		const CASE_SENSITIVE = 1 << 0;
 		///
 		const FUZZY_SEARCH = 1 << 1;
+		/// search pattern is a regular expression
+		const REGEX = 1 << 2;
 	}
 }
 
@@ -86,6 +189,98 @@ pub struct LogFilterSearchOptions {
 	pub options: SearchOptions,
 }
 
+impl LogFilterSearchOptions {
+	/// compiles the search pattern, `None` unless in `REGEX` mode
+	pub fn regex(&self) -> Result<Option<Regex>> {
+		if !self.options.contains(SearchOptions::REGEX) {
+			return Ok(None);
+		}
+
+		let regex = RegexBuilder::new(&self.search_pattern)
+			.case_insensitive(
+				!self.options.contains(SearchOptions::CASE_SENSITIVE),
+			)
+			.build()?;
+
+		Ok(Some(regex))
+	}
+}
+
+/// inclusive range of days to match commit dates against
+///
+/// parsed from `YYYY-MM-DD..YYYY-MM-DD`, either side can be left out
+/// to leave the range open, a single date matches just that day
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+	/// first day (days since unix epoch)
+	pub since: Option<i64>,
+	/// last day (days since unix epoch)
+	pub until: Option<i64>,
+}
+
+impl DateRange {
+	///
+	pub fn parse(text: &str) -> Option<Self> {
+		let text = text.trim();
+
+		let (since, until) = text
+			.split_once("..")
+			.map_or((text, text), |(since, until)| {
+				(since.trim(), until.trim())
+			});
+
+		let parse_side = |side: &str| {
+			if side.is_empty() {
+				Some(None)
+			} else {
+				parse_day(side).map(Some)
+			}
+		};
+
+		let range = Self {
+			since: parse_side(since)?,
+			until: parse_side(until)?,
+		};
+
+		(range != Self::default()).then_some(range)
+	}
+
+	/// `time` is matched against the day in its own timezone
+	pub fn contains(&self, time: git2::Time) -> bool {
+		let seconds =
+			time.seconds() + i64::from(time.offset_minutes()) * 60;
+		let day = seconds.div_euclid(SECONDS_PER_DAY);
+
+		self.since.map_or(true, |since| day >= since)
+			&& self.until.map_or(true, |until| day <= until)
+	}
+}
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// days since unix epoch of a `YYYY-MM-DD` date
+fn parse_day(text: &str) -> Option<i64> {
+	let mut parts = text.splitn(3, '-');
+	let year: i64 = parts.next()?.parse().ok()?;
+	let month: i64 = parts.next()?.parse().ok()?;
+	let day: i64 = parts.next()?.parse().ok()?;
+
+	if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+		return None;
+	}
+
+	// see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4
+		- year_of_era / 100
+		+ day_of_year;
+
+	Some(era * 146_097 + day_of_era - 719_468)
+}
+
 ///
 #[derive(Default)]
 pub struct LogFilterSearch {
@@ -93,22 +288,68 @@ pub struct LogFilterSearch {
 	pub matcher: fuzzy_matcher::skim::SkimMatcherV2,
 	///
 	pub options: LogFilterSearchOptions,
+	///
+	pub dates: Option<DateRange>,
+	/// compiled pattern in `REGEX` mode, `None` if it is invalid
+	pub regex: Option<Regex>,
 }
 
 impl LogFilterSearch {
 	///
 	pub fn new(options: LogFilterSearchOptions) -> Self {
 		let mut options = options;
+		let regex = options.regex().ok().flatten();
 		if !options.options.contains(SearchOptions::CASE_SENSITIVE) {
 			options.search_pattern =
 				options.search_pattern.to_lowercase();
 		}
+		let dates = options
+			.fields
+			.contains(SearchFields::DATES)
+			.then(|| DateRange::parse(&options.search_pattern))
+			.flatten();
 		Self {
 			matcher: fuzzy_matcher::skim::SkimMatcherV2::default(),
 			options,
+			dates,
+			regex,
 		}
 	}
 
+	fn match_hash(&self, commit_id: &CommitId) -> bool {
+		let pattern = self.options.search_pattern.trim();
+
+		!pattern.is_empty()
+			&& commit_id
+				.to_string()
+				.starts_with(pattern.to_lowercase().as_str())
+	}
+
+	/// whether any added or removed line matches, like `git log -G`
+	fn match_diff_lines(&self, diff: &Diff<'_>) -> bool {
+		let mut found = false;
+
+		// aborting the iteration on the first match makes
+		// `foreach` return an error, so it is ignored on purpose
+		let _ = diff.foreach(
+			&mut |_, _| true,
+			None,
+			None,
+			Some(&mut |_, _, line| {
+				if matches!(line.origin(), '+' | '-')
+					&& std::str::from_utf8(line.content()).is_ok_and(
+						|line| self.match_text(line.trim_end()),
+					) {
+					found = true;
+				}
+
+				!found
+			}),
+		);
+
+		found
+	}
+
 	fn match_diff(&self, diff: &Diff<'_>) -> bool {
 		diff.deltas().any(|delta| {
 			if delta
@@ -130,7 +371,14 @@ impl LogFilterSearch {
 
 	///
 	pub fn match_text(&self, text: &str) -> bool {
-		if self.options.options.contains(SearchOptions::FUZZY_SEARCH)
+		if self.options.options.contains(SearchOptions::REGEX) {
+			self.regex
+				.as_ref()
+				.is_some_and(|regex| regex.is_match(text))
+		} else if self
+			.options
+			.options
+			.contains(SearchOptions::FUZZY_SEARCH)
 		{
 			self.matcher
 				.fuzzy_match(
@@ -181,18 +429,43 @@ pub fn filter_commit_by_search(
 				.flatten()
 				.unwrap_or_default();
 
-			let file_match = filter
+			let diff = filter
 				.options
 				.fields
-				.contains(SearchFields::FILENAMES)
+				.intersects(
+					SearchFields::FILENAMES | SearchFields::DIFFS,
+				)
 				.then(|| {
 					get_commit_diff(
 						repo, *commit_id, None, None, None,
 					)
 					.ok()
 				})
-				.flatten()
-				.is_some_and(|diff| filter.match_diff(&diff));
+				.flatten();
+
+			let file_match = filter
+				.options
+				.fields
+				.contains(SearchFields::FILENAMES)
+				&& diff
+					.as_ref()
+					.is_some_and(|diff| filter.match_diff(diff));
+
+			let diff_match =
+				filter.options.fields.contains(SearchFields::DIFFS)
+					&& diff.as_ref().is_some_and(|diff| {
+						filter.match_diff_lines(diff)
+					});
+
+			let hash_match = filter
+				.options
+				.fields
+				.contains(SearchFields::COMMIT_HASHES)
+				&& filter.match_hash(commit_id);
+
+			let date_match = filter
+				.dates
+				.is_some_and(|dates| dates.contains(commit.time()));
 
 			let authors_match = filter
 				.options
@@ -214,7 +487,11 @@ pub fn filter_commit_by_search(
 
 			Ok(msg_summary_match
 				|| msg_body_match
-				|| file_match || authors_match)
+				|| file_match
+				|| authors_match
+				|| diff_match
+				|| hash_match
+				|| date_match)
 		},
 	))
 }
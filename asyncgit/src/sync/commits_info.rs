@@ -1,8 +1,13 @@
 use std::fmt::Display;
 
 use super::RepoPath;
-use crate::{error::Result, sync::repository::repo};
-use git2::{Commit, Error, Oid};
+use crate::{
+	error::{Error as GitUiError, Result},
+	sync::repository::repo,
+};
+use git2::{
+	Commit, Error, ErrorCode, Object, Oid, Repository, RevparseMode,
+};
 use scopetime::scope_time;
 use unicode_truncate::UnicodeTruncateStr;
 
@@ -46,6 +51,87 @@ impl CommitId {
 		let commit_obj = repo.revparse_single(revision)?;
 		Ok(commit_obj.id().into())
 	}
+
+	/// resolves a revspec selecting a set of commits like
+	/// `main..feature`, `v1.0...HEAD`, `HEAD~50` or `feature ^main`
+	pub fn from_revision_range(
+		repo_path: &RepoPath,
+		revspec: &str,
+	) -> Result<RevisionRange> {
+		scope_time!("CommitId::from_revision_range");
+
+		let repo = repo(repo_path)?;
+
+		revision_range(&repo, revspec)
+	}
+}
+
+/// commits selected by a revspec, see
+/// [`CommitId::from_revision_range`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevisionRange {
+	/// commits whose history is included
+	pub include: Vec<CommitId>,
+	/// commits whose history is excluded
+	pub exclude: Vec<CommitId>,
+}
+
+fn peel_commit(obj: &Object) -> Result<CommitId> {
+	Ok(obj.peel_to_commit()?.id().into())
+}
+
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn revision_range(
+	repo: &Repository,
+	revspec: &str,
+) -> Result<RevisionRange> {
+	let mut range = RevisionRange::default();
+
+	for spec in revspec.split_whitespace() {
+		if let Some(spec) = spec.strip_prefix('^') {
+			range
+				.exclude
+				.push(peel_commit(&repo.revparse_single(spec)?)?);
+			continue;
+		}
+
+		let parsed = repo.revparse(spec)?;
+		let from = parsed.from().map(peel_commit).transpose()?;
+		let to = parsed.to().map(peel_commit).transpose()?;
+
+		if parsed.mode().contains(RevparseMode::SINGLE) {
+			range.include.extend(from);
+			continue;
+		}
+
+		// an omitted side of a range means `HEAD`
+		let head = || peel_commit(&repo.revparse_single("HEAD")?);
+		let from = from.map_or_else(head, Ok)?;
+		let to = to.map_or_else(head, Ok)?;
+
+		if parsed.mode().contains(RevparseMode::MERGE_BASE) {
+			range.include.extend([from, to]);
+
+			match repo.merge_bases(from.into(), to.into()) {
+				Ok(bases) => range
+					.exclude
+					.extend(bases.iter().copied().map(CommitId::new)),
+				Err(e) if e.code() == ErrorCode::NotFound => (),
+				Err(e) => return Err(e.into()),
+			}
+		} else {
+			range.include.push(to);
+			range.exclude.push(from);
+		}
+	}
+
+	if range.include.is_empty() {
+		return Err(GitUiError::Generic(format!(
+			"revspec selects no commits: '{revspec}'"
+		)));
+	}
+
+	Ok(range)
 }
 
 impl Display for CommitId {
@@ -157,12 +243,14 @@ pub fn get_message(
 
 #[cfg(test)]
 mod tests {
-	use super::get_commits_info;
+	use super::{get_commits_info, RevisionRange};
 	use crate::{
 		error::Result,
 		sync::{
-			commit, stage_add_file, tests::repo_init_empty,
-			utils::get_head_repo, CommitId, RepoPath,
+			checkout_branch, commit, create_branch, stage_add_file,
+			tests::{repo_init_empty, write_commit_file},
+			utils::get_head_repo,
+			CommitId, RepoPath,
 		},
 	};
 	use std::{fs::File, io::Write, path::Path};
@@ -267,4 +355,62 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_get_revision_range() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "foo", "a", "c1");
+		create_branch(repo_path, "feature").unwrap();
+		let c2 = write_commit_file(&repo, "foo", "b", "c2");
+		checkout_branch(repo_path, "master").unwrap();
+		let c3 = write_commit_file(&repo, "foo", "c", "c3");
+
+		let range = |spec| {
+			CommitId::from_revision_range(repo_path, spec).unwrap()
+		};
+
+		assert_eq!(
+			range("master..feature"),
+			RevisionRange {
+				include: vec![c2],
+				exclude: vec![c3],
+			}
+		);
+		assert_eq!(
+			range("feature ^master"),
+			RevisionRange {
+				include: vec![c2],
+				exclude: vec![c3],
+			}
+		);
+		assert_eq!(
+			range("feature...master"),
+			RevisionRange {
+				include: vec![c2, c3],
+				exclude: vec![c1],
+			}
+		);
+		assert_eq!(
+			range("HEAD~1"),
+			RevisionRange {
+				include: vec![c1],
+				exclude: vec![],
+			}
+		);
+
+		assert!(CommitId::from_revision_range(
+			repo_path,
+			"master..nope"
+		)
+		.is_err());
+		assert!(CommitId::from_revision_range(repo_path, "^master")
+			.is_err());
+		assert!(
+			CommitId::from_revision_range(repo_path, " ").is_err()
+		);
+	}
 }
@@ -0,0 +1,550 @@
+//! three-way merge conflict resolution
+
+use super::{
+	repository::repo, stage_add_file, utils::stage_addremoved,
+	RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	DiffOptions, IndexConflict, IndexEntry, Patch, Repository,
+};
+use scopetime::scope_time;
+use std::{fs, io, path::Path};
+
+/// part of a conflicted file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictSection {
+	/// lines that merged cleanly
+	Resolved(Vec<String>),
+	/// lines changed differently on both sides
+	Conflict {
+		///
+		base: Vec<String>,
+		///
+		ours: Vec<String>,
+		///
+		theirs: Vec<String>,
+	},
+}
+
+/// how to resolve a single conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+	/// use our version
+	Ours,
+	/// use their version
+	Theirs,
+	/// use our version followed by theirs
+	Both,
+}
+
+/// conflicted file split into cleanly merged and conflicting sections
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileConflict {
+	///
+	pub path: String,
+	///
+	pub sections: Vec<ConflictSection>,
+}
+
+impl FileConflict {
+	/// number of conflicting sections
+	pub fn conflicts(&self) -> usize {
+		self.sections
+			.iter()
+			.filter(|s| matches!(s, ConflictSection::Conflict { .. }))
+			.count()
+	}
+
+	/// merged content using one resolution per conflicting section
+	pub fn resolve(
+		&self,
+		resolutions: &[ConflictResolution],
+	) -> Result<String> {
+		if resolutions.len() != self.conflicts() {
+			return Err(Error::Generic(format!(
+				"expected {} resolutions, got {}",
+				self.conflicts(),
+				resolutions.len()
+			)));
+		}
+
+		let mut resolutions = resolutions.iter();
+		let mut content = String::new();
+
+		for section in &self.sections {
+			match section {
+				ConflictSection::Resolved(lines) => {
+					content.extend(lines.iter().map(String::as_str));
+				}
+				ConflictSection::Conflict {
+					ours, theirs, ..
+				} => {
+					let (first, second): (&[String], &[String]) =
+						match resolutions.next() {
+							Some(ConflictResolution::Ours) => {
+								(ours, &[])
+							}
+							Some(ConflictResolution::Theirs) => {
+								(theirs, &[])
+							}
+							Some(ConflictResolution::Both) | None => {
+								(ours, theirs)
+							}
+						};
+
+					content.extend(
+						first
+							.iter()
+							.chain(second)
+							.map(String::as_str),
+					);
+				}
+			}
+		}
+
+		Ok(content)
+	}
+}
+
+/// loads ancestor/ours/theirs stages of the conflicted `path`
+/// from the index and merges them line by line
+pub fn get_file_conflict(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<FileConflict> {
+	scope_time!("get_file_conflict");
+
+	let repo = repo(repo_path)?;
+
+	let conflict = find_conflict(&repo, path)?;
+
+	let base = stage_content(&repo, conflict.ancestor.as_ref())?;
+	let ours = stage_content(&repo, conflict.our.as_ref())?;
+	let theirs = stage_content(&repo, conflict.their.as_ref())?;
+
+	Ok(FileConflict {
+		path: path.to_string(),
+		sections: merge(&base, &ours, &theirs)?,
+	})
+}
+
+/// writes `path` resolved using `resolutions` and stages it,
+/// removes it if all resolutions pick a side that deleted the file
+pub fn resolve_file_conflict(
+	repo_path: &RepoPath,
+	path: &str,
+	resolutions: &[ConflictResolution],
+) -> Result<()> {
+	scope_time!("resolve_file_conflict");
+
+	let content =
+		get_file_conflict(repo_path, path)?.resolve(resolutions)?;
+
+	let repo = repo(repo_path)?;
+	let work_dir = repo.workdir().ok_or_else(|| {
+		Error::Generic(String::from("bare repository"))
+	})?;
+
+	let conflict = find_conflict(&repo, path)?;
+	let picks_deleted =
+		|side: ConflictResolution, entry: &Option<IndexEntry>| {
+			entry.is_none()
+				&& !resolutions.is_empty()
+				&& resolutions.iter().all(|r| *r == side)
+		};
+
+	if picks_deleted(ConflictResolution::Ours, &conflict.our)
+		|| picks_deleted(ConflictResolution::Theirs, &conflict.their)
+	{
+		match fs::remove_file(work_dir.join(path)) {
+			Err(e) if e.kind() != io::ErrorKind::NotFound => {
+				return Err(e.into());
+			}
+			_ => (),
+		}
+
+		return stage_addremoved(repo_path, Path::new(path));
+	}
+
+	fs::write(work_dir.join(path), content)?;
+
+	stage_add_file(repo_path, Path::new(path))
+}
+
+pub fn find_conflict(
+	repo: &Repository,
+	path: &str,
+) -> Result<IndexConflict> {
+	let is_path = |entry: &Option<IndexEntry>| {
+		entry.as_ref().is_some_and(|e| e.path == path.as_bytes())
+	};
+
+	for conflict in repo.index()?.conflicts()? {
+		let conflict = conflict?;
+
+		if is_path(&conflict.our)
+			|| is_path(&conflict.their)
+			|| is_path(&conflict.ancestor)
+		{
+			return Ok(conflict);
+		}
+	}
+
+	Err(Error::Generic(format!("no conflict found for: {path}")))
+}
+
+fn stage_content(
+	repo: &Repository,
+	entry: Option<&IndexEntry>,
+) -> Result<String> {
+	let Some(entry) = entry else {
+		return Ok(String::new());
+	};
+
+	let blob = repo.find_blob(entry.id)?;
+
+	if blob.is_binary() {
+		return Err(Error::Generic(String::from(
+			"cannot resolve conflicts of binary files",
+		)));
+	}
+
+	String::from_utf8(blob.content().to_vec()).map_err(|_| {
+		Error::Generic(String::from(
+			"cannot resolve conflicts of files that are not UTF-8",
+		))
+	})
+}
+
+/// line range in base and the corresponding range in the other side
+struct Change {
+	base_start: usize,
+	base_end: usize,
+	start: usize,
+	end: usize,
+}
+
+fn changes(base: &str, other: &str) -> Result<Vec<Change>> {
+	let mut opts = DiffOptions::new();
+	opts.context_lines(0);
+
+	let patch = Patch::from_buffers(
+		base.as_bytes(),
+		None,
+		other.as_bytes(),
+		None,
+		Some(&mut opts),
+	)?;
+
+	// hunk starts are 1-based, except for empty ranges
+	// which point at the line before
+	let start = |start: u32, lines: u32| {
+		if lines == 0 {
+			start as usize
+		} else {
+			start as usize - 1
+		}
+	};
+
+	(0..patch.num_hunks())
+		.map(|idx| {
+			let (hunk, _) = patch.hunk(idx)?;
+			let base_start =
+				start(hunk.old_start(), hunk.old_lines());
+			let other_start =
+				start(hunk.new_start(), hunk.new_lines());
+
+			Ok(Change {
+				base_start,
+				base_end: base_start + hunk.old_lines() as usize,
+				start: other_start,
+				end: other_start + hunk.new_lines() as usize,
+			})
+		})
+		.collect()
+}
+
+/// lines of one side for the base range `lo..hi`
+fn side_lines(
+	lines: &[&str],
+	base: &[&str],
+	changes: &[&Change],
+	lo: usize,
+	hi: usize,
+) -> Vec<String> {
+	let range = match (changes.first(), changes.last()) {
+		(Some(first), Some(last)) => {
+			let start = first.start - (first.base_start - lo);
+			let end = last.end + (hi - last.base_end);
+			&lines[start..end]
+		}
+		_ => &base[lo..hi],
+	};
+
+	range.iter().map(ToString::to_string).collect()
+}
+
+fn push_resolved(
+	sections: &mut Vec<ConflictSection>,
+	lines: Vec<String>,
+) {
+	if lines.is_empty() {
+		return;
+	}
+
+	if let Some(ConflictSection::Resolved(prev)) = sections.last_mut()
+	{
+		prev.extend(lines);
+	} else {
+		sections.push(ConflictSection::Resolved(lines));
+	}
+}
+
+fn merge(
+	base: &str,
+	ours: &str,
+	theirs: &str,
+) -> Result<Vec<ConflictSection>> {
+	let ours_changes = changes(base, ours)?;
+	let theirs_changes = changes(base, theirs)?;
+
+	let base_lines = base.split_inclusive('\n').collect::<Vec<_>>();
+	let ours_lines = ours.split_inclusive('\n').collect::<Vec<_>>();
+	let theirs_lines =
+		theirs.split_inclusive('\n').collect::<Vec<_>>();
+
+	let mut all = ours_changes
+		.iter()
+		.map(|c| (true, c))
+		.chain(theirs_changes.iter().map(|c| (false, c)))
+		.collect::<Vec<_>>();
+	all.sort_by_key(|(_, c)| (c.base_start, c.base_end));
+
+	let mut sections = Vec::new();
+	let mut base_pos = 0;
+	let mut idx = 0;
+
+	while idx < all.len() {
+		let lo = all[idx].1.base_start;
+		let mut hi = all[idx].1.base_end;
+
+		let mut end = idx + 1;
+		while end < all.len() && all[end].1.base_start <= hi {
+			hi = hi.max(all[end].1.base_end);
+			end += 1;
+		}
+
+		let group = &all[idx..end];
+		let ours_group = group
+			.iter()
+			.filter_map(|(is_ours, c)| is_ours.then_some(*c))
+			.collect::<Vec<_>>();
+		let theirs_group = group
+			.iter()
+			.filter_map(|(is_ours, c)| (!is_ours).then_some(*c))
+			.collect::<Vec<_>>();
+
+		push_resolved(
+			&mut sections,
+			side_lines(&base_lines, &base_lines, &[], base_pos, lo),
+		);
+
+		let ours =
+			side_lines(&ours_lines, &base_lines, &ours_group, lo, hi);
+		let theirs = side_lines(
+			&theirs_lines,
+			&base_lines,
+			&theirs_group,
+			lo,
+			hi,
+		);
+
+		if theirs_group.is_empty() || ours == theirs {
+			push_resolved(&mut sections, ours);
+		} else if ours_group.is_empty() {
+			push_resolved(&mut sections, theirs);
+		} else {
+			sections.push(ConflictSection::Conflict {
+				base: side_lines(
+					&base_lines,
+					&base_lines,
+					&[],
+					lo,
+					hi,
+				),
+				ours,
+				theirs,
+			});
+		}
+
+		base_pos = hi;
+		idx = end;
+	}
+
+	push_resolved(
+		&mut sections,
+		side_lines(
+			&base_lines,
+			&base_lines,
+			&[],
+			base_pos,
+			base_lines.len(),
+		),
+	);
+
+	Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, commit, create_branch, merge_branch,
+		tests::{repo_init, write_commit_file},
+		utils::repo_read_file,
+		BranchType,
+	};
+	use pretty_assertions::assert_eq;
+
+	fn lines(s: &[&str]) -> Vec<String> {
+		s.iter().map(ToString::to_string).collect()
+	}
+
+	#[test]
+	fn test_merge_sections() {
+		let sections = merge(
+			"a\nb\nc\nd\ne\n",
+			"a\nB2\nc\nd\ne\n",
+			"a\nB1\nc\nd\nE\n",
+		)
+		.unwrap();
+
+		assert_eq!(
+			sections,
+			vec![
+				ConflictSection::Resolved(lines(&["a\n"])),
+				ConflictSection::Conflict {
+					base: lines(&["b\n"]),
+					ours: lines(&["B2\n"]),
+					theirs: lines(&["B1\n"]),
+				},
+				ConflictSection::Resolved(lines(&[
+					"c\n", "d\n", "E\n"
+				])),
+			]
+		);
+	}
+
+	#[test]
+	fn test_merge_insertions() {
+		let sections =
+			merge("a\nb\n", "a\nx\nb\n", "y\na\nb\nz\n").unwrap();
+
+		assert_eq!(
+			sections,
+			vec![ConflictSection::Resolved(lines(&[
+				"y\n", "a\n", "x\n", "b\n", "z\n"
+			]))]
+		);
+	}
+
+	#[test]
+	fn test_resolve() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a\nb\nc\nd\ne\n", "base");
+
+		create_branch(repo_path, "other").unwrap();
+		write_commit_file(&repo, "a.txt", "a\nB1\nc\nd\nE\n", "c1");
+
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "a.txt", "a\nB2\nc\nd\ne\n", "c2");
+
+		merge_branch(repo_path, "other", BranchType::Local).unwrap();
+
+		assert!(super::repo(repo_path)
+			.unwrap()
+			.index()
+			.unwrap()
+			.has_conflicts());
+
+		let conflict = get_file_conflict(repo_path, "a.txt").unwrap();
+
+		assert_eq!(conflict.conflicts(), 1);
+		assert_eq!(
+			conflict.resolve(&[ConflictResolution::Both]).unwrap(),
+			"a\nB2\nB1\nc\nd\nE\n"
+		);
+		assert!(conflict.resolve(&[]).is_err());
+
+		resolve_file_conflict(
+			repo_path,
+			"a.txt",
+			&[ConflictResolution::Theirs],
+		)
+		.unwrap();
+
+		assert!(!super::repo(repo_path)
+			.unwrap()
+			.index()
+			.unwrap()
+			.has_conflicts());
+		assert_eq!(
+			repo_read_file(&repo, "a.txt").unwrap(),
+			"a\nB1\nc\nd\nE\n"
+		);
+	}
+
+	#[test]
+	fn test_resolve_deleted() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a\n", "base");
+		fs::write(root.join("b.txt"), b"caf\xe9\n").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		commit(repo_path, "latin-1").unwrap();
+
+		create_branch(repo_path, "other").unwrap();
+		fs::remove_file(root.join("a.txt")).unwrap();
+		stage_addremoved(repo_path, Path::new("a.txt")).unwrap();
+		fs::write(root.join("b.txt"), b"th\xe9\n").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		commit(repo_path, "c1").unwrap();
+
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "a.txt", "a2\n", "c2");
+		fs::write(root.join("b.txt"), b"cr\xe8me\n").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		commit(repo_path, "c3").unwrap();
+
+		merge_branch(repo_path, "other", BranchType::Local).unwrap();
+
+		assert!(get_file_conflict(repo_path, "b.txt").is_err());
+
+		let conflict = get_file_conflict(repo_path, "a.txt").unwrap();
+		assert_eq!(conflict.conflicts(), 1);
+
+		resolve_file_conflict(
+			repo_path,
+			"a.txt",
+			&[ConflictResolution::Theirs],
+		)
+		.unwrap();
+
+		let repo = super::repo(repo_path).unwrap();
+		assert!(find_conflict(&repo, "a.txt").is_err());
+		assert!(repo
+			.index()
+			.unwrap()
+			.get_path(Path::new("a.txt"), 0)
+			.is_none());
+		assert!(!root.join("a.txt").exists());
+	}
+}
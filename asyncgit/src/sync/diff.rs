@@ -5,7 +5,7 @@ use super::{
 		get_commit_diff, get_compare_commits_diff, OldNew,
 	},
 	utils::{get_head_repo, work_dir},
-	CommitId, RepoPath,
+	word_diff, CommitId, RepoPath,
 };
 use crate::{
 	error::Error,
@@ -62,6 +62,9 @@ pub struct DiffLine {
 	pub line_type: DiffLineType,
 	///
 	pub position: DiffLinePosition,
+	/// byte ranges of `content` that changed compared to the line it
+	/// replaces (or is replaced by)
+	pub emphasized: Vec<(usize, usize)>,
 }
 
 ///
@@ -132,7 +135,31 @@ pub struct FileDiff {
 	pub size_delta: i64,
 }
 
+/// algorithm used to find the differences, libgit2 has no
+/// histogram diff
+#[derive(
+	Debug,
+	Default,
+	Hash,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+)]
+pub enum DiffAlgorithm {
+	/// default myers diff
+	#[default]
+	Myers,
+	/// myers diff spending extra time to find the smallest diff
+	Minimal,
+	/// patience diff
+	Patience,
+}
+
 /// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
+#[allow(clippy::struct_excessive_bools)]
 #[derive(
 	Debug, Hash, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
 )]
@@ -143,6 +170,18 @@ pub struct DiffOptions {
 	pub context: u32,
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 	pub interhunk_lines: u32,
+	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
+	#[serde(default)]
+	pub algorithm: DiffAlgorithm,
+	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
+	#[serde(default)]
+	pub ignore_whitespace_change: bool,
+	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
+	#[serde(default)]
+	pub ignore_whitespace_eol: bool,
+	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
+	#[serde(default)]
+	pub ignore_blank_lines: bool,
 }
 
 impl Default for DiffOptions {
@@ -151,10 +190,28 @@ impl Default for DiffOptions {
 			ignore_whitespace: false,
 			context: 3,
 			interhunk_lines: 0,
+			algorithm: DiffAlgorithm::default(),
+			ignore_whitespace_change: false,
+			ignore_whitespace_eol: false,
+			ignore_blank_lines: false,
 		}
 	}
 }
 
+impl DiffOptions {
+	/// applies these options to the options of a libgit2 diff
+	pub(crate) fn apply(&self, opt: &mut git2::DiffOptions) {
+		opt.context_lines(self.context);
+		opt.ignore_whitespace(self.ignore_whitespace);
+		opt.interhunk_lines(self.interhunk_lines);
+		opt.ignore_whitespace_change(self.ignore_whitespace_change);
+		opt.ignore_whitespace_eol(self.ignore_whitespace_eol);
+		opt.ignore_blank_lines(self.ignore_blank_lines);
+		opt.minimal(self.algorithm == DiffAlgorithm::Minimal);
+		opt.patience(self.algorithm == DiffAlgorithm::Patience);
+	}
+}
+
 pub(crate) fn get_diff_raw<'a>(
 	repo: &'a Repository,
 	p: &str,
@@ -166,9 +223,7 @@ pub(crate) fn get_diff_raw<'a>(
 
 	let mut opt = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opt.context_lines(options.context);
-		opt.ignore_whitespace(options.ignore_whitespace);
-		opt.interhunk_lines(options.interhunk_lines);
+		options.apply(&mut opt);
 	}
 	opt.pathspec(p);
 	opt.reverse(reverse);
@@ -272,11 +327,13 @@ fn raw_diff_to_file_diff(
 		let adder = move |header: &HunkHeader,
 		                  lines: &Vec<DiffLine>| {
 			let mut res = res_cell.borrow_mut();
+			let mut lines = lines.clone();
+			word_diff::emphasize_hunk(&mut lines);
+			res.lines += lines.len();
 			res.hunks.push(Hunk {
 				header_hash: hash(header),
-				lines: lines.clone(),
+				lines,
 			});
-			res.lines += lines.len();
 		};
 
 		let res_cell = Rc::clone(&res);
@@ -314,6 +371,7 @@ fn raw_diff_to_file_diff(
 						.trim_matches(is_newline)
 						.into(),
 					line_type: line.origin_value().into(),
+					emphasized: Vec::new(),
 				};
 
 				current_lines.push(diff_line);
@@ -420,7 +478,9 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-	use super::{get_diff, get_diff_commit};
+	use super::{
+		get_diff, get_diff_commit, DiffAlgorithm, DiffOptions,
+	};
 	use crate::{
 		error::Result,
 		sync::{
@@ -560,6 +620,58 @@ mod tests {
 		assert_eq!(res.hunks.len(), 2)
 	}
 
+	#[test]
+	fn test_whitespace_modes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let file_path = root.join("bar.txt");
+
+		File::create(&file_path)
+			.unwrap()
+			.write_all(b"a b\nc\n")
+			.unwrap();
+		stage_add_file(repo_path, Path::new("bar.txt")).unwrap();
+
+		File::create(&file_path)
+			.unwrap()
+			.write_all(b"a  b \n\nc\n")
+			.unwrap();
+
+		let diff = |options| {
+			get_diff(repo_path, "bar.txt", false, Some(options))
+				.unwrap()
+				.hunks
+				.len()
+		};
+
+		assert_eq!(diff(DiffOptions::default()), 1);
+		assert_eq!(
+			diff(DiffOptions {
+				ignore_whitespace_change: true,
+				..DiffOptions::default()
+			}),
+			1
+		);
+		assert_eq!(
+			diff(DiffOptions {
+				ignore_whitespace_change: true,
+				ignore_blank_lines: true,
+				..DiffOptions::default()
+			}),
+			0
+		);
+		assert_eq!(
+			diff(DiffOptions {
+				algorithm: DiffAlgorithm::Patience,
+				..DiffOptions::default()
+			}),
+			1
+		);
+	}
+
 	#[test]
 	fn test_diff_newfile_in_sub_dir_current_dir() {
 		let file_path = Path::new("foo/foo.txt");
@@ -0,0 +1,456 @@
+//! launching the external `diff.tool` and `merge.tool` of gitconfig
+
+use super::{
+	commit_files::OldNew, conflict::find_conflict, repository::repo,
+	stage_add_file, CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{Delta, DiffFindOptions, IndexEntry, Oid, Repository};
+use scopetime::scope_time;
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	process::Command,
+};
+use tempfile::Builder;
+
+/// which versions of a file to compare in the external diff tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffToolTarget {
+	/// index against the file in the working dir
+	WorkDir,
+	/// `HEAD` against index
+	Stage,
+	/// first parent against the commit
+	Commit(CommitId),
+	/// old commit against new commit
+	Commits(OldNew<CommitId>),
+}
+
+/// opens `path` in the configured `diff.tool` (falling back to
+/// `merge.tool` like git does) and waits for the tool to exit
+pub fn difftool(
+	repo_path: &RepoPath,
+	path: &str,
+	target: DiffToolTarget,
+) -> Result<()> {
+	scope_time!("difftool");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+	let tool = configured_tool(&repo, &["diff.tool", "merge.tool"])?;
+	let cmd = tool_cmd(&repo, "difftool", &tool, false)?;
+
+	let tmp = Builder::new().prefix("gitui-difftool-").tempdir()?;
+
+	let local = match target {
+		DiffToolTarget::WorkDir => index_blob(&repo, path)?,
+		DiffToolTarget::Stage => head_tree_blob(&repo, path)?,
+		DiffToolTarget::Commit(id) => {
+			let commit = repo.find_commit(id.into())?;
+			match commit.parents().next() {
+				Some(parent) => old_tree_blob(
+					&repo,
+					parent.id(),
+					id.into(),
+					path,
+				)?,
+				None => None,
+			}
+		}
+		DiffToolTarget::Commits(ids) => old_tree_blob(
+			&repo,
+			ids.old.into(),
+			ids.new.into(),
+			path,
+		)?,
+	};
+	let local = write_blob(&repo, tmp.path(), path, "LOCAL", local)?;
+
+	let remote = match target {
+		DiffToolTarget::WorkDir => work_dir.join(path),
+		DiffToolTarget::Stage => {
+			let blob = index_blob(&repo, path)?;
+			write_blob(&repo, tmp.path(), path, "REMOTE", blob)?
+		}
+		DiffToolTarget::Commit(id)
+		| DiffToolTarget::Commits(OldNew { new: id, .. }) => {
+			let blob = tree_blob(&repo, id.into(), path)?;
+			write_blob(&repo, tmp.path(), path, "REMOTE", blob)?
+		}
+	};
+
+	run_tool(
+		&cmd,
+		work_dir,
+		&[
+			("LOCAL", local.as_path()),
+			("REMOTE", remote.as_path()),
+			("BASE", Path::new(path)),
+			("MERGED", Path::new(path)),
+		],
+	)?;
+
+	Ok(())
+}
+
+/// opens the conflicted `path` in the configured `merge.tool` and
+/// stages the merged file if the tool reports success.
+/// returns whether the conflict got resolved.
+pub fn mergetool(repo_path: &RepoPath, path: &str) -> Result<bool> {
+	scope_time!("mergetool");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+	let tool = configured_tool(&repo, &["merge.tool"])?;
+	let cmd = tool_cmd(&repo, "mergetool", &tool, true)?;
+	let trust_exit_code = repo
+		.config()?
+		.get_bool(&format!("mergetool.{tool}.trustExitCode"))
+		.unwrap_or_default();
+
+	let conflict = find_conflict(&repo, path)?;
+
+	let tmp = Builder::new().prefix("gitui-mergetool-").tempdir()?;
+	let stage = |entry: Option<&IndexEntry>, suffix: &str| {
+		write_blob(
+			&repo,
+			tmp.path(),
+			path,
+			suffix,
+			entry.map(|e| e.id),
+		)
+	};
+
+	let base = stage(conflict.ancestor.as_ref(), "BASE")?;
+	let local = stage(conflict.our.as_ref(), "LOCAL")?;
+	let remote = stage(conflict.their.as_ref(), "REMOTE")?;
+
+	let merged = work_dir.join(path);
+	let before = fs::read(&merged).unwrap_or_default();
+
+	let success = run_tool(
+		&cmd,
+		work_dir,
+		&[
+			("LOCAL", local.as_path()),
+			("REMOTE", remote.as_path()),
+			("BASE", base.as_path()),
+			("MERGED", Path::new(path)),
+		],
+	)?;
+
+	// like git, without `trustExitCode` an unchanged file means the
+	// merge was aborted
+	let resolved = success
+		&& (trust_exit_code
+			|| fs::read(&merged).unwrap_or_default() != before);
+
+	if resolved {
+		stage_add_file(repo_path, Path::new(path))?;
+	}
+
+	Ok(resolved)
+}
+
+fn work_dir(repo: &Repository) -> Result<&Path> {
+	repo.workdir().ok_or_else(|| {
+		Error::Generic(String::from("bare repository"))
+	})
+}
+
+/// first of `keys` that is set in gitconfig
+fn configured_tool(
+	repo: &Repository,
+	keys: &[&str],
+) -> Result<String> {
+	let config = repo.config()?;
+
+	keys.iter()
+		.find_map(|key| config.get_string(key).ok())
+		.filter(|tool| !tool.is_empty())
+		.ok_or_else(|| {
+			Error::Generic(format!("{} not configured", keys[0]))
+		})
+}
+
+/// shell command to run `tool`: `<section>.<tool>.cmd` or the
+/// arguments git passes to well known tools
+fn tool_cmd(
+	repo: &Repository,
+	section: &str,
+	tool: &str,
+	merge: bool,
+) -> Result<String> {
+	let config = repo.config()?;
+
+	if let Ok(cmd) =
+		config.get_string(&format!("{section}.{tool}.cmd"))
+	{
+		return Ok(cmd);
+	}
+
+	let path = config
+		.get_string(&format!("{section}.{tool}.path"))
+		.unwrap_or_else(|_| tool.to_string());
+
+	let args = if merge {
+		match tool {
+			"meld" => {
+				r#"--output="$MERGED" "$LOCAL" "$BASE" "$REMOTE""#
+			}
+			"kdiff3" => {
+				r#"--auto "$BASE" "$LOCAL" "$REMOTE" -o "$MERGED""#
+			}
+			"vimdiff" | "nvimdiff" | "gvimdiff" => {
+				r#"-d "$LOCAL" "$BASE" "$REMOTE" "$MERGED""#
+			}
+			_ => {
+				return Err(Error::Generic(format!(
+					"{section}.{tool}.cmd not configured"
+				)))
+			}
+		}
+	} else {
+		r#""$LOCAL" "$REMOTE""#
+	};
+
+	Ok(format!("\"{path}\" {args}"))
+}
+
+/// runs `cmd` in a shell with the file paths set as env variables,
+/// returns whether it exited successfully.
+///
+/// the commands use shell syntax like in git, so on windows this
+/// needs the `sh` of git for windows on the `PATH`.
+fn run_tool(
+	cmd: &str,
+	work_dir: &Path,
+	files: &[(&str, &Path)],
+) -> Result<bool> {
+	let mut command = Command::new("sh");
+	command.arg("-c").arg(cmd).current_dir(work_dir);
+
+	for (name, path) in files {
+		command.env(name, path);
+	}
+
+	let status = command.status().map_err(|e| {
+		if cfg!(target_os = "windows")
+			&& e.kind() == io::ErrorKind::NotFound
+		{
+			Error::Generic(format!(
+				"failed to run \"{cmd}\": `sh` not found, add the `bin` folder of git for windows to the PATH"
+			))
+		} else {
+			Error::Generic(format!("failed to run \"{cmd}\": {e}"))
+		}
+	})?;
+
+	Ok(status.success())
+}
+
+fn index_blob(repo: &Repository, path: &str) -> Result<Option<Oid>> {
+	Ok(repo.index()?.get_path(Path::new(path), 0).map(|e| e.id))
+}
+
+fn head_tree_blob(
+	repo: &Repository,
+	path: &str,
+) -> Result<Option<Oid>> {
+	repo.head()
+		.and_then(|head| head.peel_to_commit())
+		.map_or(Ok(None), |head| tree_blob(repo, head.id(), path))
+}
+
+fn tree_blob(
+	repo: &Repository,
+	commit: Oid,
+	path: &str,
+) -> Result<Option<Oid>> {
+	let tree = repo.find_commit(commit)?.tree()?;
+	let entry = tree.get_path(Path::new(path)).ok();
+
+	Ok(entry.map(|entry| entry.id()))
+}
+
+/// blob of `path` of commit `new` in the older commit `old`,
+/// following a rename of the file in between
+fn old_tree_blob(
+	repo: &Repository,
+	old: Oid,
+	new: Oid,
+	path: &str,
+) -> Result<Option<Oid>> {
+	if let Some(blob) = tree_blob(repo, old, path)? {
+		return Ok(Some(blob));
+	}
+
+	let old_tree = repo.find_commit(old)?.tree()?;
+	let new_tree = repo.find_commit(new)?.tree()?;
+
+	let mut diff = repo.diff_tree_to_tree(
+		Some(&old_tree),
+		Some(&new_tree),
+		None,
+	)?;
+	diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+	let old_path = diff
+		.deltas()
+		.find(|delta| {
+			delta.status() == Delta::Renamed
+				&& delta.new_file().path() == Some(Path::new(path))
+		})
+		.and_then(|delta| delta.old_file().path())
+		.and_then(Path::to_str);
+
+	old_path
+		.map_or(Ok(None), |old_path| tree_blob(repo, old, old_path))
+}
+
+/// writes the blob (or an empty file if the version does not exist)
+/// to `dir`, keeping the extension of `path` for the tool to pick up
+fn write_blob(
+	repo: &Repository,
+	dir: &Path,
+	path: &str,
+	suffix: &str,
+	blob: Option<Oid>,
+) -> Result<PathBuf> {
+	let path = Path::new(path);
+	let stem = path
+		.file_stem()
+		.map(|s| s.to_string_lossy().to_string())
+		.unwrap_or_default();
+
+	let mut file = dir.join(format!("{stem}_{suffix}"));
+	if let Some(ext) = path.extension() {
+		file.set_extension(ext);
+	}
+
+	match blob {
+		Some(id) => fs::write(&file, repo.find_blob(id)?.content())?,
+		None => fs::write(&file, [])?,
+	}
+
+	Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, commit, create_branch, merge_branch,
+		tests::{repo_init, write_commit_file},
+		utils::{repo_read_file, stage_addremoved},
+		BranchType,
+	};
+	use pretty_assertions::assert_eq;
+	use tempfile::TempDir;
+
+	/// configures `cmd` as the `test` tool of `section`
+	fn set_tool(repo: &Repository, section: &str, cmd: &str) {
+		let mut config = repo.config().unwrap();
+		config.set_str(&format!("{section}.tool"), "test").unwrap();
+		config
+			.set_str(&format!("{section}tool.test.cmd"), cmd)
+			.unwrap();
+	}
+
+	#[test]
+	fn test_difftool() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		let out = TempDir::new().unwrap();
+
+		set_tool(
+			&repo,
+			"diff",
+			&format!(
+				r#"cat "$LOCAL" "$REMOTE" > "{}""#,
+				out.path().join("out").display()
+			),
+		);
+
+		let id = write_commit_file(&repo, "a.txt", "1\n", "c1");
+		fs::write(root.join("a.txt"), "2\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		fs::write(root.join("a.txt"), "3\n").unwrap();
+
+		let diff = |target| {
+			difftool(repo_path, "a.txt", target).unwrap();
+			fs::read_to_string(out.path().join("out")).unwrap()
+		};
+
+		assert_eq!(diff(DiffToolTarget::WorkDir), "2\n3\n");
+		assert_eq!(diff(DiffToolTarget::Stage), "1\n2\n");
+		assert_eq!(diff(DiffToolTarget::Commit(id)), "1\n");
+
+		let id2 = commit(repo_path, "c2").unwrap();
+
+		assert_eq!(
+			diff(DiffToolTarget::Commits(OldNew {
+				old: id,
+				new: id2
+			})),
+			"1\n2\n"
+		);
+
+		// renamed in the commit
+		fs::remove_file(root.join("a.txt")).unwrap();
+		stage_addremoved(repo_path, Path::new("a.txt")).unwrap();
+		fs::write(root.join("b.txt"), "2\n").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		let id3 = commit(repo_path, "c3").unwrap();
+
+		difftool(repo_path, "b.txt", DiffToolTarget::Commit(id3))
+			.unwrap();
+		assert_eq!(
+			fs::read_to_string(out.path().join("out")).unwrap(),
+			"2\n2\n"
+		);
+	}
+
+	#[test]
+	fn test_mergetool() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a\n", "base");
+
+		create_branch(repo_path, "other").unwrap();
+		write_commit_file(&repo, "a.txt", "b\n", "c1");
+
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "a.txt", "c\n", "c2");
+
+		merge_branch(repo_path, "other", BranchType::Local).unwrap();
+
+		let has_conflicts = || {
+			super::repo(repo_path)
+				.unwrap()
+				.index()
+				.unwrap()
+				.has_conflicts()
+		};
+
+		set_tool(&repo, "merge", "true");
+		assert!(!mergetool(repo_path, "a.txt").unwrap());
+		assert!(has_conflicts());
+
+		set_tool(
+			&repo,
+			"merge",
+			r#"cat "$BASE" "$REMOTE" > "$MERGED""#,
+		);
+		assert!(mergetool(repo_path, "a.txt").unwrap());
+
+		assert!(!has_conflicts());
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a\nb\n");
+	}
+}
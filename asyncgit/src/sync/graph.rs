@@ -0,0 +1,263 @@
+//! lane layout of the commit graph drawn next to the log
+//!
+//! the graph is built row by row in log order so it can be
+//! computed incrementally while the log is still being walked.
+
+use super::CommitId;
+
+/// single cell (lane) of a graph row
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GraphCell {
+	/// the commit of this row sits in this lane
+	pub commit: bool,
+	/// connected to the row above
+	pub up: bool,
+	/// connected to the row below
+	pub down: bool,
+	/// connected to the lane on the left
+	pub left: bool,
+	/// color of the line leading to the lane on the right
+	pub link: Option<u8>,
+	/// color of this lane
+	pub color: u8,
+}
+
+impl GraphCell {
+	///
+	pub const fn right(&self) -> bool {
+		self.link.is_some()
+	}
+
+	/// box drawing character representing this cell
+	pub const fn symbol(&self) -> char {
+		if self.commit {
+			return '●';
+		}
+
+		match (self.up, self.down, self.left, self.right()) {
+			(true, true, true, true) => '┼',
+			(true, true, true, false) => '┤',
+			(true, true, false, true) => '├',
+			(true, false, true, true) => '┴',
+			(false, true, true, true) => '┬',
+			(true, false, true, false) => '╯',
+			(true, false, false, true) => '╰',
+			(false, true, true, false) => '╮',
+			(false, true, false, true) => '╭',
+			(true, _, false, false) | (false, true, false, false) => {
+				'│'
+			}
+			(false, false, true, _) | (false, false, false, true) => {
+				'─'
+			}
+			(false, false, false, false) => ' ',
+		}
+	}
+}
+
+/// graph cells of a single commit in the log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphRow {
+	///
+	pub cells: Vec<GraphCell>,
+}
+
+/// keeps track of the lanes while walking the log
+#[derive(Default)]
+pub struct CommitGraph {
+	/// commit each lane is waiting for and the color of the lane
+	lanes: Vec<Option<(CommitId, u8)>>,
+	next_color: u8,
+}
+
+impl CommitGraph {
+	/// adds the next commit of the log (children need to be
+	/// pushed before their parents) and returns its row
+	pub fn push(
+		&mut self,
+		id: CommitId,
+		parents: &[CommitId],
+	) -> GraphRow {
+		let before = self.lanes.clone();
+		let mut cells = vec![GraphCell::default(); before.len()];
+
+		let waits_for_commit = |lane: &Option<(CommitId, u8)>| {
+			lane.is_some_and(|l| l.0 == id)
+		};
+
+		let col = before
+			.iter()
+			.position(waits_for_commit)
+			.unwrap_or_else(|| self.free_lane());
+
+		for (idx, (_, color)) in before
+			.iter()
+			.enumerate()
+			.filter_map(|(idx, lane)| lane.map(|lane| (idx, lane)))
+		{
+			let cell = &mut cells[idx];
+			cell.color = color;
+			cell.up = true;
+			cell.down = idx != col && !waits_for_commit(&before[idx]);
+		}
+
+		let color = match before.get(col) {
+			Some(Some((_, color))) => *color,
+			_ => self.new_color(),
+		};
+
+		// lanes of other children end in this commit
+		for idx in (0..before.len()).filter(|idx| {
+			*idx != col && waits_for_commit(&before[*idx])
+		}) {
+			let color = cells[idx].color;
+			connect(&mut cells, col, idx, color);
+			self.lanes[idx] = None;
+		}
+
+		let cell = cell_mut(&mut cells, col);
+		cell.commit = true;
+		cell.color = color;
+		cell.down = !parents.is_empty();
+
+		self.lanes[col] =
+			parents.first().map(|parent| (*parent, color));
+
+		for parent in parents.iter().skip(1) {
+			let existing = self
+				.lanes
+				.iter()
+				.position(|lane| lane.is_some_and(|l| l.0 == *parent))
+				.filter(|idx| *idx != col);
+
+			let (idx, color) = if let Some(idx) = existing {
+				(idx, self.lanes[idx].map_or(color, |l| l.1))
+			} else {
+				let idx = self.free_lane();
+				let color = self.new_color();
+				self.lanes[idx] = Some((*parent, color));
+				cell_mut(&mut cells, idx).color = color;
+				(idx, color)
+			};
+
+			cell_mut(&mut cells, idx).down = true;
+			connect(&mut cells, col, idx, color);
+		}
+
+		while self.lanes.last().is_some_and(Option::is_none) {
+			self.lanes.pop();
+		}
+
+		GraphRow { cells }
+	}
+
+	fn free_lane(&mut self) -> usize {
+		self.lanes.iter().position(Option::is_none).unwrap_or_else(
+			|| {
+				self.lanes.push(None);
+				self.lanes.len() - 1
+			},
+		)
+	}
+
+	fn new_color(&mut self) -> u8 {
+		let color = self.next_color;
+		self.next_color = self.next_color.wrapping_add(1);
+		color
+	}
+}
+
+fn cell_mut(
+	cells: &mut Vec<GraphCell>,
+	idx: usize,
+) -> &mut GraphCell {
+	if cells.len() <= idx {
+		cells.resize(idx + 1, GraphCell::default());
+	}
+
+	&mut cells[idx]
+}
+
+/// draws a horizontal line between lanes `a` and `b`
+fn connect(
+	cells: &mut Vec<GraphCell>,
+	a: usize,
+	b: usize,
+	color: u8,
+) {
+	let (lo, hi) = (a.min(b), a.max(b));
+
+	cell_mut(cells, hi).left = true;
+
+	for idx in lo..hi {
+		let cell = cell_mut(cells, idx);
+		cell.link = Some(color);
+
+		if idx > lo {
+			cell.left = true;
+
+			if !cell.up && !cell.down {
+				cell.color = color;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use git2::Oid;
+	use pretty_assertions::assert_eq;
+
+	fn id(n: u8) -> CommitId {
+		Oid::from_bytes(&[n; 20]).unwrap().into()
+	}
+
+	fn symbols(row: &GraphRow) -> String {
+		row.cells
+			.iter()
+			.flat_map(|cell| {
+				[cell.symbol(), if cell.right() { '─' } else { ' ' }]
+			})
+			.collect::<String>()
+			.trim_end()
+			.to_string()
+	}
+
+	#[test]
+	fn test_linear() {
+		let mut graph = CommitGraph::default();
+
+		assert_eq!(symbols(&graph.push(id(3), &[id(2)])), "●");
+		assert_eq!(symbols(&graph.push(id(2), &[id(1)])), "●");
+		assert_eq!(symbols(&graph.push(id(1), &[])), "●");
+	}
+
+	#[test]
+	fn test_merge() {
+		let mut graph = CommitGraph::default();
+
+		// 4 merges 3 (main) and 2 (feature), both based on 1
+		assert_eq!(
+			symbols(&graph.push(id(4), &[id(3), id(2)])),
+			"●─╮"
+		);
+		assert_eq!(symbols(&graph.push(id(3), &[id(1)])), "● │");
+		assert_eq!(symbols(&graph.push(id(2), &[id(1)])), "│ ●");
+		assert_eq!(symbols(&graph.push(id(1), &[])), "●─╯");
+	}
+
+	#[test]
+	fn test_branches() {
+		let mut graph = CommitGraph::default();
+
+		// two branch heads on top of 1
+		assert_eq!(symbols(&graph.push(id(3), &[id(1)])), "●");
+		assert_eq!(symbols(&graph.push(id(2), &[id(1)])), "│ ●");
+		let row = graph.push(id(1), &[]);
+		assert_eq!(symbols(&row), "●─╯");
+		assert_ne!(row.cells[0].color, row.cells[1].color);
+		assert!(graph.lanes.is_empty());
+	}
+}
@@ -69,7 +69,11 @@ pub fn reset_hunk(
 	}
 }
 
-fn find_hunk_index(diff: &Diff, hunk_hash: u64) -> Option<usize> {
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn find_hunk_index(
+	diff: &Diff,
+	hunk_hash: u64,
+) -> Option<usize> {
 	let mut result = None;
 
 	let mut hunk_count = 0;
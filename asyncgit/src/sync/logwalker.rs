@@ -1,7 +1,11 @@
 #![allow(dead_code)]
-use super::{CommitId, SharedCommitFilterFn};
+use super::{
+	commits_info::revision_range, CommitId, RevisionRange,
+	SharedCommitFilterFn, SharedWalkStartFn,
+};
 use crate::error::Result;
-use git2::{Commit, Oid, Repository};
+use git2::{Commit, Oid, Reference, Repository};
+use serde::{Deserialize, Serialize};
 use std::{
 	cmp::Ordering,
 	collections::{BinaryHeap, HashSet},
@@ -29,6 +33,112 @@ impl<'a> Ord for TimeOrderedCommit<'a> {
 	}
 }
 
+/// how [`LogWalker`] traverses the history
+#[derive(
+	Debug,
+	Default,
+	Hash,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+)]
+pub struct LogWalkerOptions {
+	/// only follow the first parent of merge commits
+	/// (`--first-parent`)
+	pub first_parent: bool,
+	/// skip merge commits (`--no-merges`)
+	pub no_merges: bool,
+}
+
+/// references the log is walked from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LogRefs {
+	/// `HEAD` only
+	#[default]
+	Head,
+	/// `HEAD`, all branches, remote branches and tags (`--all`)
+	All,
+	/// `HEAD` and all local branches
+	Local,
+	/// `HEAD` and all remote branches
+	Remotes,
+	/// the given references (full names like `refs/heads/master`),
+	/// references that do not exist (anymore) are ignored
+	Selected(Vec<String>),
+	/// commits selected by a revspec (like `main..feature`)
+	Revspec(String),
+}
+
+impl LogRefs {
+	/// commits the log starts from (without duplicates) and the
+	/// commits whose history is excluded
+	pub fn resolve(
+		&self,
+		repo: &Repository,
+	) -> Result<RevisionRange> {
+		Ok(match self {
+			Self::Revspec(revspec) => revision_range(repo, revspec)?,
+			_ => RevisionRange {
+				include: self.tips(repo)?,
+				exclude: Vec::new(),
+			},
+		})
+	}
+
+	fn tips(&self, repo: &Repository) -> Result<Vec<CommitId>> {
+		let globs: &[&str] = match self {
+			Self::Head | Self::Revspec(_) => {
+				let head = repo.head()?.peel_to_commit()?;
+				return Ok(vec![head.id().into()]);
+			}
+			Self::All => {
+				&["refs/heads/*", "refs/remotes/*", "refs/tags/*"]
+			}
+			Self::Local => &["refs/heads/*"],
+			Self::Remotes => &["refs/remotes/*"],
+			Self::Selected(refs) => {
+				let mut tips = Vec::with_capacity(refs.len());
+				for reference in refs {
+					if let Ok(reference) =
+						repo.find_reference(reference)
+					{
+						push_tip(&mut tips, &reference);
+					}
+				}
+				return Ok(tips);
+			}
+		};
+
+		let mut tips = Vec::new();
+
+		if let Ok(head) = repo.head() {
+			push_tip(&mut tips, &head);
+		}
+
+		for glob in globs {
+			for reference in repo.references_glob(glob)? {
+				push_tip(&mut tips, &reference?);
+			}
+		}
+
+		Ok(tips)
+	}
+}
+
+/// references not pointing to a commit (e.g. tags of trees) are
+/// ignored
+fn push_tip(tips: &mut Vec<CommitId>, reference: &Reference) {
+	if let Ok(commit) = reference.peel_to_commit() {
+		let id = commit.id().into();
+		if !tips.contains(&id) {
+			tips.push(id);
+		}
+	}
+}
+
 ///
 pub struct LogWalker<'a> {
 	commits: BinaryHeap<TimeOrderedCommit<'a>>,
@@ -36,23 +146,65 @@ pub struct LogWalker<'a> {
 	limit: usize,
 	repo: &'a Repository,
 	filter: Option<SharedCommitFilterFn>,
+	walk_start: Option<SharedWalkStartFn>,
+	options: LogWalkerOptions,
+	/// commits not reachable from excluded commits, `None` if nothing
+	/// is excluded
+	allowed: Option<HashSet<Oid>>,
 }
 
 impl<'a> LogWalker<'a> {
 	///
 	pub fn new(repo: &'a Repository, limit: usize) -> Result<Self> {
-		let c = repo.head()?.peel_to_commit()?;
+		Self::with_refs(repo, limit, &LogRefs::Head)
+	}
 
-		let mut commits = BinaryHeap::with_capacity(10);
-		commits.push(TimeOrderedCommit(c));
+	/// walks the commits reachable from any of `refs`
+	pub fn with_refs(
+		repo: &'a Repository,
+		limit: usize,
+		refs: &LogRefs,
+	) -> Result<Self> {
+		Self::with_range(repo, limit, &refs.resolve(repo)?)
+	}
 
-		Ok(Self {
-			commits,
+	/// walks the commits reachable from `range.include` but not from
+	/// `range.exclude`
+	pub fn with_range(
+		repo: &'a Repository,
+		limit: usize,
+		range: &RevisionRange,
+	) -> Result<Self> {
+		let allowed =
+			if range.exclude.is_empty() {
+				None
+			} else {
+				let mut walk = repo.revwalk()?;
+				for id in &range.include {
+					walk.push(id.get_oid())?;
+				}
+				for id in &range.exclude {
+					walk.hide(id.get_oid())?;
+				}
+				Some(walk.collect::<std::result::Result<HashSet<_>, _>>()?)
+			};
+
+		let mut walker = Self {
+			commits: BinaryHeap::with_capacity(10),
 			limit,
 			visited: HashSet::with_capacity(1000),
 			repo,
 			filter: None,
-		})
+			walk_start: None,
+			options: LogWalkerOptions::default(),
+			allowed,
+		};
+
+		for id in &range.include {
+			walker.visit(repo.find_commit((*id).into())?);
+		}
+
+		Ok(walker)
 	}
 
 	///
@@ -60,6 +212,13 @@ impl<'a> LogWalker<'a> {
 		self.visited.len()
 	}
 
+	/// `false` if `id` is excluded by the range walked
+	pub fn in_range(&self, id: CommitId) -> bool {
+		self.allowed
+			.as_ref()
+			.map_or(true, |allowed| allowed.contains(&id.get_oid()))
+	}
+
 	///
 	#[must_use]
 	pub fn filter(
@@ -69,18 +228,44 @@ impl<'a> LogWalker<'a> {
 		Self { filter, ..self }
 	}
 
+	/// `walk_start` is called before the first commit is read
+	#[must_use]
+	pub fn on_walk_start(
+		self,
+		walk_start: Option<SharedWalkStartFn>,
+	) -> Self {
+		Self { walk_start, ..self }
+	}
+
+	///
+	#[must_use]
+	pub fn options(self, options: LogWalkerOptions) -> Self {
+		Self { options, ..self }
+	}
+
 	///
 	pub fn read(&mut self, out: &mut Vec<CommitId>) -> Result<usize> {
 		let mut count = 0_usize;
 
+		if let Some(walk_start) = self.walk_start.take() {
+			walk_start()?;
+		}
+
 		while let Some(c) = self.commits.pop() {
-			for p in c.0.parents() {
+			let parents = if self.options.first_parent {
+				1
+			} else {
+				usize::MAX
+			};
+			for p in c.0.parents().take(parents) {
 				self.visit(p);
 			}
 
 			let id: CommitId = c.0.id().into();
-			let commit_should_be_included =
-				if let Some(ref filter) = self.filter {
+			let is_hidden_merge =
+				self.options.no_merges && c.0.parent_count() > 1;
+			let commit_should_be_included = !is_hidden_merge
+				&& if let Some(ref filter) = self.filter {
 					filter(self.repo, &id)?
 				} else {
 					true
@@ -101,6 +286,16 @@ impl<'a> LogWalker<'a> {
 
 	//
 	fn visit(&mut self, c: Commit<'a>) {
+		// everything reachable from an excluded commit is excluded
+		// as well, so there is no need to walk past it
+		if self
+			.allowed
+			.as_ref()
+			.is_some_and(|allowed| !allowed.contains(&c.id()))
+		{
+			return;
+		}
+
 		if !self.visited.contains(&c.id()) {
 			self.visited.insert(c.id());
 			self.commits.push(TimeOrderedCommit(c));
@@ -113,13 +308,17 @@ mod tests {
 	use super::*;
 	use crate::error::Result;
 	use crate::sync::commit_filter::{SearchFields, SearchOptions};
-	use crate::sync::tests::write_commit_file;
+	use crate::sync::tests::{
+		write_commit_file, write_commit_file_at,
+	};
 	use crate::sync::{
-		commit, get_commits_info, stage_add_file,
+		checkout_branch, commit, create_branch, get_commits_info,
+		merge_commit, stage_add_all, stage_add_file,
 		tests::repo_init_empty,
 	};
 	use crate::sync::{
-		diff_contains_file, filter_commit_by_search, LogFilterSearch,
+		diff_contains_file, diff_contains_file_follow,
+		filter_commit_by_search, LogFilterSearch,
 		LogFilterSearchOptions, RepoPath,
 	};
 	use pretty_assertions::assert_eq;
@@ -280,4 +479,290 @@ mod tests {
 
 		assert_eq!(items.len(), 2);
 	}
+
+	#[test]
+	fn test_logwalker_with_regex_search() {
+		let (_td, repo) = repo_init_empty().unwrap();
+
+		write_commit_file(&repo, "foo", "a", "fix: crash (#12)");
+		let second = write_commit_file(&repo, "foo", "b", "Feat: x");
+		write_commit_file(&repo, "foo", "c", "docs: feat list");
+
+		let options = LogFilterSearchOptions {
+			fields: SearchFields::MESSAGE_SUMMARY,
+			options: SearchOptions::REGEX,
+			search_pattern: String::from("^feat:"),
+		};
+		assert!(options.regex().unwrap().is_some());
+
+		let mut items = Vec::new();
+		LogWalker::new(&repo, 100)
+			.unwrap()
+			.filter(Some(filter_commit_by_search(
+				LogFilterSearch::new(options.clone()),
+			)))
+			.read(&mut items)
+			.unwrap();
+
+		assert_eq!(items, vec![second]);
+
+		let options = LogFilterSearchOptions {
+			options: SearchOptions::REGEX
+				| SearchOptions::CASE_SENSITIVE,
+			..options
+		};
+
+		let mut items = Vec::new();
+		LogWalker::new(&repo, 100)
+			.unwrap()
+			.filter(Some(filter_commit_by_search(
+				LogFilterSearch::new(options),
+			)))
+			.read(&mut items)
+			.unwrap();
+
+		assert!(items.is_empty());
+
+		let invalid = LogFilterSearchOptions {
+			fields: SearchFields::MESSAGE_SUMMARY,
+			options: SearchOptions::REGEX,
+			search_pattern: String::from("(#12"),
+		};
+		assert!(invalid.regex().is_err());
+	}
+
+	#[test]
+	fn test_logwalker_with_extended_search() {
+		let (_td, repo) = repo_init_empty().unwrap();
+
+		// 2021-01-01 and 2022-06-15, both at noon UTC
+		let at = |secs| git2::Time::new(secs, 0);
+		let first = write_commit_file_at(
+			&repo,
+			"foo",
+			"needle\n",
+			"commit1",
+			at(1_609_502_400),
+		);
+		let second = write_commit_file_at(
+			&repo,
+			"foo",
+			"needle\nhay\n",
+			"commit2",
+			at(1_655_294_400),
+		);
+
+		let search = |fields, pattern: &str| {
+			let log_filter = filter_commit_by_search(
+				LogFilterSearch::new(LogFilterSearchOptions {
+					fields,
+					options: SearchOptions::default(),
+					search_pattern: pattern.to_string(),
+				}),
+			);
+			let mut items = Vec::new();
+			LogWalker::new(&repo, 100)
+				.unwrap()
+				.filter(Some(log_filter))
+				.read(&mut items)
+				.unwrap();
+			items
+		};
+
+		let hash = second.to_string();
+		assert_eq!(
+			search(SearchFields::COMMIT_HASHES, &hash[..7]),
+			vec![second]
+		);
+
+		assert_eq!(
+			search(SearchFields::DATES, "2021-01-01"),
+			vec![first]
+		);
+		assert_eq!(
+			search(SearchFields::DATES, "2022-01-01.."),
+			vec![second]
+		);
+		assert_eq!(
+			search(SearchFields::DATES, "..2022-06-15").len(),
+			2
+		);
+		assert!(search(SearchFields::DATES, "yesterday").is_empty());
+
+		assert_eq!(
+			search(SearchFields::DIFFS, "needle"),
+			vec![first]
+		);
+		assert_eq!(search(SearchFields::DIFFS, "hay"), vec![second]);
+	}
+
+	#[test]
+	fn test_logwalker_refs() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let at = |secs| git2::Time::new(secs, 0);
+
+		let c1 =
+			write_commit_file_at(&repo, "foo", "a", "commit1", at(1));
+		create_branch(repo_path, "other").unwrap();
+		let c2 =
+			write_commit_file_at(&repo, "foo", "b", "commit2", at(2));
+		checkout_branch(repo_path, "master").unwrap();
+		let c3 =
+			write_commit_file_at(&repo, "foo", "c", "commit3", at(3));
+
+		let read = |refs: &LogRefs| {
+			let mut items = Vec::new();
+			LogWalker::with_refs(&repo, 100, refs)
+				.unwrap()
+				.read(&mut items)
+				.unwrap();
+			items
+		};
+
+		assert_eq!(read(&LogRefs::Head), vec![c3, c1]);
+		assert_eq!(read(&LogRefs::All), vec![c3, c2, c1]);
+		assert_eq!(read(&LogRefs::Local), vec![c3, c2, c1]);
+		assert_eq!(
+			read(&LogRefs::Selected(vec![String::from(
+				"refs/heads/other"
+			)])),
+			vec![c2, c1]
+		);
+		assert_eq!(
+			read(&LogRefs::Selected(vec![String::from(
+				"refs/heads/gone"
+			)])),
+			vec![]
+		);
+		assert_eq!(
+			read(&LogRefs::Revspec(String::from("master..other"))),
+			vec![c2]
+		);
+		assert_eq!(
+			read(&LogRefs::Revspec(String::from("other...master"))),
+			vec![c3, c2]
+		);
+
+		let walker = LogWalker::with_refs(
+			&repo,
+			100,
+			&LogRefs::Revspec(String::from("master..other")),
+		)
+		.unwrap();
+		assert!(walker.in_range(c2));
+		assert!(!walker.in_range(c1));
+		assert!(LogWalker::new(&repo, 100).unwrap().in_range(c2));
+	}
+
+	#[test]
+	fn test_logwalker_options() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let at = |secs| git2::Time::new(secs, 0);
+
+		let c1 = write_commit_file_at(&repo, "a", "a", "c1", at(1));
+		create_branch(repo_path, "feature").unwrap();
+		let c2 = write_commit_file_at(&repo, "b", "b", "c2", at(2));
+		checkout_branch(repo_path, "master").unwrap();
+		let c3 = write_commit_file_at(&repo, "c", "c", "c3", at(3));
+		let merge = merge_commit(repo_path, "merge", &[c2]).unwrap();
+
+		let read = |options: LogWalkerOptions| {
+			let mut items = Vec::new();
+			LogWalker::new(&repo, 100)
+				.unwrap()
+				.options(options)
+				.read(&mut items)
+				.unwrap();
+			items
+		};
+
+		assert_eq!(
+			read(LogWalkerOptions::default()),
+			vec![merge, c3, c2, c1]
+		);
+		assert_eq!(
+			read(LogWalkerOptions {
+				first_parent: true,
+				no_merges: false,
+			}),
+			vec![merge, c3, c1]
+		);
+		assert_eq!(
+			read(LogWalkerOptions {
+				first_parent: true,
+				no_merges: true,
+			}),
+			vec![c3, c1]
+		);
+	}
+
+	#[test]
+	fn test_logwalker_follow() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let at = |secs| git2::Time::new(secs, 0);
+
+		let content = "1\n2\n3\n4\n5\n6\n7\n8\n";
+		let c1 =
+			write_commit_file_at(&repo, "old", content, "c1", at(1));
+		write_commit_file_at(&repo, "other", "a", "c2", at(2));
+
+		std::fs::rename(root.join("old"), root.join("new")).unwrap();
+		stage_add_all(repo_path, "*", None).unwrap();
+		let c3 = commit(repo_path, "rename").unwrap();
+
+		let c4 = write_commit_file_at(
+			&repo,
+			"new",
+			&format!("{content}9\n"),
+			"c4",
+			at(4),
+		);
+
+		let (filter, walk_start, paths) =
+			diff_contains_file_follow(String::from("new"));
+
+		let read = || {
+			let mut items = Vec::new();
+			LogWalker::new(&repo, 100)
+				.unwrap()
+				.filter(Some(filter.clone()))
+				.on_walk_start(Some(walk_start.clone()))
+				.read(&mut items)
+				.unwrap();
+			items
+		};
+
+		assert_eq!(read(), vec![c4, c3, c1]);
+
+		let paths = paths.lock().unwrap();
+		assert_eq!(paths[&c4], "new");
+		assert_eq!(paths[&c3], "new");
+		assert_eq!(paths[&c1], "old");
+		drop(paths);
+
+		// walking again starts over with the new path
+		assert_eq!(read(), vec![c4, c3, c1]);
+
+		// also when the walk starts at a commit not seen before
+		let c5 = write_commit_file_at(
+			&repo,
+			"new",
+			&format!("{content}9\n10\n"),
+			"c5",
+			at(5),
+		);
+		assert_eq!(read(), vec![c5, c4, c3, c1]);
+	}
 }
@@ -5,6 +5,7 @@ use crate::{
 		rebase::{
 			abort_rebase, continue_rebase, get_rebase_progress,
 		},
+		rebase_interactive::continue_interactive,
 		repository::repo,
 		reset_stage, reset_workdir, CommitId,
 	},
@@ -86,6 +87,18 @@ pub fn continue_pending_rebase(
 	continue_rebase(&repo)
 }
 
+/// continue pending interactive rebase that stopped on a `reword` step
+pub fn continue_pending_rebase_reword(
+	repo_path: &RepoPath,
+	message: &str,
+) -> Result<RebaseState> {
+	scope_time!("continue_pending_rebase_reword");
+
+	let repo = repo(repo_path)?;
+
+	continue_interactive(&repo, Some(message))
+}
+
 ///
 pub fn abort_pending_rebase(repo_path: &RepoPath) -> Result<()> {
 	scope_time!("abort_pending_rebase");
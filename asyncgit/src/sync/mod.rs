@@ -3,8 +3,11 @@
 //TODO: remove once we have this activated on the toplevel
 #![deny(clippy::expect_used)]
 
+mod absorb;
+mod autosquash;
 pub mod blame;
 pub mod branch;
+mod cherry_pick;
 pub mod commit;
 mod commit_details;
 pub mod commit_files;
@@ -12,8 +15,11 @@ mod commit_filter;
 mod commit_revert;
 mod commits_info;
 mod config;
+mod conflict;
 pub mod cred;
 pub mod diff;
+mod difftool;
+mod graph;
 mod hooks;
 mod hunks;
 mod ignore;
@@ -21,6 +27,8 @@ mod logwalker;
 mod merge;
 mod patches;
 mod rebase;
+mod rebase_interactive;
+mod reflog;
 pub mod remotes;
 mod repository;
 mod reset;
@@ -34,8 +42,18 @@ mod submodules;
 mod tags;
 mod tree;
 pub mod utils;
+mod word_diff;
+mod worktree;
 
-pub use blame::{blame_file, BlameHunk, FileBlame};
+pub use absorb::{
+	absorb, absorb_plan, AbsorbHunk, AbsorbPlan, AbsorbTarget,
+};
+pub use autosquash::{
+	autosquash, commit_fixup, fixup_message, FixupKind,
+};
+pub use blame::{
+	blame_file, blame_ignore_revs, blame_parent, BlameHunk, FileBlame,
+};
 pub use branch::{
 	branch_compare_upstream, checkout_branch, checkout_commit,
 	config_is_pull_rebase, create_branch, delete_branch,
@@ -45,39 +63,63 @@ pub use branch::{
 	merge_rebase::merge_upstream_rebase, rename::rename_branch,
 	validate_branch_name, BranchCompare, BranchDetails, BranchInfo,
 };
+pub use cherry_pick::{
+	abort_cherry_pick, cherry_pick, cherry_pick_head,
+	continue_cherry_pick,
+};
 pub use commit::{amend, commit, tag_commit};
 pub use commit_details::{
 	get_commit_details, CommitDetails, CommitMessage, CommitSignature,
 };
 pub use commit_files::get_commit_files;
 pub use commit_filter::{
-	diff_contains_file, filter_commit_by_search, LogFilterSearch,
+	diff_contains_file, diff_contains_file_follow,
+	filter_commit_by_search, DateRange, LogFilterSearch,
 	LogFilterSearchOptions, SearchFields, SearchOptions,
-	SharedCommitFilterFn,
+	SharedCommitFilterFn, SharedFilePaths, SharedWalkStartFn,
 };
 pub use commit_revert::{commit_revert, revert_commit, revert_head};
 pub use commits_info::{
 	get_commit_info, get_commits_info, CommitId, CommitInfo,
+	RevisionRange,
 };
 pub use config::{
 	get_config_string, untracked_files_config,
 	ShowUntrackedFilesConfig,
 };
+pub use conflict::{
+	get_file_conflict, resolve_file_conflict, ConflictResolution,
+	ConflictSection, FileConflict,
+};
 pub use diff::get_diff_commit;
+pub use difftool::{difftool, mergetool, DiffToolTarget};
 pub use git2::BranchType;
+pub use graph::{CommitGraph, GraphCell, GraphRow};
 pub use hooks::{
 	hooks_commit_msg, hooks_post_commit, hooks_pre_commit,
 	hooks_prepare_commit_msg, HookResult, PrepareCommitMsgSource,
 };
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
 pub use ignore::add_to_ignore;
-pub use logwalker::LogWalker;
+pub use logwalker::{LogRefs, LogWalker, LogWalkerOptions};
 pub use merge::{
 	abort_pending_rebase, abort_pending_state,
-	continue_pending_rebase, merge_branch, merge_commit, merge_msg,
-	mergehead_ids, rebase_progress,
+	continue_pending_rebase, continue_pending_rebase_reword,
+	merge_branch, merge_commit, merge_msg, mergehead_ids,
+	rebase_progress,
+};
+pub use patches::{
+	apply_patch_file, export_patches, format_patch, PatchApplyMode,
+};
+pub use rebase::{rebase_branch, RebaseState};
+pub use rebase_interactive::{
+	rebase_interactive, rebase_interactive_plan,
+	rebase_stopped_for_commit, split_commit, RebaseAction,
+	RebasePlanEntry,
+};
+pub use reflog::{
+	get_reflog, get_reflog_refs, ReflogEntry, REFLOG_HEAD,
 };
-pub use rebase::rebase_branch;
 pub use remotes::{
 	get_default_remote, get_default_remote_for_push, get_remotes,
 	push::AsyncProgress, tags::PushTagsProgress,
@@ -88,7 +130,9 @@ pub use reset::{reset_repo, reset_stage, reset_workdir};
 pub use reword::reword;
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
-	get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	export_stash_patch, get_stashes, stash_apply, stash_branch,
+	stash_drop, stash_patch, stash_pop, stash_save, stash_save_files,
+	StashFile,
 };
 pub use state::{repo_state, RepoState};
 pub use status::is_workdir_clean;
@@ -105,6 +149,10 @@ pub use utils::{
 	get_head, get_head_tuple, repo_dir, repo_open_error,
 	stage_add_all, stage_add_file, stage_addremoved, Head,
 };
+pub use worktree::{
+	add_worktree, get_worktrees, is_worktree_clean, lock_worktree,
+	prune_worktrees, remove_worktree, unlock_worktree, WorktreeInfo,
+};
 
 pub use git2::ResetType;
 
@@ -1,6 +1,40 @@
-use super::diff::{get_diff_raw, DiffOptions, HunkHeader};
+//! patches for partial staging, `format-patch` style export of
+//! commits and applying patch files (like `git apply`/`git am`)
+
+use super::{
+	commit::signature_allow_undefined_name,
+	commit_files::get_commit_diff,
+	diff::{get_diff_raw, DiffOptions, HunkHeader},
+	get_commit_details,
+	repository::repo,
+	utils::work_dir,
+	CommitId, RepoPath,
+};
 use crate::error::{Error, Result};
-use git2::{Diff, DiffLine, Patch, Repository};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use git2::{
+	build::CheckoutBuilder, ApplyLocation, Diff, DiffFormat,
+	DiffLine, DiffStatsFormat, Oid, Patch, Repository, Signature,
+	Time,
+};
+use scopetime::scope_time;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// max length of the subject part of exported patch file names
+const FILE_NAME_SUBJECT_LEN: usize = 52;
+
+/// how `apply_patch_file` applies the patches of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchApplyMode {
+	/// change the working tree only (like `git apply`)
+	WorkDir,
+	/// commit each patch on top of `HEAD` (like `git am`),
+	/// nothing is committed unless all patches apply
+	Commits,
+}
 
 #[allow(clippy::redundant_pub_crate)]
 pub(crate) struct HunkLines<'a> {
@@ -80,3 +114,684 @@ fn get_patches<'a>(diff: &Diff<'a>) -> Result<Vec<Patch<'a>>> {
 
 	Ok(res)
 }
+
+/// textual patch of `diff` (like `git diff` prints it)
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn patch_text(diff: &Diff) -> Result<String> {
+	let mut patch = Vec::new();
+	diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+		if matches!(line.origin(), '+' | '-' | ' ') {
+			let mut origin = [0; 4];
+			patch.extend_from_slice(
+				line.origin().encode_utf8(&mut origin).as_bytes(),
+			);
+		}
+		patch.extend_from_slice(line.content());
+		true
+	})?;
+
+	Ok(String::from_utf8_lossy(&patch).into_owned())
+}
+
+/// commit `id` as mbox message like `git format-patch` creates it,
+/// `number` is its position within a series of `total` patches
+pub fn format_patch(
+	repo_path: &RepoPath,
+	id: CommitId,
+	number: usize,
+	total: usize,
+) -> Result<String> {
+	scope_time!("format_patch");
+
+	let details = get_commit_details(repo_path, id)?;
+	let message = details.message.unwrap_or_default();
+
+	let repo = repo(repo_path)?;
+	let diff = get_commit_diff(&repo, id, None, None, None)?;
+	let stats = diff.stats()?.to_buf(
+		DiffStatsFormat::FULL | DiffStatsFormat::INCLUDE_SUMMARY,
+		72,
+	)?;
+
+	let date = FixedOffset::east_opt(details.author.offset * 60)
+		.and_then(|tz| {
+			tz.timestamp_opt(details.author.time, 0).single()
+		})
+		.map(|date| date.to_rfc2822())
+		.unwrap_or_default();
+
+	let prefix = if total > 1 {
+		format!("[PATCH {number}/{total}]")
+	} else {
+		String::from("[PATCH]")
+	};
+
+	let mut mail = format!(
+		"From {} Mon Sep 17 00:00:00 2001\nFrom: {} <{}>\nDate: {date}\nSubject: {prefix} {}\n\n",
+		details.hash,
+		details.author.name,
+		details.author.email,
+		message.subject,
+	);
+
+	if let Some(body) = message.body.as_deref().map(str::trim) {
+		if !body.is_empty() {
+			mail.push_str(body);
+			mail.push('\n');
+		}
+	}
+
+	mail.push_str("---\n");
+	mail.push_str(stats.as_str().unwrap_or_default());
+	mail.push('\n');
+	mail.push_str(&patch_text(&diff)?);
+
+	Ok(mail)
+}
+
+/// writes one `NNNN-subject.patch` file per commit of `ids` (oldest
+/// first) into `dir` (relative to the working directory),
+/// returns the paths of the files
+pub fn export_patches(
+	repo_path: &RepoPath,
+	ids: &[CommitId],
+	dir: &Path,
+) -> Result<Vec<PathBuf>> {
+	scope_time!("export_patches");
+
+	let dir = work_dir(&repo(repo_path)?)?.join(dir);
+
+	ids.iter()
+		.enumerate()
+		.map(|(idx, id)| {
+			let number = idx + 1;
+			let content =
+				format_patch(repo_path, *id, number, ids.len())?;
+			let subject = get_commit_details(repo_path, *id)?
+				.message
+				.unwrap_or_default()
+				.subject;
+
+			let file = dir.join(format!(
+				"{number:04}-{}.patch",
+				file_name_subject(&subject)
+			));
+			fs::write(&file, content)?;
+
+			Ok(file)
+		})
+		.collect()
+}
+
+/// subject reduced to a file name like git does
+fn file_name_subject(subject: &str) -> String {
+	let mut name = String::new();
+
+	for c in subject.chars() {
+		if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+			name.push(c);
+		} else if !name.ends_with('-') {
+			name.push('-');
+		}
+	}
+
+	let name: String =
+		name.chars().take(FILE_NAME_SUBJECT_LEN).collect();
+
+	name.trim_matches(|c| c == '-' || c == '.').to_string()
+}
+
+/// applies the patch or mbox file at `path` (relative to the working
+/// directory) onto `HEAD`, returns the number of applied patches
+pub fn apply_patch_file(
+	repo_path: &RepoPath,
+	path: &Path,
+	mode: PatchApplyMode,
+) -> Result<usize> {
+	scope_time!("apply_patch_file");
+
+	let repo = repo(repo_path)?;
+	let content = fs::read(work_dir(&repo)?.join(path))?;
+	let content = String::from_utf8_lossy(&content);
+
+	let mails = split_mbox(&content)
+		.into_iter()
+		.map(PatchMail::parse)
+		.collect::<Vec<_>>();
+
+	if mails.is_empty() {
+		return Err(Error::Generic(String::from("no patch found")));
+	}
+
+	if mode == PatchApplyMode::Commits {
+		if mails.iter().any(|mail| mail.subject.is_none()) {
+			return Err(Error::Generic(String::from(
+				"patch without commit message (From/Subject)",
+			)));
+		}
+
+		if has_staged_changes(&repo)? {
+			return Err(Error::Generic(String::from(
+				"cannot apply patches as commits with staged changes",
+			)));
+		}
+	}
+
+	let patch_failed = |idx: usize, e: Error| {
+		Error::Generic(format!(
+			"patch {}/{} failed: {e}",
+			idx + 1,
+			mails.len()
+		))
+	};
+
+	if mode == PatchApplyMode::WorkDir {
+		let diffs = mails
+			.iter()
+			.enumerate()
+			.map(|(idx, mail)| {
+				Diff::from_buffer(mail.diff.as_bytes())
+					.map_err(|e| patch_failed(idx, e.into()))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		// the whole series is applied in memory first so that a
+		// failing patch leaves the working tree untouched
+		let mut tree =
+			repo.find_tree(work_dir_tree(repo_path, &repo, &diffs)?)?;
+		for (idx, diff) in diffs.iter().enumerate() {
+			let mut index = repo
+				.apply_to_tree(&tree, diff, None)
+				.map_err(|e| patch_failed(idx, e.into()))?;
+			tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+		}
+
+		for (idx, diff) in diffs.iter().enumerate() {
+			repo.apply(diff, ApplyLocation::WorkDir, None)
+				.map_err(|e| patch_failed(idx, e.into()))?;
+		}
+
+		return Ok(mails.len());
+	}
+
+	// the commits are created without touching `HEAD`, the index or
+	// the working tree so that a failing patch leaves no partial
+	// series behind
+	let mut head = repo.head()?.peel_to_commit()?.id();
+	for (idx, mail) in mails.iter().enumerate() {
+		head = commit_mail(&repo, head, mail)
+			.map_err(|e| patch_failed(idx, e))?;
+	}
+
+	let tree = repo.find_commit(head)?.tree()?;
+
+	// fails without changing anything if local changes are in the way
+	repo.checkout_tree(
+		tree.as_object(),
+		Some(&mut CheckoutBuilder::new()),
+	)
+	.map_err(|e| {
+		Error::Generic(format!(
+			"local changes would be overwritten by the patches: {e}"
+		))
+	})?;
+
+	repo.head()?.set_target(
+		head,
+		&format!("am: applied {} patches", mails.len()),
+	)?;
+
+	Ok(mails.len())
+}
+
+/// tree of the index with the files touched by `diffs` as they are
+/// in the working directory
+fn work_dir_tree(
+	repo_path: &RepoPath,
+	repo: &Repository,
+	diffs: &[Diff],
+) -> Result<Oid> {
+	let work_dir = work_dir(repo)?;
+
+	// separate handle, its index is only changed in memory and never
+	// written back
+	let scratch_repo = super::repository::repo(repo_path)?;
+	let mut index = scratch_repo.index()?;
+
+	for delta in diffs.iter().flat_map(Diff::deltas) {
+		for path in [delta.old_file().path(), delta.new_file().path()]
+			.into_iter()
+			.flatten()
+		{
+			if fs::symlink_metadata(work_dir.join(path)).is_ok() {
+				index.add_path(path)?;
+			} else {
+				index.remove_path(path)?;
+			}
+		}
+	}
+
+	Ok(index.write_tree()?)
+}
+
+/// commits the patch of `mail` on top of `parent` (without updating
+/// any reference), returns the new commit
+fn commit_mail(
+	repo: &Repository,
+	parent: Oid,
+	mail: &PatchMail,
+) -> Result<Oid> {
+	let diff = Diff::from_buffer(mail.diff.as_bytes())?;
+
+	let parent = repo.find_commit(parent)?;
+	let mut index =
+		repo.apply_to_tree(&parent.tree()?, &diff, None)?;
+	let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+
+	let committer = signature_allow_undefined_name(repo)?;
+	let author =
+		mail.author()?.unwrap_or_else(|| committer.to_owned());
+
+	let id = repo.commit(
+		None,
+		&author,
+		&committer,
+		&mail.message(),
+		&tree,
+		&[&parent],
+	)?;
+
+	Ok(id)
+}
+
+fn has_staged_changes(repo: &Repository) -> Result<bool> {
+	let head = repo.head()?.peel_to_tree()?;
+	let diff = repo.diff_tree_to_index(Some(&head), None, None)?;
+
+	Ok(diff.deltas().len() > 0)
+}
+
+/// splits mbox `content` into its mails, plain patches are a single
+/// mail without headers
+fn split_mbox(content: &str) -> Vec<&str> {
+	let mut starts = Vec::new();
+	let mut offset = 0;
+	let mut lines = content.split_inclusive('\n').peekable();
+
+	while let Some(line) = lines.next() {
+		let is_header = |l: &&str| {
+			["From:", "Date:", "Subject:"]
+				.iter()
+				.any(|header| l.starts_with(header))
+		};
+
+		if line.starts_with("From ")
+			&& lines.peek().is_some_and(is_header)
+		{
+			starts.push(offset);
+		}
+
+		offset += line.len();
+	}
+
+	if starts.is_empty() {
+		return if content.trim().is_empty() {
+			Vec::new()
+		} else {
+			vec![content]
+		};
+	}
+
+	starts
+		.iter()
+		.zip(starts.iter().skip(1).chain([&content.len()]))
+		.map(|(start, end)| &content[*start..*end])
+		.collect()
+}
+
+/// single patch of a mbox
+struct PatchMail<'a> {
+	from: Option<String>,
+	date: Option<DateTime<FixedOffset>>,
+	subject: Option<String>,
+	body: String,
+	diff: &'a str,
+}
+
+impl<'a> PatchMail<'a> {
+	fn parse(mail: &'a str) -> Self {
+		let mut from = None;
+		let mut date = None;
+		let mut subject: Option<String> = None;
+
+		let mut rest = mail;
+		if mail.starts_with("From ") {
+			let mut lines = mail.split_inclusive('\n');
+			lines.next();
+			rest = &rest
+				[rest.find('\n').map_or(rest.len(), |i| i + 1)..];
+
+			let mut last_subject = false;
+			for line in lines {
+				rest = &rest[line.len()..];
+
+				if line.trim().is_empty() {
+					break;
+				}
+
+				if line.starts_with([' ', '\t']) {
+					if let (true, Some(subject)) =
+						(last_subject, subject.as_mut())
+					{
+						subject.push_str(line.trim_end());
+					}
+					continue;
+				}
+
+				last_subject = false;
+				if let Some(value) = line.strip_prefix("From:") {
+					from = Some(value.trim().to_string());
+				} else if let Some(value) = line.strip_prefix("Date:")
+				{
+					date = DateTime::parse_from_rfc2822(value.trim())
+						.ok();
+				} else if let Some(value) =
+					line.strip_prefix("Subject:")
+				{
+					subject = Some(value.trim().to_string());
+					last_subject = true;
+				}
+			}
+		}
+
+		let diff_start = find_diff_start(rest);
+		let body = rest[..diff_start]
+			.lines()
+			.take_while(|line| *line != "---")
+			.collect::<Vec<_>>()
+			.join("\n");
+
+		Self {
+			from,
+			date,
+			subject: subject.map(|s| strip_subject_prefix(&s)),
+			body: body.trim().to_string(),
+			diff: &rest[diff_start..],
+		}
+	}
+
+	fn message(&self) -> String {
+		let subject = self.subject.clone().unwrap_or_default();
+
+		if self.body.is_empty() {
+			subject
+		} else {
+			format!("{subject}\n\n{}", self.body)
+		}
+	}
+
+	fn author(&self) -> Result<Option<Signature<'static>>> {
+		let Some((name, email)) =
+			self.from.as_deref().and_then(|from| {
+				let (name, email) = from.rsplit_once('<')?;
+				Some((
+					name.trim(),
+					email.trim_end_matches('>').trim(),
+				))
+			})
+		else {
+			return Ok(None);
+		};
+
+		let signature = match self.date {
+			Some(date) => Signature::new(
+				name,
+				email,
+				&Time::new(
+					date.timestamp(),
+					date.offset().local_minus_utc() / 60,
+				),
+			)?,
+			None => Signature::now(name, email)?,
+		};
+
+		Ok(Some(signature))
+	}
+}
+
+/// byte offset of the first line of the diff
+fn find_diff_start(text: &str) -> usize {
+	let mut offset = 0;
+	let mut lines = text.split_inclusive('\n').peekable();
+
+	while let Some(line) = lines.next() {
+		if line.starts_with("diff --git ")
+			|| (line.starts_with("--- ")
+				&& lines
+					.peek()
+					.is_some_and(|l| l.starts_with("+++ ")))
+		{
+			return offset;
+		}
+
+		offset += line.len();
+	}
+
+	text.len()
+}
+
+/// subject without the `[PATCH n/m]` like prefixes
+fn strip_subject_prefix(subject: &str) -> String {
+	let mut subject = subject.trim();
+
+	while let Some(rest) = subject.strip_prefix('[') {
+		let Some(end) = rest.find(']') else {
+			break;
+		};
+		subject = rest[end + 1..].trim_start();
+	}
+
+	subject.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		tests::{repo_init, write_commit_file},
+		utils::repo_read_file,
+	};
+	use git2::ResetType;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_strip_subject_prefix() {
+		assert_eq!(strip_subject_prefix("[PATCH 1/2] foo"), "foo");
+		assert_eq!(strip_subject_prefix("[PATCH] [RFC] foo"), "foo");
+		assert_eq!(strip_subject_prefix("foo [bar]"), "foo [bar]");
+		assert_eq!(
+			file_name_subject("fix: the (big) bug!"),
+			"fix-the-big-bug"
+		);
+	}
+
+	#[test]
+	fn test_export_and_apply() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "c1");
+		let c2 = write_commit_file(
+			&repo,
+			"b.txt",
+			"b\n",
+			"add b\n\nsome body",
+		);
+		let c3 =
+			write_commit_file(&repo, "b.txt", "b\nc\n", "change b");
+
+		let files =
+			export_patches(repo_path, &[c2, c3], Path::new(""))
+				.unwrap();
+
+		assert_eq!(
+			files,
+			vec![
+				root.join("0001-add-b.patch"),
+				root.join("0002-change-b.patch")
+			]
+		);
+
+		let first = fs::read_to_string(&files[0]).unwrap();
+		assert!(first.starts_with(&format!("From {c2} ")));
+		assert!(first.contains("\nFrom: name <email>\n"));
+		assert!(first.contains(
+			"\nSubject: [PATCH 1/2] add b\n\nsome body\n---\n"
+		));
+		assert!(first.contains("\n+++ b/b.txt\n@@ -0,0 +1 @@\n+b\n"));
+
+		// a series in one mbox, with the signature git appends
+		let second = fs::read_to_string(&files[1]).unwrap();
+		fs::write(
+			root.join("series.mbox"),
+			format!("{first}-- \n2.43.0\n\n{second}"),
+		)
+		.unwrap();
+
+		let reset_to_c1 = || {
+			repo.reset(
+				repo.find_commit(c1.into()).unwrap().as_object(),
+				ResetType::Hard,
+				None,
+			)
+			.unwrap();
+		};
+
+		reset_to_c1();
+		assert_eq!(
+			apply_patch_file(
+				repo_path,
+				Path::new("series.mbox"),
+				PatchApplyMode::Commits
+			)
+			.unwrap(),
+			2
+		);
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.message(), Some("change b"));
+		let parent = head.parent(0).unwrap();
+		assert_eq!(parent.message(), Some("add b\n\nsome body"));
+		assert_eq!(parent.parent_id(0).unwrap(), c1.into());
+		assert_eq!(
+			get_commit_details(repo_path, parent.id().into())
+				.unwrap()
+				.author,
+			get_commit_details(repo_path, c2).unwrap().author
+		);
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b\nc\n");
+
+		reset_to_c1();
+		assert_eq!(
+			apply_patch_file(
+				repo_path,
+				Path::new("0001-add-b.patch"),
+				PatchApplyMode::WorkDir
+			)
+			.unwrap(),
+			1
+		);
+
+		assert_eq!(
+			repo.head().unwrap().peel_to_commit().unwrap().id(),
+			c1.into()
+		);
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b\n");
+	}
+
+	#[test]
+	fn test_apply_failing_patch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b\n", "add b");
+		let c3 =
+			write_commit_file(&repo, "a.txt", "a\nc\n", "change a");
+
+		let files =
+			export_patches(repo_path, &[c2, c3], Path::new(""))
+				.unwrap();
+		fs::write(
+			root.join("series.mbox"),
+			format!(
+				"{}{}",
+				fs::read_to_string(&files[0]).unwrap(),
+				fs::read_to_string(&files[1]).unwrap()
+			),
+		)
+		.unwrap();
+
+		// the second patch does not apply onto this
+		repo.reset(
+			repo.find_commit(c1.into()).unwrap().as_object(),
+			ResetType::Hard,
+			None,
+		)
+		.unwrap();
+		let head = write_commit_file(&repo, "a.txt", "x\n", "other");
+
+		let res = apply_patch_file(
+			repo_path,
+			Path::new("series.mbox"),
+			PatchApplyMode::Commits,
+		);
+
+		assert!(res.unwrap_err().to_string().contains("patch 2/2"));
+		assert_eq!(
+			repo.head().unwrap().peel_to_commit().unwrap().id(),
+			head.into()
+		);
+		assert!(!root.join("b.txt").exists());
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "x\n");
+		assert!(!has_staged_changes(&repo).unwrap());
+
+		let res = apply_patch_file(
+			repo_path,
+			Path::new("series.mbox"),
+			PatchApplyMode::WorkDir,
+		);
+
+		assert!(res.unwrap_err().to_string().contains("patch 2/2"));
+		assert!(!root.join("b.txt").exists());
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "x\n");
+
+		// local changes in the way of a patch
+		repo.reset(
+			repo.find_commit(c1.into()).unwrap().as_object(),
+			ResetType::Hard,
+			None,
+		)
+		.unwrap();
+		fs::write(root.join("b.txt"), "local\n").unwrap();
+
+		assert!(apply_patch_file(
+			repo_path,
+			Path::new("series.mbox"),
+			PatchApplyMode::Commits,
+		)
+		.is_err());
+		assert_eq!(
+			repo.head().unwrap().peel_to_commit().unwrap().id(),
+			c1.into()
+		);
+		assert_eq!(
+			repo_read_file(&repo, "b.txt").unwrap(),
+			"local\n"
+		);
+	}
+}
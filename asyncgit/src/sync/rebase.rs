@@ -69,12 +69,19 @@ pub fn conflict_free_rebase(
 }
 
 ///
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum RebaseState {
 	///
 	Finished,
 	///
 	Conflicted,
+	/// interactive rebase stopped after committing this commit for amending
+	Edit(CommitId),
+	/// interactive rebase waits for a new message of this commit
+	Reword(CommitId),
+	/// interactive rebase stopped with the changes of this commit
+	/// unstaged to commit them in parts
+	Split(CommitId),
 }
 
 /// rebase
@@ -110,6 +117,12 @@ pub fn rebase(
 pub fn continue_rebase(
 	repo: &git2::Repository,
 ) -> Result<RebaseState> {
+	if super::rebase_interactive::is_interactive(repo) {
+		return super::rebase_interactive::continue_interactive(
+			repo, None,
+		);
+	}
+
 	let mut rebase = repo.open_rebase(None)?;
 	let signature =
 		crate::sync::commit::signature_allow_undefined_name(repo)?;
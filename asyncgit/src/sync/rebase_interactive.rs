@@ -0,0 +1,991 @@
+//! interactive rebase
+//!
+//! the plan is stored next to libgit2s own rebase state
+//! (`.git/rebase-merge`) so that `get_rebase_progress`,
+//! `abort_rebase` and `repo_state` keep working unchanged.
+
+use super::{
+	autosquash::FixupKind, commit::signature_allow_undefined_name,
+	rebase::RebaseState, repository::repo, CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	ErrorCode, Oid, Rebase, Repository, Signature, Sort,
+	StatusOptions,
+};
+use scopetime::scope_time;
+use std::{collections::HashSet, fs, path::PathBuf, str::FromStr};
+
+const REBASE_DIR: &str = "rebase-merge";
+const TODO_FILE: &str = "gitui-todo";
+const DONE_FILE: &str = "gitui-done";
+
+/// what to do with a commit during an interactive rebase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebaseAction {
+	/// use commit
+	Pick,
+	/// use commit, but edit the commit message
+	Reword,
+	/// use commit, but stop for amending
+	Edit,
+	/// use commit, but stop with its changes unstaged
+	/// to commit them in parts
+	Split,
+	/// meld into previous commit, combining the messages
+	Squash,
+	/// meld into previous commit, discarding this message
+	Fixup,
+	/// meld into previous commit, replacing its message with the
+	/// body of this (`amend!`) commit
+	Amend,
+	/// remove commit
+	Drop,
+}
+
+impl RebaseAction {
+	/// name as used in a git rebase todo list
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Pick => "pick",
+			Self::Reword => "reword",
+			Self::Edit => "edit",
+			Self::Split => "split",
+			Self::Squash => "squash",
+			Self::Fixup => "fixup",
+			Self::Amend => "amend",
+			Self::Drop => "drop",
+		}
+	}
+
+	#[allow(clippy::redundant_pub_crate)]
+	pub(crate) const fn melds(self) -> bool {
+		matches!(self, Self::Squash | Self::Fixup | Self::Amend)
+	}
+}
+
+impl FromStr for RebaseAction {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		match s {
+			"pick" => Ok(Self::Pick),
+			"reword" => Ok(Self::Reword),
+			"edit" => Ok(Self::Edit),
+			"split" => Ok(Self::Split),
+			"squash" => Ok(Self::Squash),
+			"fixup" => Ok(Self::Fixup),
+			"amend" => Ok(Self::Amend),
+			"drop" => Ok(Self::Drop),
+			_ => Err(Error::Generic(format!(
+				"unknown rebase action: {s}"
+			))),
+		}
+	}
+}
+
+/// single step of an interactive rebase plan
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebasePlanEntry {
+	///
+	pub action: RebaseAction,
+	///
+	pub id: CommitId,
+	/// first line of the commit message
+	pub summary: String,
+}
+
+/// returns all commits between `base` (exclusive) and HEAD,
+/// oldest first, each set to `pick`.
+/// merge commits are skipped, just like libgit2 does when rebasing.
+pub fn rebase_interactive_plan(
+	repo_path: &RepoPath,
+	base: CommitId,
+) -> Result<Vec<RebasePlanEntry>> {
+	scope_time!("rebase_interactive_plan");
+
+	let repo = repo(repo_path)?;
+
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+	walk.push_head()?;
+	walk.hide(base.into())?;
+
+	let mut plan = Vec::new();
+	for id in walk {
+		let commit = repo.find_commit(id?)?;
+
+		if commit.parent_count() > 1 {
+			continue;
+		}
+
+		plan.push(RebasePlanEntry {
+			action: RebaseAction::Pick,
+			id: commit.id().into(),
+			summary: commit.summary().unwrap_or_default().into(),
+		});
+	}
+
+	if plan.is_empty() {
+		return Err(Error::Generic(String::from(
+			"nothing to rebase: base is not an ancestor of HEAD",
+		)));
+	}
+
+	Ok(plan)
+}
+
+/// rebases HEAD onto `base` executing `plan` step by step.
+///
+/// stops on conflicts, on `edit` steps (after committing) and on
+/// `reword` steps (before committing), use
+/// [`continue_pending_rebase`](super::continue_pending_rebase) or
+/// [`continue_pending_rebase_reword`](super::continue_pending_rebase_reword)
+/// to resume.
+pub fn rebase_interactive(
+	repo_path: &RepoPath,
+	base: CommitId,
+	plan: &[RebasePlanEntry],
+) -> Result<RebaseState> {
+	scope_time!("rebase_interactive");
+
+	validate_plan(repo_path, base, plan)?;
+
+	let repo = repo(repo_path)?;
+
+	let onto = repo.find_annotated_commit(base.into())?;
+
+	// let libgit2 setup its state (checking out `onto`),
+	// we replace the list of commits right afterwards
+	drop(repo.rebase(None, Some(&onto), None, None)?);
+
+	if let Err(e) = write_plan(&repo, plan) {
+		repo.open_rebase(None)?.abort()?;
+		return Err(e);
+	}
+
+	continue_interactive(&repo, None)
+}
+
+/// splits `commit` into multiple commits.
+///
+/// rebases HEAD onto the parent of `commit` and stops right after
+/// it with its changes reset into the working directory. commit them
+/// in parts and use
+/// [`continue_pending_rebase`](super::continue_pending_rebase) to
+/// replay the commits on top. changes still staged by then are
+/// committed using the original message.
+pub fn split_commit(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<RebaseState> {
+	scope_time!("split_commit");
+
+	let base: CommitId = {
+		let repo = repo(repo_path)?;
+		let commit = repo.find_commit(commit.into())?;
+
+		if commit.parent_count() > 1 {
+			return Err(Error::Generic(String::from(
+				"cannot split a merge commit",
+			)));
+		}
+
+		commit.parent_id(0).map_err(|_| Error::NoParent)?.into()
+	};
+
+	let mut plan = rebase_interactive_plan(repo_path, base)?;
+
+	match plan.first_mut() {
+		Some(entry) if entry.id == commit => {
+			entry.action = RebaseAction::Split;
+		}
+		_ => {
+			return Err(Error::Generic(String::from(
+				"commit is not part of the current branch",
+			)));
+		}
+	}
+
+	rebase_interactive(repo_path, base, &plan).map_err(|e| {
+		if let Ok(repo) = repo(repo_path) {
+			if let Ok(mut rebase) = repo.open_rebase(None) {
+				if let Err(e) = rebase.abort() {
+					log::error!("abort split failed: {e}");
+				}
+			}
+		}
+		e
+	})
+}
+
+/// checks `plan` before touching the repository, all of its commits
+/// have to be part of `base..HEAD`
+fn validate_plan(
+	repo_path: &RepoPath,
+	base: CommitId,
+	plan: &[RebasePlanEntry],
+) -> Result<()> {
+	let commits = rebase_interactive_plan(repo_path, base)?
+		.into_iter()
+		.map(|entry| entry.id)
+		.collect::<HashSet<_>>();
+
+	let mut seen = HashSet::with_capacity(plan.len());
+	for entry in plan {
+		if !commits.contains(&entry.id) {
+			return Err(Error::Generic(format!(
+				"commit {} is not part of {}..HEAD",
+				entry.id.get_short_string(),
+				base.get_short_string()
+			)));
+		}
+
+		if !seen.insert(entry.id) {
+			return Err(Error::Generic(format!(
+				"commit {} is listed more than once",
+				entry.id.get_short_string()
+			)));
+		}
+	}
+
+	let first =
+		plan.iter().find(|entry| entry.action != RebaseAction::Drop);
+
+	if first.is_some_and(|entry| entry.action.melds()) {
+		return Err(Error::Generic(String::from(
+			"cannot squash/fixup without a previous commit",
+		)));
+	}
+
+	Ok(())
+}
+
+fn state_path(repo: &Repository) -> PathBuf {
+	repo.path().join(REBASE_DIR)
+}
+
+/// `true` if the pending rebase was started by [`rebase_interactive`]
+pub fn is_interactive(repo: &Repository) -> bool {
+	state_path(repo).join(TODO_FILE).is_file()
+}
+
+/// `true` if the pending interactive rebase stopped after an `edit`
+/// or `split` step, where new commits are to be created before
+/// continuing
+pub fn rebase_stopped_for_commit(
+	repo_path: &RepoPath,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	if !is_interactive(&repo) {
+		return Ok(false);
+	}
+
+	let mut rebase = repo.open_rebase(None)?;
+	let Some(current) = rebase.operation_current() else {
+		return Ok(false);
+	};
+
+	let action = read_todo(&repo)?.get(current).copied();
+
+	Ok(read_done(&repo) > current
+		&& matches!(
+			action,
+			Some(RebaseAction::Edit | RebaseAction::Split)
+		))
+}
+
+fn write_plan(
+	repo: &Repository,
+	plan: &[RebasePlanEntry],
+) -> Result<()> {
+	let path = state_path(repo);
+
+	let old_end: usize = fs::read_to_string(path.join("end"))?
+		.trim()
+		.parse()
+		.map_err(|_| {
+			Error::Generic(String::from("invalid rebase state"))
+		})?;
+
+	for i in 1..=old_end {
+		fs::remove_file(path.join(format!("cmt.{i}")))?;
+	}
+
+	let steps = plan
+		.iter()
+		.filter(|entry| entry.action != RebaseAction::Drop)
+		.collect::<Vec<_>>();
+
+	for (i, entry) in steps.iter().enumerate() {
+		fs::write(
+			path.join(format!("cmt.{}", i + 1)),
+			format!("{}\n", entry.id),
+		)?;
+	}
+
+	let todo = steps
+		.iter()
+		.map(|entry| {
+			format!("{} {}", entry.action.as_str(), entry.id)
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	fs::write(path.join("end"), format!("{}\n", steps.len()))?;
+	fs::write(path.join(TODO_FILE), todo)?;
+
+	Ok(())
+}
+
+fn read_todo(repo: &Repository) -> Result<Vec<RebaseAction>> {
+	fs::read_to_string(state_path(repo).join(TODO_FILE))?
+		.lines()
+		.map(|line| {
+			line.split_whitespace()
+				.next()
+				.unwrap_or_default()
+				.parse::<RebaseAction>()
+		})
+		.collect()
+}
+
+/// amount of steps that are completely applied
+fn read_done(repo: &Repository) -> usize {
+	fs::read_to_string(state_path(repo).join(DONE_FILE))
+		.ok()
+		.and_then(|s| s.trim().parse().ok())
+		.unwrap_or_default()
+}
+
+fn write_done(repo: &Repository, done: usize) -> Result<()> {
+	fs::write(state_path(repo).join(DONE_FILE), format!("{done}\n"))?;
+	Ok(())
+}
+
+/// continue a pending interactive rebase,
+/// `message` is used if the rebase stopped on a `reword` step
+pub fn continue_interactive(
+	repo: &Repository,
+	message: Option<&str>,
+) -> Result<RebaseState> {
+	let mut rebase = repo.open_rebase(None)?;
+	let todo = read_todo(repo)?;
+	let signature = signature_allow_undefined_name(repo)?;
+
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	if let Some(current) = rebase.operation_current() {
+		let action = todo.get(current).copied().ok_or_else(|| {
+			Error::Generic(String::from("invalid rebase todo"))
+		})?;
+
+		if read_done(repo) > current {
+			// we stopped after the step was applied
+			if action == RebaseAction::Edit {
+				amend_staged(repo, &signature)?;
+			} else if action == RebaseAction::Split {
+				let commit_id = rebase
+					.nth(current)
+					.map(|op| op.id())
+					.ok_or_else(|| {
+						Error::Generic(String::from(
+							"invalid rebase operation",
+						))
+					})?;
+
+				commit_split_rest(repo, &signature, commit_id)?;
+			}
+		} else if let Some(state) = apply_step(
+			repo,
+			&mut rebase,
+			&signature,
+			action,
+			message,
+		)? {
+			return Ok(state);
+		}
+	}
+
+	while let Some(op) = rebase.next() {
+		let _op = op?;
+
+		if repo.index()?.has_conflicts() {
+			return Ok(RebaseState::Conflicted);
+		}
+
+		let current = rebase.operation_current().unwrap_or_default();
+		let action = todo.get(current).copied().ok_or_else(|| {
+			Error::Generic(String::from("invalid rebase todo"))
+		})?;
+
+		if let Some(state) =
+			apply_step(repo, &mut rebase, &signature, action, None)?
+		{
+			return Ok(state);
+		}
+	}
+
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	rebase.finish(Some(&signature))?;
+
+	Ok(RebaseState::Finished)
+}
+
+/// applies the current (already merged) step,
+/// returns `Some` if the rebase has to stop here
+fn apply_step(
+	repo: &Repository,
+	rebase: &mut Rebase,
+	signature: &Signature,
+	action: RebaseAction,
+	message: Option<&str>,
+) -> Result<Option<RebaseState>> {
+	let current = rebase.operation_current().unwrap_or_default();
+	let commit_id = rebase
+		.nth(current)
+		.map(|op| CommitId::from(op.id()))
+		.ok_or_else(|| {
+			Error::Generic(String::from("invalid rebase operation"))
+		})?;
+
+	let stop = match action {
+		RebaseAction::Pick | RebaseAction::Drop => {
+			commit_step(rebase, signature, None)?;
+			None
+		}
+		RebaseAction::Reword => {
+			if message.is_none() {
+				return Ok(Some(RebaseState::Reword(commit_id)));
+			}
+
+			commit_step(rebase, signature, message)?;
+			None
+		}
+		RebaseAction::Edit => {
+			let id = commit_step(rebase, signature, None)?;
+			Some(RebaseState::Edit(id.unwrap_or(commit_id)))
+		}
+		RebaseAction::Split => {
+			// nothing to split if the commit became empty
+			if commit_step(rebase, signature, None)?.is_some() {
+				unstage_head(repo)?;
+				Some(RebaseState::Split(commit_id))
+			} else {
+				None
+			}
+		}
+		RebaseAction::Squash
+		| RebaseAction::Fixup
+		| RebaseAction::Amend => {
+			meld_into_head(repo, signature, commit_id, action)?;
+			None
+		}
+	};
+
+	write_done(repo, current + 1)?;
+
+	Ok(stop)
+}
+
+/// commits the current step, skipping it if it became empty
+fn commit_step(
+	rebase: &mut Rebase,
+	signature: &Signature,
+	message: Option<&str>,
+) -> Result<Option<CommitId>> {
+	match rebase.commit(None, signature, message) {
+		Ok(id) => Ok(Some(id.into())),
+		Err(e) if e.code() == ErrorCode::Applied => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// amends HEAD with the current index, combining the messages
+/// according to the meld `action`
+fn meld_into_head(
+	repo: &Repository,
+	signature: &Signature,
+	commit_id: CommitId,
+	action: RebaseAction,
+) -> Result<Oid> {
+	let head = repo.head()?.peel_to_commit()?;
+	let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+	let head_msg = head.message().unwrap_or_default().trim_end();
+
+	let msg = match action {
+		RebaseAction::Squash => {
+			let commit = repo.find_commit(commit_id.into())?;
+			let msg = commit.message().unwrap_or_default();
+			let msg = strip_subject(msg, FixupKind::Squash.prefix())
+				.unwrap_or(msg)
+				.trim();
+
+			if msg.is_empty() {
+				head_msg.to_string()
+			} else {
+				format!("{head_msg}\n\n{msg}")
+			}
+		}
+		RebaseAction::Amend => {
+			let commit = repo.find_commit(commit_id.into())?;
+			let msg = commit.message().unwrap_or_default();
+
+			strip_subject(msg, FixupKind::Amend.prefix())
+				.unwrap_or(msg)
+				.trim()
+				.to_string()
+		}
+		_ => head_msg.to_string(),
+	};
+
+	let id = head.amend(
+		Some("HEAD"),
+		None,
+		Some(signature),
+		None,
+		Some(&msg),
+		Some(&tree),
+	)?;
+
+	Ok(id)
+}
+
+/// message without its first line if that starts with `prefix`
+fn strip_subject<'a>(msg: &'a str, prefix: &str) -> Option<&'a str> {
+	msg.starts_with(prefix)
+		.then(|| msg.split_once('\n').map_or("", |(_, body)| body))
+}
+
+/// amends HEAD if the index contains changes
+fn amend_staged(
+	repo: &Repository,
+	signature: &Signature,
+) -> Result<()> {
+	let head = repo.head()?.peel_to_commit()?;
+	let tree_id = repo.index()?.write_tree()?;
+
+	if tree_id != head.tree_id() {
+		let tree = repo.find_tree(tree_id)?;
+		head.amend(
+			Some("HEAD"),
+			None,
+			Some(signature),
+			None,
+			None,
+			Some(&tree),
+		)?;
+	}
+
+	Ok(())
+}
+
+/// moves HEAD to its parent keeping the changes in the workdir.
+///
+/// this is a mixed reset, `Repository::reset` cannot be used as it
+/// cleans up the rebase state
+fn unstage_head(repo: &Repository) -> Result<()> {
+	let parent = repo.head()?.peel_to_commit()?.parent(0)?;
+
+	let mut index = repo.index()?;
+	index.read_tree(&parent.tree()?)?;
+	index.write()?;
+
+	repo.set_head_detached(parent.id())?;
+
+	Ok(())
+}
+
+/// commits changes left staged after splitting `commit_id`
+/// using its message, fails if unstaged changes are left
+fn commit_split_rest(
+	repo: &Repository,
+	signature: &Signature,
+	commit_id: Oid,
+) -> Result<()> {
+	let mut opts = StatusOptions::new();
+	opts.include_untracked(false);
+
+	let unstaged =
+		repo.statuses(Some(&mut opts))?.iter().any(|entry| {
+			entry.status().is_wt_modified()
+				|| entry.status().is_wt_deleted()
+				|| entry.status().is_wt_renamed()
+				|| entry.status().is_wt_typechange()
+		});
+
+	if unstaged {
+		return Err(Error::Generic(String::from(
+			"unstaged changes left: commit or stash them before continuing",
+		)));
+	}
+
+	let head = repo.head()?.peel_to_commit()?;
+	let tree_id = repo.index()?.write_tree()?;
+
+	if tree_id != head.tree_id() {
+		let original = repo.find_commit(commit_id)?;
+		let tree = repo.find_tree(tree_id)?;
+
+		repo.commit(
+			Some("HEAD"),
+			&original.author(),
+			signature,
+			original.message().unwrap_or_default(),
+			&tree,
+			&[&head],
+		)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit, continue_pending_rebase,
+		continue_pending_rebase_reword, get_commit_info,
+		rebase_progress, repo_state, stage_add_file,
+		tests::{get_commit_ids, repo_init, write_commit_file},
+		utils::{get_head_repo, repo_read_file, repo_write_file},
+		RepoState,
+	};
+	use pretty_assertions::assert_eq;
+	use std::path::Path;
+
+	fn messages(
+		repo_path: &RepoPath,
+		repo: &Repository,
+	) -> Vec<String> {
+		get_commit_ids(repo, 10)
+			.iter()
+			.map(|id| get_commit_info(repo_path, id).unwrap().message)
+			.collect()
+	}
+
+	fn plan_with(
+		repo_path: &RepoPath,
+		base: CommitId,
+		actions: &[(usize, RebaseAction)],
+	) -> Vec<RebasePlanEntry> {
+		let plan = rebase_interactive_plan(repo_path, base).unwrap();
+
+		actions
+			.iter()
+			.map(|(idx, action)| RebasePlanEntry {
+				action: *action,
+				..plan[*idx].clone()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_reorder_and_drop() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		write_commit_file(&repo, "c.txt", "c", "c3");
+		write_commit_file(&repo, "d.txt", "d", "c4");
+
+		let plan = plan_with(
+			repo_path,
+			base,
+			&[
+				(2, RebaseAction::Pick),
+				(1, RebaseAction::Drop),
+				(0, RebaseAction::Pick),
+			],
+		);
+
+		let res = rebase_interactive(repo_path, base, &plan).unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			messages(repo_path, &repo),
+			vec!["c2", "c4", "c1", "initial"]
+		);
+		assert!(!root.join("c.txt").exists());
+	}
+
+	#[test]
+	fn test_squash_fixup() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		write_commit_file(&repo, "c.txt", "c", "c3");
+		write_commit_file(&repo, "d.txt", "d", "c4");
+
+		let plan = plan_with(
+			repo_path,
+			base,
+			&[
+				(0, RebaseAction::Pick),
+				(1, RebaseAction::Squash),
+				(2, RebaseAction::Fixup),
+			],
+		);
+
+		let res = rebase_interactive(repo_path, base, &plan).unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+
+		let ids = get_commit_ids(&repo, 10);
+		assert_eq!(ids.len(), 3);
+		assert_eq!(
+			get_commit_info(repo_path, &ids[0]).unwrap().message,
+			"c2\n\nc3"
+		);
+		assert!(root.join("d.txt").exists());
+	}
+
+	#[test]
+	fn test_edit_and_reword() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		write_commit_file(&repo, "c.txt", "c", "c3");
+
+		let plan = plan_with(
+			repo_path,
+			base,
+			&[(0, RebaseAction::Edit), (1, RebaseAction::Reword)],
+		);
+
+		let res = rebase_interactive(repo_path, base, &plan).unwrap();
+
+		assert!(matches!(res, RebaseState::Edit(_)));
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(rebase_progress(repo_path).unwrap().steps, 2);
+
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+
+		let res = continue_pending_rebase(repo_path).unwrap();
+
+		assert!(matches!(res, RebaseState::Reword(_)));
+
+		let res =
+			continue_pending_rebase_reword(repo_path, "reworded")
+				.unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+		assert_eq!(
+			messages(repo_path, &repo),
+			vec!["reworded", "c2", "c1", "initial"]
+		);
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+	}
+
+	#[test]
+	fn test_conflict_continue() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "a.txt", "b", "c2");
+		write_commit_file(&repo, "a.txt", "c", "c3");
+
+		let plan = plan_with(
+			repo_path,
+			base,
+			&[(1, RebaseAction::Pick), (0, RebaseAction::Pick)],
+		);
+
+		let res = rebase_interactive(repo_path, base, &plan).unwrap();
+
+		assert_eq!(res, RebaseState::Conflicted);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+
+		repo_write_file(&repo, "a.txt", "c").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		let res = continue_pending_rebase(repo_path).unwrap();
+
+		// second step conflicts as well
+		assert_eq!(res, RebaseState::Conflicted);
+
+		repo_write_file(&repo, "a.txt", "b").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		let res = continue_pending_rebase(repo_path).unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+		assert_eq!(
+			messages(repo_path, &repo),
+			vec!["c2", "c3", "c1", "initial"]
+		);
+	}
+
+	#[test]
+	fn test_invalid_plan() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		let plan =
+			plan_with(repo_path, base, &[(0, RebaseAction::Fixup)]);
+
+		assert!(rebase_interactive(repo_path, base, &plan).is_err());
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+		let head = get_head_repo(&repo).unwrap();
+		let valid =
+			plan_with(repo_path, base, &[(0, RebaseAction::Pick)]);
+
+		// commit outside of `base..HEAD`
+		let mut plan = valid.clone();
+		plan[0].id = base;
+		assert!(rebase_interactive(repo_path, base, &plan).is_err());
+
+		// commit not in the repository
+		plan[0].id = CommitId::new(Oid::zero());
+		assert!(rebase_interactive(repo_path, base, &plan).is_err());
+
+		// commit listed twice
+		let plan = [valid[0].clone(), valid[0].clone()];
+		assert!(rebase_interactive(repo_path, base, &plan).is_err());
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(get_head_repo(&repo).unwrap(), head);
+		assert!(!state_path(&repo).exists());
+	}
+
+	#[test]
+	fn test_split() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		repo_write_file(&repo, "b.txt", "b").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		let c2 = write_commit_file(&repo, "c.txt", "c", "c2");
+		write_commit_file(&repo, "d.txt", "d", "c3");
+
+		let res = split_commit(repo_path, c2).unwrap();
+
+		assert_eq!(res, RebaseState::Split(c2));
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(get_head_repo(&repo).unwrap(), base);
+
+		stage_add_file(repo_path, Path::new("c.txt")).unwrap();
+		commit(repo_path, "part").unwrap();
+
+		// unstaged changes block continuing
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		assert!(continue_pending_rebase(repo_path).is_err());
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+
+		let res = continue_pending_rebase(repo_path).unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			messages(repo_path, &repo),
+			vec!["c3", "c2", "part", "c1", "initial"]
+		);
+		assert!(root.join("d.txt").exists());
+	}
+
+	#[test]
+	fn test_split_commit_and_continue() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		repo_write_file(&repo, "b.txt", "b").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		let c2 = write_commit_file(&repo, "c.txt", "c", "c2");
+
+		assert!(!rebase_stopped_for_commit(repo_path).unwrap());
+
+		split_commit(repo_path, c2).unwrap();
+
+		assert!(rebase_stopped_for_commit(repo_path).unwrap());
+
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		commit(repo_path, "part b").unwrap();
+
+		assert!(rebase_stopped_for_commit(repo_path).unwrap());
+
+		stage_add_file(repo_path, Path::new("c.txt")).unwrap();
+		commit(repo_path, "part c").unwrap();
+
+		let res = continue_pending_rebase(repo_path).unwrap();
+
+		assert_eq!(res, RebaseState::Finished);
+		assert!(!rebase_stopped_for_commit(repo_path).unwrap());
+		assert_eq!(
+			messages(repo_path, &repo),
+			vec!["part c", "part b", "c1", "initial"]
+		);
+	}
+
+	#[test]
+	fn test_reword_does_not_allow_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		let plan =
+			plan_with(repo_path, base, &[(0, RebaseAction::Reword)]);
+		let res = rebase_interactive(repo_path, base, &plan).unwrap();
+
+		assert!(matches!(res, RebaseState::Reword(_)));
+		assert!(!rebase_stopped_for_commit(repo_path).unwrap());
+	}
+
+	#[test]
+	fn test_split_invalid() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		// dirty workdir
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+
+		assert!(split_commit(repo_path, c1).is_err());
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+	}
+}
@@ -0,0 +1,127 @@
+//! reflog of HEAD and branches
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::BranchType;
+use scopetime::scope_time;
+
+/// name of the reflog of `HEAD`
+pub const REFLOG_HEAD: &str = "HEAD";
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+	/// commit the ref pointed to before
+	pub old_id: CommitId,
+	/// commit the ref pointed to afterwards
+	pub new_id: CommitId,
+	/// action that moved the ref (e.g. `commit: msg`)
+	pub message: String,
+	///
+	pub committer: String,
+	///
+	pub time: i64,
+}
+
+/// references that have a reflog: `HEAD` and local branches
+pub fn get_reflog_refs(repo_path: &RepoPath) -> Result<Vec<String>> {
+	scope_time!("get_reflog_refs");
+
+	let repo = repo(repo_path)?;
+
+	let mut refs = vec![REFLOG_HEAD.to_string()];
+
+	for branch in repo.branches(Some(BranchType::Local))? {
+		let (branch, _) = branch?;
+
+		if let Some(name) = branch.get().name() {
+			if !repo.reflog(name)?.is_empty() {
+				refs.push(name.to_string());
+			}
+		}
+	}
+
+	Ok(refs)
+}
+
+/// entries of the reflog of `reference`, newest first
+pub fn get_reflog(
+	repo_path: &RepoPath,
+	reference: &str,
+) -> Result<Vec<ReflogEntry>> {
+	scope_time!("get_reflog");
+
+	let repo = repo(repo_path)?;
+	let reflog = repo.reflog(reference)?;
+
+	let entries = reflog
+		.iter()
+		.map(|entry| {
+			let committer = entry.committer();
+
+			ReflogEntry {
+				old_id: entry.id_old().into(),
+				new_id: entry.id_new().into(),
+				message: entry
+					.message_bytes()
+					.map(String::from_utf8_lossy)
+					.unwrap_or_default()
+					.into_owned(),
+				committer: String::from_utf8_lossy(
+					committer.name_bytes(),
+				)
+				.into_owned(),
+				time: committer.when().seconds(),
+			}
+		})
+		.collect();
+
+	Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_head_reflog() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		let entries = get_reflog(repo_path, REFLOG_HEAD).unwrap();
+
+		assert_eq!(entries[0].old_id, c1);
+		assert_eq!(entries[0].new_id, c2);
+		assert_eq!(entries[0].message, "commit: c2");
+		assert_eq!(entries[1].new_id, c1);
+	}
+
+	#[test]
+	fn test_reflog_refs() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "other").unwrap();
+
+		assert_eq!(
+			get_reflog_refs(repo_path).unwrap(),
+			vec![
+				String::from("HEAD"),
+				String::from("refs/heads/master"),
+				String::from("refs/heads/other"),
+			]
+		);
+	}
+}
@@ -3,11 +3,13 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		diff::DiffLinePosition,
-		patches::get_file_diff_patch_and_hunklines, repository::repo,
+		patches::{get_file_diff_patch_and_hunklines, HunkLines},
+		repository::repo,
 		RepoPath,
 	},
 };
 use easy_cast::Conv;
+use git2::{DiffLineType, IndexEntry, Repository};
 use scopetime::scope_time;
 use std::path::Path;
 
@@ -29,20 +31,63 @@ pub fn stage_lines(
 
 	let mut index = repo.index()?;
 	index.read(true)?;
-	let mut idx =
-		index.get_path(Path::new(file_path), 0).ok_or_else(|| {
-			Error::Generic(String::from(
-				"only non new files supported",
-			))
-		})?;
+	match index.get_path(Path::new(file_path), 0) {
+		Some(idx) => stage_lines_of_entry(
+			&repo, idx, file_path, is_stage, lines,
+		),
+		None if !is_stage => {
+			let idx = intent_to_add(&repo, file_path)?;
+
+			let res = stage_lines_of_entry(
+				&repo, idx, file_path, false, lines,
+			);
+
+			// do not leave the file half added behind
+			if res.is_err() {
+				index.read(true)?;
+				index.remove_path(Path::new(file_path))?;
+				index.write()?;
+			}
+
+			res
+		}
+		None => Err(Error::Generic(String::from(
+			"file not found in index",
+		))),
+	}
+}
+
+/// stages (or unstages) `lines` of `file_path` whose index entry is
+/// `idx`
+fn stage_lines_of_entry(
+	repo: &Repository,
+	mut idx: IndexEntry,
+	file_path: &str,
+	is_stage: bool,
+	lines: &[DiffLinePosition],
+) -> Result<()> {
+	let mut index = repo.index()?;
+	index.read(true)?;
+
 	let blob = repo.find_blob(idx.id)?;
 	let indexed_content = String::from_utf8(blob.content().into())?;
 
-	let new_content = {
-		let (_patch, hunks) = get_file_diff_patch_and_hunklines(
-			&repo, file_path, is_stage, false,
-		)?;
+	let (_patch, hunks) = get_file_diff_patch_and_hunklines(
+		repo, file_path, is_stage, false,
+	)?;
+
+	if is_stage
+		&& !is_in_head(repo, file_path)?
+		&& all_additions_selected(&hunks, lines)
+	{
+		// unstaging everything of a new file makes it untracked again
+		index.remove_path(Path::new(file_path))?;
+		index.write()?;
+
+		return Ok(());
+	}
 
+	let new_content = {
 		let old_lines = indexed_content.lines().collect::<Vec<_>>();
 
 		apply_selection(lines, &hunks, &old_lines, is_stage, false)?
@@ -60,6 +105,49 @@ pub fn stage_lines(
 	Ok(())
 }
 
+/// adds an empty index entry for the untracked `file_path` so that
+/// its content shows up as additions in the workdir diff
+fn intent_to_add(
+	repo: &Repository,
+	file_path: &str,
+) -> Result<IndexEntry> {
+	let mut index = repo.index()?;
+	let path = Path::new(file_path);
+
+	// gets us mode and stat data of the workdir file
+	index.add_path(path)?;
+
+	let mut entry = index.get_path(path, 0).ok_or_else(|| {
+		Error::Generic(format!("could not add to index: {file_path}"))
+	})?;
+	entry.id = repo.blob(&[])?;
+	entry.file_size = 0;
+
+	index.add(&entry)?;
+	index.write()?;
+
+	Ok(entry)
+}
+
+fn is_in_head(repo: &Repository, file_path: &str) -> Result<bool> {
+	let Ok(head) = repo.head() else {
+		return Ok(false);
+	};
+
+	Ok(head.peel_to_tree()?.get_path(Path::new(file_path)).is_ok())
+}
+
+fn all_additions_selected(
+	hunks: &[HunkLines],
+	lines: &[DiffLinePosition],
+) -> bool {
+	hunks
+		.iter()
+		.flat_map(|hunk| hunk.lines.iter())
+		.filter(|line| line.origin_value() == DiffLineType::Addition)
+		.all(|line| lines.contains(&line.into()))
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -189,4 +277,126 @@ c = 4";
 
 		assert_eq!(diff.lines, 4);
 	}
+
+	#[test]
+	fn test_stage_untracked() {
+		static FILE_1: &str = r"0
+1
+2
+3
+";
+
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "other.txt", "", "c1");
+
+		repo_write_file(&repo, "test.txt", FILE_1).unwrap();
+
+		assert_eq!(get_statuses(path), (1, 0));
+
+		stage_lines(
+			path,
+			"test.txt",
+			false,
+			&[
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(1),
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(3),
+				},
+			],
+		)
+		.unwrap();
+
+		assert_eq!(get_statuses(path), (1, 1));
+
+		let diff = get_diff(path, "test.txt", true, None).unwrap();
+
+		assert_eq!(diff.lines, 3);
+		assert_eq!(
+			&*diff.hunks[0].lines[0].content,
+			"@@ -0,0 +1,2 @@"
+		);
+		assert_eq!(&*diff.hunks[0].lines[2].content, "2");
+
+		let diff = get_diff(path, "test.txt", false, None).unwrap();
+
+		assert!(!diff.untracked);
+		assert_eq!(diff.lines, 5);
+	}
+
+	#[test]
+	fn test_stage_untracked_failing() {
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "other.txt", "", "c1");
+
+		// nothing to stage in an empty file
+		repo_write_file(&repo, "test.txt", "").unwrap();
+
+		assert!(stage_lines(
+			path,
+			"test.txt",
+			false,
+			&[DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(1),
+			}],
+		)
+		.is_err());
+
+		// still untracked
+		assert_eq!(get_statuses(path), (1, 0));
+		assert!(
+			get_diff(path, "test.txt", false, None)
+				.unwrap()
+				.untracked
+		);
+	}
+
+	#[test]
+	fn test_unstage_new_file() {
+		static FILE_1: &str = r"0
+1
+";
+
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "other.txt", "", "c1");
+
+		repo_write_file(&repo, "test.txt", FILE_1).unwrap();
+
+		let all_lines = [
+			DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(1),
+			},
+			DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(2),
+			},
+		];
+
+		stage_lines(path, "test.txt", false, &all_lines).unwrap();
+
+		assert_eq!(get_statuses(path), (0, 1));
+
+		stage_lines(path, "test.txt", true, &all_lines[..1]).unwrap();
+
+		assert_eq!(get_statuses(path), (1, 1));
+
+		// remaining staged line moved up
+		stage_lines(path, "test.txt", true, &all_lines[..1]).unwrap();
+
+		// back to untracked
+		assert_eq!(get_statuses(path), (1, 0));
+		let diff = get_diff(path, "test.txt", false, None).unwrap();
+		assert!(diff.untracked);
+	}
 }
@@ -1,13 +1,37 @@
-use super::{CommitId, RepoPath};
+use super::{
+	commit_files::get_commit_diff,
+	diff::{get_diff_raw, DiffOptions},
+	hunks::find_hunk_index,
+	patches::patch_text,
+	utils::work_dir,
+	CommitId, RepoPath,
+};
 use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
 };
 use git2::{
-	build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
-	StashFlags,
+	build::CheckoutBuilder, ApplyLocation, ApplyOptions, Commit,
+	Diff, IndexEntry, Oid, Repository, StashApplyOptions, StashFlags,
+	Tree,
 };
 use scopetime::scope_time;
+use std::{
+	collections::HashSet,
+	fs,
+	io::{self, Write},
+	path::{Path, PathBuf},
+};
+
+/// changes of a single file to include in a partial stash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashFile {
+	/// path relative to the working directory
+	pub path: String,
+	/// hashes of unstaged hunks to stash (like in `stage_hunk`),
+	/// `None` stashes all changes of the file
+	pub hunks: Option<Vec<u64>>,
+}
 
 ///
 pub fn get_stashes(repo_path: &RepoPath) -> Result<Vec<CommitId>> {
@@ -77,6 +101,96 @@ pub fn stash_apply(
 	Ok(())
 }
 
+/// creates `branch_name` at the commit the stash is based on, checks
+/// it out and pops the stash on top of it (like `git stash branch`)
+pub fn stash_branch(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+	branch_name: &str,
+) -> Result<()> {
+	scope_time!("stash_branch");
+
+	let mut repo = repo(repo_path)?;
+
+	{
+		let base = repo.find_commit(stash_id.into())?.parent(0)?;
+		let mut branch = repo.branch(branch_name, &base, false)?;
+
+		if let Err(e) = repo.checkout_tree(
+			base.as_object(),
+			Some(&mut CheckoutBuilder::new()),
+		) {
+			branch.delete()?;
+			return Err(e.into());
+		}
+
+		let branch_ref = branch.get().name().ok_or_else(|| {
+			Error::Generic(String::from("branch ref not found"))
+		})?;
+
+		repo.set_head(branch_ref)?;
+	}
+
+	let index = get_stash_index(&mut repo, stash_id.into())?;
+
+	let mut opt = StashApplyOptions::default();
+	opt.reinstantiate_index();
+	repo.stash_pop(index, Some(&mut opt))?;
+
+	Ok(())
+}
+
+/// changes of a stash (including untracked files) as a patch
+/// against the commit it is based on
+pub fn stash_patch(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+) -> Result<String> {
+	scope_time!("stash_patch");
+
+	let repo = repo(repo_path)?;
+	let diff = get_commit_diff(
+		&repo,
+		stash_id,
+		None,
+		None,
+		Some(&HashSet::from([stash_id])),
+	)?;
+
+	patch_text(&diff)
+}
+
+/// writes `stash_patch` to the new file `path` (relative to the
+/// working directory), returns the absolute path of the file
+pub fn export_stash_patch(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+	path: &Path,
+) -> Result<PathBuf> {
+	scope_time!("export_stash_patch");
+
+	let content = stash_patch(repo_path, stash_id)?;
+
+	let file = work_dir(&repo(repo_path)?)?.join(path);
+	fs::OpenOptions::new()
+		.write(true)
+		.create_new(true)
+		.open(&file)
+		.map_err(|e| {
+			if e.kind() == io::ErrorKind::AlreadyExists {
+				Error::Generic(format!(
+					"'{}' already exists",
+					file.display()
+				))
+			} else {
+				e.into()
+			}
+		})?
+		.write_all(content.as_bytes())?;
+
+	Ok(file)
+}
+
 fn get_stash_index(
 	repo: &mut Repository,
 	stash_id: Oid,
@@ -110,6 +224,19 @@ pub fn stash_save(
 
 	let sig = repo.signature()?;
 
+	let id = repo.stash_save2(
+		&sig,
+		message,
+		Some(stash_flags(include_untracked, keep_index)),
+	)?;
+
+	Ok(CommitId::new(id))
+}
+
+fn stash_flags(
+	include_untracked: bool,
+	keep_index: bool,
+) -> StashFlags {
 	let mut options = StashFlags::DEFAULT;
 
 	if include_untracked {
@@ -119,16 +246,383 @@ pub fn stash_save(
 		options.insert(StashFlags::KEEP_INDEX);
 	}
 
-	let id = repo.stash_save2(&sig, message, Some(options))?;
+	options
+}
+
+/// stashes only the changes of `files` (like `git stash push --
+/// <paths>`), all other changes in the working directory and index
+/// stay untouched.
+///
+/// the stash commits are built from trees before anything is reset,
+/// so the changes are never only kept in memory.
+/// files with selected hunks must not have staged changes.
+pub fn stash_save_files(
+	repo_path: &RepoPath,
+	message: Option<&str>,
+	include_untracked: bool,
+	keep_index: bool,
+	files: &[StashFile],
+	options: Option<DiffOptions>,
+) -> Result<CommitId> {
+	scope_time!("stash_save_files");
+
+	if files.is_empty() {
+		return Err(Error::Generic(String::from(
+			"no files selected to stash",
+		)));
+	}
+
+	let repo = repo(repo_path)?;
+	let head = repo.head()?.peel_to_commit()?;
+
+	let selection =
+		StashSelection::new(&repo, &head, files, include_untracked)?;
+	let trees =
+		stash_trees(repo_path, &repo, &head, &selection, options)?;
+
+	if trees.index == head.tree_id()
+		&& trees.work_dir == head.tree_id()
+		&& trees.untracked.is_none()
+	{
+		return Err(Error::Generic(String::from(
+			"no local changes to save",
+		)));
+	}
+
+	let id = stash_commit(&repo, &head, message, &trees)?;
+
+	reset_selection(&repo, &selection, keep_index, options)?;
+
+	Ok(id)
+}
+
+/// selected files split by how they are stashed
+struct StashSelection<'a> {
+	/// tracked files stashed with all their changes
+	tracked: Vec<&'a str>,
+	/// tracked files of which only some hunks are stashed
+	hunks: Vec<(&'a str, &'a [u64])>,
+	untracked: Vec<&'a str>,
+}
+
+impl<'a> StashSelection<'a> {
+	fn new(
+		repo: &Repository,
+		head: &Commit,
+		files: &'a [StashFile],
+		include_untracked: bool,
+	) -> Result<Self> {
+		let index = repo.index()?;
+		let head_tree = head.tree()?;
+
+		let mut selection = Self {
+			tracked: Vec::new(),
+			hunks: Vec::new(),
+			untracked: Vec::new(),
+		};
+
+		for file in files {
+			let path = Path::new(&file.path);
+			let index_id = index.get_path(path, 0).map(|e| e.id);
+			let head_id =
+				head_tree.get_path(path).ok().map(|e| e.id());
+
+			match &file.hunks {
+				Some(_) if index_id.is_none() => {
+					return Err(Error::Generic(format!(
+						"cannot stash hunks of untracked file: {}",
+						file.path
+					)));
+				}
+				Some(hunks) => {
+					if index_id != head_id {
+						return Err(Error::Generic(format!(
+							"cannot stash hunks of file with staged changes: {}",
+							file.path
+						)));
+					}
+
+					selection.hunks.push((&file.path, hunks));
+				}
+				None if index_id.is_some() || head_id.is_some() => {
+					selection.tracked.push(&file.path);
+				}
+				None if include_untracked => {
+					selection.untracked.push(&file.path);
+				}
+				None => (),
+			}
+		}
+
+		Ok(selection)
+	}
+}
+
+/// trees of the stash commits
+struct StashTrees {
+	index: Oid,
+	work_dir: Oid,
+	untracked: Option<Oid>,
+}
+
+/// builds the trees of the stash commits: `HEAD` with the selected
+/// files as they are in the index and in the working directory, and
+/// the selected untracked files on their own
+fn stash_trees(
+	repo_path: &RepoPath,
+	repo: &Repository,
+	head: &Commit,
+	selection: &StashSelection,
+	options: Option<DiffOptions>,
+) -> Result<StashTrees> {
+	let head_tree = head.tree()?;
+	let index = repo.index()?;
+
+	// separate handle, its index is only changed in memory and never
+	// written back
+	let scratch_repo = super::repository::repo(repo_path)?;
+	let mut scratch = scratch_repo.index()?;
+
+	scratch.read_tree(&head_tree)?;
+	for path in &selection.tracked {
+		let path = Path::new(path);
+		if let Some(entry) = index.get_path(path, 0) {
+			scratch.add(&entry)?;
+		} else {
+			scratch.remove_path(path)?;
+		}
+	}
+	let index_tree = scratch.write_tree()?;
+
+	let work_dir = work_dir(repo)?;
+	scratch.read_tree(&head_tree)?;
+	for path in &selection.tracked {
+		if fs::symlink_metadata(work_dir.join(path)).is_ok() {
+			scratch.add_path(Path::new(path))?;
+		} else {
+			scratch.remove_path(Path::new(path))?;
+		}
+	}
+	for (path, hunks) in &selection.hunks {
+		match hunks_entry(repo, &head_tree, path, hunks, options)? {
+			Some(entry) => scratch.add(&entry)?,
+			None => scratch.remove_path(Path::new(path))?,
+		}
+	}
+	let work_dir_tree = scratch.write_tree()?;
+
+	scratch.clear()?;
+	for path in &selection.untracked {
+		scratch.add_path(Path::new(path))?;
+	}
+	let untracked_tree = if scratch.is_empty() {
+		None
+	} else {
+		Some(scratch.write_tree()?)
+	};
+
+	Ok(StashTrees {
+		index: index_tree,
+		work_dir: work_dir_tree,
+		untracked: untracked_tree,
+	})
+}
+
+/// entry of `path` in `HEAD` with only the selected `hunks` of the
+/// unstaged changes applied, `None` if that deletes the file
+fn hunks_entry(
+	repo: &Repository,
+	head_tree: &Tree,
+	path: &str,
+	hunks: &[u64],
+	options: Option<DiffOptions>,
+) -> Result<Option<IndexEntry>> {
+	let diff = get_diff_raw(repo, path, false, false, options)?;
+	let selected = hunk_indices(&diff, path, hunks)?;
+
+	let mut hunk_idx = 0;
+	let mut opt = ApplyOptions::new();
+	opt.hunk_callback(|_hunk| {
+		let res = selected.contains(&hunk_idx);
+		hunk_idx += 1;
+		res
+	});
+
+	let index =
+		repo.apply_to_tree(head_tree, &diff, Some(&mut opt))?;
+
+	Ok(index.get_path(Path::new(path), 0))
+}
+
+fn hunk_indices(
+	diff: &Diff,
+	path: &str,
+	hunks: &[u64],
+) -> Result<HashSet<usize>> {
+	hunks
+		.iter()
+		.map(|hunk| {
+			find_hunk_index(diff, *hunk).ok_or_else(|| {
+				Error::Generic(format!("hunk not found in: {path}"))
+			})
+		})
+		.collect()
+}
+
+/// creates the stash commits like `git stash` does and adds them to
+/// `refs/stash`
+fn stash_commit(
+	repo: &Repository,
+	head: &Commit,
+	message: Option<&str>,
+	trees: &StashTrees,
+) -> Result<CommitId> {
+	let sig = repo.signature()?;
+
+	let head_ref = repo.head()?;
+	let branch = if head_ref.is_branch() {
+		head_ref.shorthand().unwrap_or_default()
+	} else {
+		"(no branch)"
+	};
+	let head_summary = format!(
+		"{} {}",
+		CommitId::new(head.id()).get_short_string(),
+		head.summary().unwrap_or_default()
+	);
+
+	let index_commit = repo.find_commit(repo.commit(
+		None,
+		&sig,
+		&sig,
+		&format!("index on {branch}: {head_summary}"),
+		&repo.find_tree(trees.index)?,
+		&[head],
+	)?)?;
+
+	let untracked_commit = trees
+		.untracked
+		.map(|tree| -> Result<Commit> {
+			Ok(repo.find_commit(repo.commit(
+				None,
+				&sig,
+				&sig,
+				&format!(
+					"untracked files on {branch}: {head_summary}"
+				),
+				&repo.find_tree(tree)?,
+				&[],
+			)?)?)
+		})
+		.transpose()?;
+
+	let message = message.map_or_else(
+		|| format!("WIP on {branch}: {head_summary}"),
+		|message| format!("On {branch}: {message}"),
+	);
+
+	let mut parents = vec![head, &index_commit];
+	parents.extend(untracked_commit.as_ref());
+
+	let id = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&message,
+		&repo.find_tree(trees.work_dir)?,
+		&parents,
+	)?;
+
+	repo.reference_ensure_log("refs/stash")?;
+	repo.reference("refs/stash", id, true, &message)?;
 
 	Ok(CommitId::new(id))
 }
 
+/// removes the stashed changes of the selected files from the
+/// working directory (and the index unless `keep_index`)
+fn reset_selection(
+	repo: &Repository,
+	selection: &StashSelection,
+	keep_index: bool,
+	options: Option<DiffOptions>,
+) -> Result<()> {
+	let work_dir = work_dir(repo)?;
+
+	if !keep_index && !selection.tracked.is_empty() {
+		let head = repo.head()?.peel_to_commit()?;
+		repo.reset_default(
+			Some(head.as_object()),
+			selection.tracked.iter().copied(),
+		)?;
+	}
+
+	let index = repo.index()?;
+	for path in &selection.tracked {
+		if index.get_path(Path::new(path), 0).is_some() {
+			let mut checkout = CheckoutBuilder::new();
+			checkout.force().path(path);
+			repo.checkout_index(None, Some(&mut checkout))?;
+		} else {
+			remove_file(&work_dir.join(path))?;
+		}
+	}
+
+	for (path, hunks) in &selection.hunks {
+		let diff = get_diff_raw(repo, path, false, false, options)?;
+		let selected = hunk_indices(&diff, path, hunks)?;
+
+		reverse_hunks(repo, path, options, |idx| {
+			selected.contains(&idx)
+		})?;
+	}
+
+	for path in &selection.untracked {
+		remove_file(&work_dir.join(path))?;
+	}
+
+	Ok(())
+}
+
+/// reverts the unstaged hunks of `path` for which `filter` is true
+fn reverse_hunks(
+	repo: &Repository,
+	path: &str,
+	options: Option<DiffOptions>,
+	filter: impl Fn(usize) -> bool,
+) -> Result<()> {
+	let diff = get_diff_raw(repo, path, false, true, options)?;
+
+	let mut hunk_idx = 0;
+	let mut opt = ApplyOptions::new();
+	opt.hunk_callback(|_hunk| {
+		let res = filter(hunk_idx);
+		hunk_idx += 1;
+		res
+	});
+
+	repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
+
+	Ok(())
+}
+
+fn remove_file(path: &Path) -> Result<()> {
+	match fs::remove_file(path) {
+		Err(e) if e.kind() != io::ErrorKind::NotFound => {
+			Err(e.into())
+		}
+		_ => Ok(()),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::sync::{
-		commit, get_commit_files, get_commits_info, stage_add_file,
+		commit,
+		diff::get_diff,
+		get_commit_files, get_commits_info, stage_add_all,
+		stage_add_file,
 		tests::{
 			debug_cmd_print, get_statuses, repo_init,
 			write_commit_file,
@@ -376,4 +870,285 @@ mod tests {
 			"test3"
 		);
 	}
+
+	#[test]
+	fn test_stash_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+		repo_write_file(&repo, "c.txt", "c").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+
+		let id = stash_save_files(
+			repo_path,
+			Some("only a"),
+			true,
+			false,
+			&[StashFile {
+				path: String::from("a.txt"),
+				hunks: None,
+			}],
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+		assert_eq!(repo_read_file(&repo, "c.txt").unwrap(), "c");
+		assert_eq!(get_statuses(repo_path), (1, 1));
+
+		let files = get_commit_files(repo_path, id, None).unwrap();
+		assert_eq!(files.len(), 1);
+		assert_eq!(files[0].path, "a.txt");
+
+		let infos = get_commits_info(repo_path, &[id], 100).unwrap();
+		assert_eq!(infos[0].message, "On master: only a");
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_stash_files_symlink() {
+		use std::os::unix::fs::symlink;
+
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+		repo_write_file(&repo, "b.txt", "b").unwrap();
+		symlink("a.txt", root.join("link")).unwrap();
+		stage_add_all(repo_path, "*", None).unwrap();
+		commit(repo_path, "c1").unwrap();
+
+		fs::remove_file(root.join("link")).unwrap();
+		symlink("b.txt", root.join("link")).unwrap();
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+
+		let id = stash_save_files(
+			repo_path,
+			None,
+			false,
+			false,
+			&[StashFile {
+				path: String::from("link"),
+				hunks: None,
+			}],
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			fs::read_link(root.join("link")).unwrap(),
+			Path::new("a.txt")
+		);
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+
+		let entry = repo
+			.find_commit(id.into())
+			.unwrap()
+			.tree()
+			.unwrap()
+			.get_path(Path::new("link"))
+			.unwrap();
+		assert_eq!(entry.filemode(), 0o120_000);
+		assert_eq!(
+			repo.find_blob(entry.id()).unwrap().content(),
+			b"b.txt"
+		);
+	}
+
+	#[test]
+	fn test_stash_files_keep_index() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		repo_write_file(&repo, "a.txt", "a3").unwrap();
+		repo_write_file(&repo, "b.txt", "b").unwrap();
+
+		let id = stash_save_files(
+			repo_path,
+			None,
+			true,
+			true,
+			&[
+				StashFile {
+					path: String::from("a.txt"),
+					hunks: None,
+				},
+				StashFile {
+					path: String::from("b.txt"),
+					hunks: None,
+				},
+			],
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+		assert!(!root.join("b.txt").exists());
+		assert_eq!(get_statuses(repo_path), (0, 1));
+
+		let stash = repo.find_commit(id.into()).unwrap();
+		assert_eq!(stash.parent_count(), 3);
+		assert_eq!(
+			stash.parent(0).unwrap().id(),
+			repo.head().unwrap().peel_to_commit().unwrap().id()
+		);
+
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		stash_pop(repo_path, id).unwrap();
+
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a3");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b");
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_stash_files_nothing_to_stash() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+
+		assert!(stash_save_files(
+			repo_path,
+			None,
+			false,
+			false,
+			&[StashFile {
+				path: String::from("a.txt"),
+				hunks: None,
+			}],
+			None,
+		)
+		.is_err());
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a");
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_stash_hunks() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let lines = (1..=20)
+			.map(|i| i.to_string())
+			.collect::<Vec<_>>()
+			.join("\n")
+			+ "\n";
+		write_commit_file(&repo, "a.txt", &lines, "c1");
+
+		let modified = lines
+			.replacen("\n2\n", "\n2x\n", 1)
+			.replacen("\n19\n", "\n19x\n", 1);
+		repo_write_file(&repo, "a.txt", &modified).unwrap();
+
+		let diff = get_diff(repo_path, "a.txt", false, None).unwrap();
+		assert_eq!(diff.hunks.len(), 2);
+
+		let id = stash_save_files(
+			repo_path,
+			None,
+			false,
+			false,
+			&[StashFile {
+				path: String::from("a.txt"),
+				hunks: Some(vec![diff.hunks[1].header_hash]),
+			}],
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			repo_read_file(&repo, "a.txt").unwrap(),
+			lines.replacen("\n2\n", "\n2x\n", 1)
+		);
+
+		repo_write_file(&repo, "a.txt", &lines).unwrap();
+		stash_apply(repo_path, id, false).unwrap();
+
+		assert_eq!(
+			repo_read_file(&repo, "a.txt").unwrap(),
+			lines.replacen("\n19\n", "\n19x\n", 1)
+		);
+	}
+
+	#[test]
+	fn test_stash_branch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "test.txt", "test", "c1");
+
+		repo_write_file(&repo, "test.txt", "test2").unwrap();
+		let id =
+			stash_save(repo_path, Some("foo"), true, false).unwrap();
+
+		write_commit_file(&repo, "test.txt", "test3", "c2");
+
+		stash_branch(repo_path, id, "from-stash").unwrap();
+
+		assert_eq!(
+			repo.head().unwrap().shorthand().unwrap(),
+			"from-stash"
+		);
+		assert_eq!(
+			repo.head().unwrap().peel_to_commit().unwrap().id(),
+			c1.into()
+		);
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			"test2"
+		);
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_stash_patch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "a\n", "c1");
+
+		repo_write_file(&repo, "test.txt", "b\n").unwrap();
+		repo_write_file(&repo, "new.txt", "new\n").unwrap();
+		let id = stash_save(repo_path, None, true, false).unwrap();
+
+		let file =
+			export_stash_patch(repo_path, id, Path::new("s.patch"))
+				.unwrap();
+		let patch = std::fs::read_to_string(&file).unwrap();
+
+		// existing files are not overwritten
+		assert!(export_stash_patch(repo_path, id, &file).is_err());
+		assert_eq!(std::fs::read_to_string(&file).unwrap(), patch);
+
+		assert!(patch.contains("--- a/test.txt\n+++ b/test.txt\n"));
+		assert!(patch.contains("-a\n+b\n"));
+		assert!(patch.contains("+++ b/new.txt\n"));
+		assert!(patch.contains("+new\n"));
+	}
 }
@@ -0,0 +1,302 @@
+//! intra-line (word level) differences of changed lines
+
+use super::diff::{DiffLine, DiffLineType};
+
+/// lines with more tokens than this (combined) are not compared
+const MAX_TOKEN_PAIRS: usize = 100_000;
+
+/// blocks with more line combinations than this are paired up line
+/// by line instead of by similarity
+const MAX_LINE_PAIRS: usize = 1_000;
+
+/// byte ranges within a line
+type Ranges = Vec<(usize, usize)>;
+
+/// marks the changed parts of lines that replace each other within a
+/// hunk: a block of deleted lines directly followed by a block of
+/// added lines, see [`emphasize_block`]
+pub fn emphasize_hunk(lines: &mut [DiffLine]) {
+	let mut i = 0;
+	while i < lines.len() {
+		let deleted = count_type(&lines[i..], DiffLineType::Delete);
+		if deleted == 0 {
+			i += 1;
+			continue;
+		}
+
+		let added =
+			count_type(&lines[i + deleted..], DiffLineType::Add);
+
+		if added > 0 {
+			let (old, new) = lines[i..].split_at_mut(deleted);
+			emphasize_block(old, &mut new[..added]);
+		}
+
+		i += deleted + added;
+	}
+}
+
+/// blocks of the same size are compared line by line, otherwise each
+/// deleted line is compared to the most similar of the added lines
+/// following the one the previous deleted line was compared to
+fn emphasize_block(old: &mut [DiffLine], new: &mut [DiffLine]) {
+	if old.len() == new.len()
+		|| old.len() * new.len() > MAX_LINE_PAIRS
+	{
+		for (old, new) in old.iter_mut().zip(new.iter_mut()) {
+			let (old_ranges, new_ranges) =
+				changed_ranges(&old.content, &new.content);
+			old.emphasized = old_ranges;
+			new.emphasized = new_ranges;
+		}
+		return;
+	}
+
+	let mut next = 0;
+	for old in old.iter_mut() {
+		let best = new
+			.iter()
+			.enumerate()
+			.skip(next)
+			.filter_map(|(idx, new)| {
+				compare(&old.content, &new.content)
+					.map(|ranges| (idx, ranges))
+			})
+			.fold(None, |best, (idx, ranges)| {
+				let common = common_len(&old.content, &ranges.0);
+				match best {
+					Some((_, best_common, _))
+						if best_common >= common =>
+					{
+						best
+					}
+					_ => Some((idx, common, ranges)),
+				}
+			});
+
+		if let Some((idx, _, (old_ranges, new_ranges))) = best {
+			old.emphasized = old_ranges;
+			new[idx].emphasized = new_ranges;
+			next = idx + 1;
+		}
+	}
+}
+
+/// bytes of `line` outside of the `changed` ranges
+fn common_len(line: &str, changed: &Ranges) -> usize {
+	line.len()
+		- changed
+			.iter()
+			.map(|(start, end)| end - start)
+			.sum::<usize>()
+}
+
+fn count_type(lines: &[DiffLine], line_type: DiffLineType) -> usize {
+	lines
+		.iter()
+		.take_while(|line| line.line_type == line_type)
+		.count()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+	Word,
+	Whitespace,
+	Other,
+}
+
+impl TokenKind {
+	fn of(c: char) -> Self {
+		if c.is_alphanumeric() || c == '_' {
+			Self::Word
+		} else if c.is_whitespace() {
+			Self::Whitespace
+		} else {
+			Self::Other
+		}
+	}
+}
+
+/// byte ranges of words, whitespace runs and single other chars
+fn tokenize(text: &str) -> Ranges {
+	let mut tokens = Vec::new();
+	let mut current: Option<(usize, TokenKind)> = None;
+
+	for (idx, c) in text.char_indices() {
+		let kind = TokenKind::of(c);
+
+		match current {
+			Some((_, current_kind))
+				if current_kind == kind
+					&& kind != TokenKind::Other => {}
+			Some((start, _)) => {
+				tokens.push((start, idx));
+				current = Some((idx, kind));
+			}
+			None => current = Some((idx, kind)),
+		}
+	}
+
+	if let Some((start, _)) = current {
+		tokens.push((start, text.len()));
+	}
+
+	tokens
+}
+
+/// byte ranges of `old` and `new` that are not part of the longest
+/// common token sequence of both, empty if the lines have nothing but
+/// whitespace in common or are too long to compare
+fn changed_ranges(old: &str, new: &str) -> (Ranges, Ranges) {
+	compare(old, new).unwrap_or_default()
+}
+
+/// like [`changed_ranges`] but `None` if the lines have nothing but
+/// whitespace in common or are too long to compare
+fn compare(old: &str, new: &str) -> Option<(Ranges, Ranges)> {
+	let old_tokens = tokenize(old);
+	let new_tokens = tokenize(new);
+
+	let n = old_tokens.len();
+	let m = new_tokens.len();
+
+	if n == 0 || m == 0 || n * m > MAX_TOKEN_PAIRS {
+		return None;
+	}
+
+	let old_words: Vec<&str> = old_tokens
+		.iter()
+		.map(|&(start, end)| &old[start..end])
+		.collect();
+	let new_words: Vec<&str> = new_tokens
+		.iter()
+		.map(|&(start, end)| &new[start..end])
+		.collect();
+
+	// lcs[i][j]: length of the common sequence of old[i..] and new[j..]
+	let mut lcs = vec![0_u32; (n + 1) * (m + 1)];
+	let idx = |i: usize, j: usize| i * (m + 1) + j;
+	for i in (0..n).rev() {
+		for j in (0..m).rev() {
+			lcs[idx(i, j)] = if old_words[i] == new_words[j] {
+				lcs[idx(i + 1, j + 1)] + 1
+			} else {
+				lcs[idx(i + 1, j)].max(lcs[idx(i, j + 1)])
+			};
+		}
+	}
+
+	let mut old_changed = vec![true; n];
+	let mut new_changed = vec![true; m];
+	let mut common_text = false;
+
+	let (mut i, mut j) = (0, 0);
+	while i < n && j < m {
+		if old_words[i] == new_words[j] {
+			old_changed[i] = false;
+			new_changed[j] = false;
+			common_text |= !old_words[i].trim().is_empty();
+			i += 1;
+			j += 1;
+		} else if lcs[idx(i + 1, j)] >= lcs[idx(i, j + 1)] {
+			i += 1;
+		} else {
+			j += 1;
+		}
+	}
+
+	common_text.then(|| {
+		(
+			merge_ranges(&old_tokens, &old_changed),
+			merge_ranges(&new_tokens, &new_changed),
+		)
+	})
+}
+
+/// joins adjacent changed tokens into single ranges
+fn merge_ranges(
+	tokens: &[(usize, usize)],
+	changed: &[bool],
+) -> Ranges {
+	let mut ranges: Ranges = Vec::new();
+
+	for (&(start, end), _) in
+		tokens.iter().zip(changed).filter(|(_, changed)| **changed)
+	{
+		match ranges.last_mut() {
+			Some(last) if last.1 == start => last.1 = end,
+			_ => ranges.push((start, end)),
+		}
+	}
+
+	ranges
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn line(content: &str, line_type: DiffLineType) -> DiffLine {
+		DiffLine {
+			content: content.into(),
+			line_type,
+			..DiffLine::default()
+		}
+	}
+
+	#[test]
+	fn test_changed_ranges() {
+		assert_eq!(
+			changed_ranges("let foo = 1;", "let bar = 1;"),
+			(vec![(4, 7)], vec![(4, 7)])
+		);
+		assert_eq!(
+			changed_ranges("a(b, c)", "a(b, c, d)"),
+			(vec![], vec![(6, 9)])
+		);
+		assert_eq!(
+			changed_ranges("foo bar", "baz qux"),
+			(vec![], vec![])
+		);
+	}
+
+	#[test]
+	fn test_emphasize_hunk() {
+		let mut lines = vec![
+			line("@@ -1,3 +1,3 @@", DiffLineType::Header),
+			line("fn foo(x: u8)", DiffLineType::Delete),
+			line("fn foo(x: u16)", DiffLineType::Add),
+			line("}", DiffLineType::None),
+			line("a b", DiffLineType::Delete),
+			line("a c", DiffLineType::Add),
+			line("a d", DiffLineType::Add),
+		];
+
+		emphasize_hunk(&mut lines);
+
+		assert_eq!(lines[1].emphasized, vec![(10, 12)]);
+		assert_eq!(lines[2].emphasized, vec![(10, 13)]);
+		assert_eq!(lines[4].emphasized, vec![(2, 3)]);
+		assert_eq!(lines[5].emphasized, vec![(2, 3)]);
+		assert!(lines[6].emphasized.is_empty());
+	}
+
+	#[test]
+	fn test_emphasize_unequal_blocks() {
+		let mut lines = vec![
+			line("let a = foo(1);", DiffLineType::Delete),
+			line("let b = bar(2);", DiffLineType::Delete),
+			line("// new", DiffLineType::Add),
+			line("let a = foo(3);", DiffLineType::Add),
+			line("let b = baz(2);", DiffLineType::Add),
+		];
+
+		emphasize_hunk(&mut lines);
+
+		assert_eq!(lines[0].emphasized, vec![(12, 13)]);
+		assert_eq!(lines[1].emphasized, vec![(8, 11)]);
+		assert!(lines[2].emphasized.is_empty());
+		assert_eq!(lines[3].emphasized, vec![(12, 13)]);
+		assert_eq!(lines[4].emphasized, vec![(8, 11)]);
+	}
+}
@@ -0,0 +1,410 @@
+//! linked worktrees
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use git2::{
+	BranchType, Repository, StatusOptions, WorktreeAddOptions,
+	WorktreeLockStatus, WorktreePruneOptions,
+};
+use scopetime::scope_time;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+///
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+	///
+	pub name: String,
+	/// absolute path of the working directory
+	pub path: PathBuf,
+	/// branch checked out (`None` if detached or not readable)
+	pub branch: Option<String>,
+	///
+	pub is_locked: bool,
+	///
+	pub lock_reason: Option<String>,
+	/// `false` if the working directory is gone
+	pub is_valid: bool,
+	///
+	pub is_prunable: bool,
+	/// the main worktree of the repository (cannot be removed)
+	pub is_main: bool,
+	/// the worktree `repo_path` points to
+	pub is_current: bool,
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+	match (a.canonicalize(), b.canonicalize()) {
+		(Ok(a), Ok(b)) => a == b,
+		_ => a.components().eq(b.components()),
+	}
+}
+
+/// `.git` folder shared by all worktrees of the repository
+fn common_dir(repo: &Repository) -> Result<PathBuf> {
+	if !repo.is_worktree() {
+		return Ok(repo.path().to_path_buf());
+	}
+
+	let commondir =
+		fs::read_to_string(repo.path().join("commondir"))?;
+
+	Ok(repo.path().join(commondir.trim_end()).canonicalize()?)
+}
+
+fn head_branch(repo: &Repository) -> Option<String> {
+	let head = repo.head().ok()?;
+
+	if head.is_branch() {
+		head.shorthand().map(String::from)
+	} else {
+		None
+	}
+}
+
+/// main worktree followed by all linked worktrees
+pub fn get_worktrees(
+	repo_path: &RepoPath,
+) -> Result<Vec<WorktreeInfo>> {
+	scope_time!("get_worktrees");
+
+	let repo = repo(repo_path)?;
+	let current = work_dir(&repo)?;
+
+	let mut res = Vec::new();
+
+	let common_dir = common_dir(&repo)?;
+
+	if let Some(main_path) = common_dir.parent() {
+		let main = Repository::open(&common_dir)?;
+
+		if !main.is_bare() {
+			res.push(WorktreeInfo {
+				name: main_path
+					.file_name()
+					.map(|n| n.to_string_lossy().to_string())
+					.unwrap_or_default(),
+				path: main_path.to_path_buf(),
+				branch: head_branch(&main),
+				is_locked: false,
+				lock_reason: None,
+				is_valid: true,
+				is_prunable: false,
+				is_main: true,
+				is_current: same_path(main_path, current),
+			});
+		}
+	}
+
+	for name in repo.worktrees()?.iter().flatten() {
+		let worktree = repo.find_worktree(name)?;
+
+		let lock_reason = match worktree.is_locked()? {
+			WorktreeLockStatus::Unlocked => None,
+			WorktreeLockStatus::Locked(reason) => {
+				Some(reason.unwrap_or_default())
+			}
+		};
+
+		let is_valid = worktree.validate().is_ok();
+
+		res.push(WorktreeInfo {
+			name: name.to_string(),
+			path: worktree.path().to_path_buf(),
+			branch: Repository::open_from_worktree(&worktree)
+				.ok()
+				.as_ref()
+				.and_then(head_branch),
+			is_locked: lock_reason.is_some(),
+			lock_reason,
+			is_valid,
+			is_prunable: worktree.is_prunable(None)?,
+			is_main: false,
+			is_current: is_valid
+				&& same_path(worktree.path(), current),
+		});
+	}
+
+	Ok(res)
+}
+
+/// creates a new worktree named after the last component of `path`
+///
+/// relative paths are relative to the working directory of the
+/// repository (the main or linked worktree `repo_path` points to).
+/// checks out
+/// the local branch `branch` if given, an existing local branch
+/// named like the worktree or a new branch of that name
+pub fn add_worktree(
+	repo_path: &RepoPath,
+	path: &Path,
+	branch: Option<&str>,
+) -> Result<()> {
+	scope_time!("add_worktree");
+
+	let repo = repo(repo_path)?;
+	let path = work_dir(&repo)?.join(path);
+
+	let name = path
+		.file_name()
+		.and_then(|n| n.to_str())
+		.filter(|n| !n.is_empty())
+		.ok_or_else(|| {
+			Error::Generic(format!(
+				"invalid worktree path: {}",
+				path.display()
+			))
+		})?;
+
+	let branch = match branch {
+		Some(branch) => {
+			Some(repo.find_branch(branch, BranchType::Local)?)
+		}
+		None => repo.find_branch(name, BranchType::Local).ok(),
+	};
+
+	let mut opts = WorktreeAddOptions::new();
+	if let Some(branch) = &branch {
+		opts.reference(Some(branch.get()));
+	}
+
+	repo.worktree(name, &path, Some(&opts))?;
+
+	Ok(())
+}
+
+///
+pub fn lock_worktree(
+	repo_path: &RepoPath,
+	name: &str,
+	reason: Option<&str>,
+) -> Result<()> {
+	scope_time!("lock_worktree");
+
+	let repo = repo(repo_path)?;
+	repo.find_worktree(name)?.lock(reason)?;
+
+	Ok(())
+}
+
+///
+pub fn unlock_worktree(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<()> {
+	scope_time!("unlock_worktree");
+
+	let repo = repo(repo_path)?;
+	repo.find_worktree(name)?.unlock()?;
+
+	Ok(())
+}
+
+/// prunes the administrative data of all worktrees whose working
+/// directory is gone, returns the number of pruned worktrees
+pub fn prune_worktrees(repo_path: &RepoPath) -> Result<usize> {
+	scope_time!("prune_worktrees");
+
+	let repo = repo(repo_path)?;
+
+	let mut count = 0;
+
+	for name in repo.worktrees()?.iter().flatten() {
+		let worktree = repo.find_worktree(name)?;
+
+		if worktree.is_prunable(None)? {
+			worktree.prune(None)?;
+			count += 1;
+		}
+	}
+
+	Ok(count)
+}
+
+/// `true` if the working directory of the worktree `name` has no
+/// uncommitted or untracked changes (or is gone)
+pub fn is_worktree_clean(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<bool> {
+	scope_time!("is_worktree_clean");
+
+	let repo = repo(repo_path)?;
+	let worktree = repo.find_worktree(name)?;
+
+	if worktree.validate().is_err() {
+		return Ok(true);
+	}
+
+	let worktree_repo = Repository::open_from_worktree(&worktree)?;
+
+	let mut opts = StatusOptions::new();
+	opts.include_untracked(true)
+		.include_ignored(false)
+		.exclude_submodules(true);
+
+	let clean = worktree_repo.statuses(Some(&mut opts))?.is_empty();
+
+	Ok(clean)
+}
+
+/// removes the working directory and administrative data of the
+/// unlocked worktree `name`.
+///
+/// like `git worktree remove` this fails if the worktree has
+/// uncommitted or untracked changes unless `force` is set
+pub fn remove_worktree(
+	repo_path: &RepoPath,
+	name: &str,
+	force: bool,
+) -> Result<()> {
+	scope_time!("remove_worktree");
+
+	let repo = repo(repo_path)?;
+	let worktree = repo.find_worktree(name)?;
+
+	if let WorktreeLockStatus::Locked(_) = worktree.is_locked()? {
+		return Err(Error::Generic(format!(
+			"worktree is locked: {name}"
+		)));
+	}
+
+	if !force && !is_worktree_clean(repo_path, name)? {
+		return Err(Error::UncommittedChanges);
+	}
+
+	worktree.prune(Some(
+		WorktreePruneOptions::new().valid(true).working_tree(true),
+	))?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+	use pretty_assertions::assert_eq;
+	use tempfile::TempDir;
+
+	#[test]
+	fn test_add_lock_remove() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let wt_dir = TempDir::new().unwrap();
+		let wt_path = wt_dir.path().join("feature");
+
+		add_worktree(repo_path, &wt_path, None).unwrap();
+
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees.len(), 2);
+		assert!(worktrees[0].is_main);
+		assert!(worktrees[0].is_current);
+		assert_eq!(worktrees[1].name, "feature");
+		assert_eq!(worktrees[1].branch.as_deref(), Some("feature"));
+		assert!(wt_path.join("a.txt").exists());
+
+		// listing from within the linked worktree
+		let wt_repo_path: &RepoPath =
+			&wt_path.as_os_str().to_str().unwrap().into();
+		let worktrees = get_worktrees(wt_repo_path).unwrap();
+		assert_eq!(worktrees.len(), 2);
+		assert!(same_path(&worktrees[0].path, root));
+		assert!(!worktrees[0].is_current);
+		assert!(worktrees[1].is_current);
+
+		lock_worktree(repo_path, "feature", Some("wip")).unwrap();
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert!(worktrees[1].is_locked);
+		assert_eq!(worktrees[1].lock_reason.as_deref(), Some("wip"));
+		assert!(remove_worktree(repo_path, "feature", false).is_err());
+
+		unlock_worktree(repo_path, "feature").unwrap();
+		remove_worktree(repo_path, "feature", false).unwrap();
+
+		assert_eq!(get_worktrees(repo_path).unwrap().len(), 1);
+		assert!(!wt_path.exists());
+	}
+
+	#[test]
+	fn test_add_existing_branch_and_prune() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		create_branch(repo_path, "other").unwrap();
+		crate::sync::checkout_branch(repo_path, "master").unwrap();
+
+		let wt_dir = TempDir::new().unwrap();
+		let wt_path = wt_dir.path().join("wt");
+
+		add_worktree(repo_path, &wt_path, Some("other")).unwrap();
+		create_branch(repo_path, "wt2").unwrap();
+		crate::sync::checkout_branch(repo_path, "master").unwrap();
+		add_worktree(repo_path, &wt_dir.path().join("wt2"), None)
+			.unwrap();
+
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees[1].branch.as_deref(), Some("other"));
+		assert_eq!(worktrees[2].branch.as_deref(), Some("wt2"));
+		assert!(!worktrees[1].is_prunable);
+
+		std::fs::remove_dir_all(&wt_path).unwrap();
+
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert!(!worktrees[1].is_valid);
+		assert!(worktrees[1].is_prunable);
+
+		assert_eq!(prune_worktrees(repo_path).unwrap(), 1);
+		assert_eq!(get_worktrees(repo_path).unwrap().len(), 2);
+	}
+
+	#[test]
+	fn test_remove_dirty() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let wt_dir = TempDir::new().unwrap();
+		let wt_path = wt_dir.path().join("feature");
+
+		add_worktree(repo_path, &wt_path, None).unwrap();
+		assert!(is_worktree_clean(repo_path, "feature").unwrap());
+
+		// untracked file
+		fs::write(wt_path.join("b.txt"), "b").unwrap();
+		assert!(!is_worktree_clean(repo_path, "feature").unwrap());
+		assert!(matches!(
+			remove_worktree(repo_path, "feature", false),
+			Err(Error::UncommittedChanges)
+		));
+		fs::remove_file(wt_path.join("b.txt")).unwrap();
+
+		// uncommitted change
+		fs::write(wt_path.join("a.txt"), "a2").unwrap();
+		assert!(remove_worktree(repo_path, "feature", false).is_err());
+		assert!(wt_path.join("a.txt").exists());
+		assert_eq!(get_worktrees(repo_path).unwrap().len(), 2);
+
+		remove_worktree(repo_path, "feature", true).unwrap();
+
+		assert_eq!(get_worktrees(repo_path).unwrap().len(), 1);
+		assert!(!wt_path.exists());
+	}
+}
@@ -10,18 +10,20 @@ use crate::{
 	options::{Options, SharedOptions},
 	popup_stack::PopupStack,
 	popups::{
-		AppOption, BlameFilePopup, BranchListPopup, CommitPopup,
-		CompareCommitsPopup, ConfirmPopup, CreateBranchPopup,
+		AbsorbPopup, AppOption, ApplyPatchPopup, BlameFilePopup,
+		BranchListPopup, CommitPopup, CompareCommitsPopup,
+		ConfirmPopup, CreateBranchPopup, ExportStashPopup,
 		ExternalEditorPopup, FetchPopup, FileRevlogPopup,
 		FuzzyFindPopup, HelpPopup, InspectCommitPopup,
-		LogSearchPopupPopup, MsgPopup, OptionsPopup, PullPopup,
-		PushPopup, PushTagsPopup, RenameBranchPopup, ResetPopup,
+		InteractiveRebasePopup, LogRevspecPopup, LogSearchPopupPopup,
+		MsgPopup, OptionsPopup, PullPopup, PushPopup, PushTagsPopup,
+		ReflogPopup, RenameBranchPopup, ResetPopup,
 		RevisionFilesPopup, StashMsgPopup, SubmodulesListPopup,
-		TagCommitPopup, TagListPopup,
+		TagCommitPopup, TagListPopup, WorktreesListPopup,
 	},
 	queue::{
-		Action, AppTabs, InternalEvent, NeedsUpdate, Queue,
-		StackablePopupOpen,
+		Action, AppTabs, ExternalTool, InternalEvent, NeedsUpdate,
+		Queue, StackablePopupOpen,
 	},
 	setup_popups,
 	strings::{self, ellipsis_trim_start, order},
@@ -35,7 +37,7 @@ use asyncgit::{
 	sync::{
 		self,
 		utils::{repo_work_dir, undo_last_commit},
-		RepoPath, RepoPathRef,
+		RebaseState, RepoPath, RepoPathRef,
 	},
 	AsyncGitNotification, PushType,
 };
@@ -90,8 +92,15 @@ pub struct App {
 	select_branch_popup: BranchListPopup,
 	options_popup: OptionsPopup,
 	submodule_popup: SubmodulesListPopup,
+	worktree_popup: WorktreesListPopup,
 	tags_popup: TagListPopup,
+	reflog_popup: ReflogPopup,
+	absorb_popup: AbsorbPopup,
+	log_revspec_popup: LogRevspecPopup,
+	apply_patch_popup: ApplyPatchPopup,
+	export_stash_popup: ExportStashPopup,
 	reset_popup: ResetPopup,
+	rebase_interactive_popup: InteractiveRebasePopup,
 	cmdbar: RefCell<CommandBar>,
 	tab: usize,
 	revlog: Revlog,
@@ -110,6 +119,7 @@ pub struct App {
 	// "Flags"
 	requires_redraw: Cell<bool>,
 	file_to_open: Option<String>,
+	tool_to_open: Option<ExternalTool>,
 }
 
 pub struct Environment {
@@ -185,6 +195,9 @@ impl App {
 			push_popup: PushPopup::new(&env),
 			push_tags_popup: PushTagsPopup::new(&env),
 			reset_popup: ResetPopup::new(&env),
+			rebase_interactive_popup: InteractiveRebasePopup::new(
+				&env,
+			),
 			pull_popup: PullPopup::new(&env),
 			fetch_popup: FetchPopup::new(&env),
 			tag_commit_popup: TagCommitPopup::new(&env),
@@ -192,8 +205,14 @@ impl App {
 			rename_branch_popup: RenameBranchPopup::new(&env),
 			select_branch_popup: BranchListPopup::new(&env),
 			tags_popup: TagListPopup::new(&env),
+			reflog_popup: ReflogPopup::new(&env),
+			absorb_popup: AbsorbPopup::new(&env),
+			log_revspec_popup: LogRevspecPopup::new(&env),
+			apply_patch_popup: ApplyPatchPopup::new(&env),
+			export_stash_popup: ExportStashPopup::new(&env),
 			options_popup: OptionsPopup::new(&env),
 			submodule_popup: SubmodulesListPopup::new(&env),
+			worktree_popup: WorktreesListPopup::new(&env),
 			log_search_popup: LogSearchPopupPopup::new(&env),
 			fuzzy_find_popup: FuzzyFindPopup::new(&env),
 			do_quit: QuitState::None,
@@ -215,6 +234,7 @@ impl App {
 			key_config: env.key_config,
 			requires_redraw: Cell::new(false),
 			file_to_open: None,
+			tool_to_open: None,
 			repo: env.repo,
 			repo_path_text,
 			popup_stack: PopupStack::default(),
@@ -342,8 +362,12 @@ impl App {
 		} else if let InputEvent::State(polling_state) = ev {
 			self.external_editor_popup.hide();
 			if matches!(polling_state, InputState::Paused) {
-				let result =
-					if let Some(path) = self.file_to_open.take() {
+				if let Some(tool) = self.tool_to_open.take() {
+					self.run_external_tool(&tool)?;
+				} else {
+					let result = if let Some(path) =
+						self.file_to_open.take()
+					{
 						ExternalEditorPopup::open_file_in_editor(
 							&self.repo.borrow(),
 							Path::new(&path),
@@ -354,11 +378,12 @@ impl App {
 						self.commit_popup.show_editor(changes)
 					};
 
-				if let Err(e) = result {
-					let msg =
-						format!("failed to launch editor:\n{e}");
-					log::error!("{}", msg.as_str());
-					self.msg_popup.show_error(msg.as_str())?;
+					if let Err(e) = result {
+						let msg =
+							format!("failed to launch editor:\n{e}");
+						log::error!("{}", msg.as_str());
+						self.msg_popup.show_error(msg.as_str())?;
+					}
 				}
 
 				self.requires_redraw.set(true);
@@ -408,6 +433,11 @@ impl App {
 			self.select_branch_popup.update_git(ev)?;
 		}
 
+		self.status_tab.update_syntax(ev);
+		self.stashing_tab.update_syntax(ev);
+		self.file_revlog_popup.update_syntax(ev);
+		self.inspect_commit_popup.update_syntax(ev);
+		self.compare_commits_popup.update_syntax(ev);
 		self.files_tab.update_async(ev)?;
 		self.blame_file_popup.update_async(ev)?;
 		self.revision_files_popup.update(ev)?;
@@ -483,12 +513,19 @@ impl App {
 			fetch_popup,
 			tag_commit_popup,
 			reset_popup,
+			rebase_interactive_popup,
 			create_branch_popup,
 			rename_branch_popup,
 			select_branch_popup,
 			revision_files_popup,
 			submodule_popup,
+			worktree_popup,
 			tags_popup,
+			reflog_popup,
+			absorb_popup,
+			log_revspec_popup,
+			apply_patch_popup,
+			export_stash_popup,
 			options_popup,
 			help_popup,
 			revlog,
@@ -513,8 +550,15 @@ impl App {
 			tag_commit_popup,
 			select_branch_popup,
 			submodule_popup,
+			worktree_popup,
 			tags_popup,
+			reflog_popup,
+			absorb_popup,
+			log_revspec_popup,
+			apply_patch_popup,
+			export_stash_popup,
 			reset_popup,
+			rebase_interactive_popup,
 			create_branch_popup,
 			rename_branch_popup,
 			revision_files_popup,
@@ -636,6 +680,7 @@ impl App {
 		//NOTE: set when any tree component changed selection
 		if flags.contains(NeedsUpdate::DIFF) {
 			self.status_tab.update_diff()?;
+			self.stashing_tab.update_diff()?;
 			self.inspect_commit_popup.update_diff()?;
 			self.compare_commits_popup.update_diff()?;
 			self.file_revlog_popup.update_diff()?;
@@ -717,11 +762,22 @@ impl App {
 			}
 			InternalEvent::Update(u) => flags.insert(u),
 			InternalEvent::OpenCommit => self.commit_popup.show()?,
+			InternalEvent::OpenCommitWithMessage(msg) => {
+				self.commit_popup.open_with_message(msg)?;
+			}
 			InternalEvent::RewordCommit(id) => {
 				self.commit_popup.open(Some(id))?;
 			}
-			InternalEvent::PopupStashing(opts) => {
-				self.stashmsg_popup.options(opts);
+			InternalEvent::PopupStashing(
+				opts,
+				files,
+				diff_options,
+			) => {
+				self.stashmsg_popup.options(
+					opts,
+					files,
+					diff_options,
+				);
 				self.stashmsg_popup.show()?;
 			}
 			InternalEvent::TagCommit(id) => {
@@ -731,19 +787,53 @@ impl App {
 			InternalEvent::CreateBranch => {
 				self.create_branch_popup.open()?;
 			}
+			InternalEvent::StashBranch(id) => {
+				self.create_branch_popup.open_stash_branch(id)?;
+			}
+			InternalEvent::ExportStash(id) => {
+				self.export_stash_popup.open(id)?;
+			}
 			InternalEvent::RenameBranch(branch_ref, cur_name) => {
 				self.rename_branch_popup
 					.open(branch_ref, cur_name)?;
 			}
 			InternalEvent::SelectBranch => {
-				self.select_branch_popup.open()?;
+				self.select_branch_popup
+					.open(self.revlog.selected_log_refs())?;
 			}
 			InternalEvent::ViewSubmodules => {
 				self.submodule_popup.open()?;
 			}
+			InternalEvent::ViewWorktrees => {
+				self.worktree_popup.open()?;
+			}
 			InternalEvent::Tags => {
 				self.tags_popup.open()?;
 			}
+			InternalEvent::ToggleLogRef(reference) => {
+				self.revlog.toggle_log_ref(reference)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::LogRevspec(revspec) => {
+				self.revlog.set_log_revspec(revspec)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenLogRevspec => {
+				self.log_revspec_popup
+					.open(self.revlog.log_revspec())?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenApplyPatch => {
+				self.apply_patch_popup.open()?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenAbsorb => {
+				self.absorb_popup.open()?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenReflog => {
+				self.reflog_popup.open()?;
+			}
 			InternalEvent::TabSwitchStatus => self.set_tab(0)?,
 			InternalEvent::TabSwitch(tab) => {
 				self.switch_to_tab(&tab)?;
@@ -765,6 +855,12 @@ impl App {
 				self.file_to_open = path;
 				flags.insert(NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::OpenExternalTool(tool) => {
+				self.input.set_polling(false);
+				self.external_editor_popup.show()?;
+				self.tool_to_open = Some(tool);
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::Push(branch, push_type, force, delete) => {
 				self.push_popup
 					.push(branch, push_type, force, delete)?;
@@ -810,9 +906,15 @@ impl App {
 					}
 					AppOption::DiffContextLines
 					| AppOption::DiffIgnoreWhitespaces
+					| AppOption::DiffIgnoreWhitespaceChange
+					| AppOption::DiffIgnoreWhitespaceEol
+					| AppOption::DiffIgnoreBlankLines
+					| AppOption::DiffAlgorithm
 					| AppOption::DiffInterhunkLines => {
 						self.status_tab.update_diff()?;
+						self.stashing_tab.update_diff()?;
 					}
+					AppOption::DiffSideBySide => {}
 				}
 
 				flags.insert(NeedsUpdate::ALL);
@@ -872,11 +974,76 @@ impl App {
 			InternalEvent::CommitSearch(options) => {
 				self.revlog.search(options);
 			}
+			InternalEvent::OpenInteractiveRebase(base) => {
+				self.rebase_interactive_popup.open(base)?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::RebaseProgressed(state) => {
+				self.rebase_progressed(state)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
 		};
 
 		Ok(flags)
 	}
 
+	fn rebase_progressed(
+		&mut self,
+		state: RebaseState,
+	) -> Result<()> {
+		match state {
+			RebaseState::Finished => (),
+			RebaseState::Conflicted => {
+				self.queue.push(InternalEvent::TabSwitchStatus);
+			}
+			RebaseState::Edit(id) => {
+				self.queue.push(InternalEvent::TabSwitchStatus);
+				self.msg_popup.show_info(
+					&strings::rebase_interactive_stopped_edit(id),
+				)?;
+			}
+			RebaseState::Reword(id) => {
+				self.commit_popup.open_rebase_reword(id)?;
+			}
+			RebaseState::Split(id) => {
+				self.queue.push(InternalEvent::TabSwitchStatus);
+				self.msg_popup.show_info(
+					&strings::rebase_interactive_stopped_split(id),
+				)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// runs `tool` and refreshes the status of the changed files
+	fn run_external_tool(
+		&mut self,
+		tool: &ExternalTool,
+	) -> Result<()> {
+		match ExternalEditorPopup::open_external_tool(
+			&self.repo.borrow(),
+			tool,
+		) {
+			Ok(true) => (),
+			Ok(false) => {
+				if let ExternalTool::Merge(path) = tool {
+					self.msg_popup.show_info(
+						&strings::msg_merge_tool_unresolved(path),
+					)?;
+				}
+			}
+			Err(e) => {
+				let msg =
+					format!("failed to run external tool:\n{e}");
+				log::error!("{}", msg.as_str());
+				self.msg_popup.show_error(msg.as_str())?;
+			}
+		}
+
+		self.status_tab.update()
+	}
+
 	fn process_confirmed_action(
 		&mut self,
 		action: Action,
@@ -954,6 +1121,9 @@ impl App {
 			Action::AbortRebase => {
 				self.status_tab.abort_rebase();
 			}
+			Action::AbortCherryPick => {
+				self.status_tab.abort_cherry_pick();
+			}
 			Action::UndoCommit => {
 				try_or_popup!(
 					self,
@@ -961,6 +1131,19 @@ impl App {
 					undo_last_commit(&self.repo.borrow())
 				);
 			}
+			Action::RemoveWorktree(name, force) => {
+				try_or_popup!(
+					self,
+					"remove worktree failed:",
+					sync::remove_worktree(
+						&self.repo.borrow(),
+						&name,
+						force
+					)
+				);
+
+				self.worktree_popup.update_worktrees()?;
+			}
 		};
 
 		flags.insert(NeedsUpdate::ALL);
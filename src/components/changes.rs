@@ -8,12 +8,15 @@ use crate::{
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
+	queue::{
+		Action, ExternalTool, InternalEvent, NeedsUpdate, Queue,
+		ResetItem,
+	},
 	strings, try_or_popup,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, RepoPathRef},
+	sync::{self, DiffToolTarget, RepoPathRef},
 	StatusItem, StatusItemType,
 };
 use crossterm::event::Event;
@@ -76,6 +79,19 @@ impl ChangesComponent {
 		self.files.is_file_selected()
 	}
 
+	/// diff tool for the selected file, merge tool if it is conflicted
+	fn external_tool(&self) -> Option<ExternalTool> {
+		let item = self.files.selection_file()?;
+
+		Some(if item.status == StatusItemType::Conflicted {
+			ExternalTool::Merge(item.path)
+		} else if self.is_working_dir {
+			ExternalTool::Diff(item.path, DiffToolTarget::WorkDir)
+		} else {
+			ExternalTool::Diff(item.path, DiffToolTarget::Stage)
+		})
+	}
+
 	fn index_add_remove(&mut self) -> Result<bool> {
 		if let Some(tree_item) = self.selection() {
 			if self.is_working_dir {
@@ -235,6 +251,17 @@ impl Component for ChangesComponent {
 			));
 		}
 
+		let tool = self.external_tool();
+		out.push(CommandInfo::new(
+			if matches!(tool, Some(ExternalTool::Merge(_))) {
+				strings::commands::open_mergetool(&self.key_config)
+			} else {
+				strings::commands::open_difftool(&self.key_config)
+			},
+			tool.is_some(),
+			self.focused() || force_all,
+		));
+
 		CommandBlocking::PassingOn
 	}
 
@@ -282,6 +309,16 @@ impl Component for ChangesComponent {
 				) && self.is_working_dir
 				{
 					Ok(self.dispatch_reset_workdir().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.external_tool,
+				) {
+					if let Some(tool) = self.external_tool() {
+						self.queue.push(
+							InternalEvent::OpenExternalTool(tool),
+						);
+					}
+					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
 					self.key_config.keys.status_ignore_file,
@@ -10,11 +10,12 @@ use crate::{
 	accessors,
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
+	queue::{ExternalTool, InternalEvent, Queue},
 	strings,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{commit_files::OldNew, CommitTags},
+	sync::{commit_files::OldNew, CommitTags, DiffToolTarget},
 	AsyncCommitFiles, CommitFilesParams,
 };
 use compare_details::CompareDetailsComponent;
@@ -32,6 +33,7 @@ pub struct CommitDetailsComponent {
 	file_tree: StatusTreeComponent,
 	git_commit_files: AsyncCommitFiles,
 	visible: bool,
+	queue: Queue,
 	key_config: SharedKeyConfig,
 }
 
@@ -50,6 +52,7 @@ impl CommitDetailsComponent {
 			file_tree: StatusTreeComponent::new(env, "", false),
 			visible: false,
 			commit: None,
+			queue: env.queue.clone(),
 			key_config: env.key_config.clone(),
 		}
 	}
@@ -136,6 +139,24 @@ impl CommitDetailsComponent {
 	fn is_compare(&self) -> bool {
 		self.commit.is_some_and(|p| p.other.is_some())
 	}
+
+	/// diff tool comparing the selected file to its previous version
+	fn external_tool(&self) -> Option<ExternalTool> {
+		let commit = self.commit?;
+		let item = self.file_tree.selection_file()?;
+
+		let target = commit.other.map_or(
+			DiffToolTarget::Commit(commit.id),
+			|old| {
+				DiffToolTarget::Commits(OldNew {
+					old,
+					new: commit.id,
+				})
+			},
+		);
+
+		Some(ExternalTool::Diff(item.path, target))
+	}
 }
 
 impl DrawableComponent for CommitDetailsComponent {
@@ -190,6 +211,12 @@ impl Component for CommitDetailsComponent {
 				force_all,
 				self.components().as_slice(),
 			);
+
+			out.push(CommandInfo::new(
+				strings::commands::open_difftool(&self.key_config),
+				self.external_tool().is_some(),
+				self.file_tree.focused() || force_all,
+			));
 		}
 
 		CommandBlocking::PassingOn
@@ -221,6 +248,17 @@ impl Component for CommitDetailsComponent {
 					self.file_tree.focus(false);
 					self.set_details_focus(true);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.external_tool,
+				) && self.file_tree.focused()
+				{
+					if let Some(tool) = self.external_tool() {
+						self.queue.push(
+							InternalEvent::OpenExternalTool(tool),
+						);
+					}
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
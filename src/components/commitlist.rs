@@ -15,7 +15,7 @@ use crate::{
 use anyhow::Result;
 use asyncgit::sync::{
 	self, checkout_commit, BranchDetails, BranchInfo, CommitId,
-	RepoPathRef, Tags,
+	GraphRow, RepoPathRef, Tags,
 };
 use chrono::{DateTime, Local};
 use crossterm::event::Event;
@@ -35,6 +35,7 @@ use std::{
 
 const ELEMENTS_PER_LINE: usize = 9;
 const SLICE_SIZE: usize = 1200;
+const MAX_GRAPH_LANES: usize = 16;
 
 ///
 pub struct CommitList {
@@ -45,6 +46,7 @@ pub struct CommitList {
 	items: ItemBatch,
 	highlights: Option<Rc<IndexSet<CommitId>>>,
 	commits: IndexSet<CommitId>,
+	graph: Vec<GraphRow>,
 	marked: Vec<(usize, CommitId)>,
 	scroll_state: (Instant, f32),
 	tags: Option<Tags>,
@@ -67,6 +69,7 @@ impl CommitList {
 			selection: 0,
 			highlighted_selection: None,
 			commits: IndexSet::new(),
+			graph: Vec::new(),
 			highlights: None,
 			scroll_state: (Instant::now(), 0_f32),
 			tags: None,
@@ -86,10 +89,16 @@ impl CommitList {
 		self.tags.as_ref()
 	}
 
+	///
+	pub fn set_title(&mut self, title: &str) {
+		self.title = title.into();
+	}
+
 	///
 	pub fn clear(&mut self) {
 		self.items.clear();
 		self.commits.clear();
+		self.graph.clear();
 	}
 
 	///
@@ -235,6 +244,11 @@ impl CommitList {
 		}
 	}
 
+	/// appends graph rows for the commits in the same order
+	pub fn extend_graph(&mut self, rows: Vec<GraphRow>) {
+		self.graph.extend(rows);
+	}
+
 	///
 	pub fn set_highlighting(
 		&mut self,
@@ -452,6 +466,7 @@ impl CommitList {
 		&self,
 		e: &'a LogEntry,
 		selected: bool,
+		graph: Vec<Span<'a>>,
 		tags: Option<String>,
 		local_branches: Option<String>,
 		remote_branches: Option<String>,
@@ -490,6 +505,12 @@ impl CommitList {
 			txt.push(splitter.clone());
 		}
 
+		// commit graph
+		if !graph.is_empty() {
+			txt.extend(graph);
+			txt.push(splitter.clone());
+		}
+
 		let style_hash = normal
 			.then(|| theme.commit_hash(selected))
 			.unwrap_or_else(|| theme.commit_unhighlighted());
@@ -548,9 +569,8 @@ impl CommitList {
 
 		txt.push(splitter);
 
-		let message_width = width.saturating_sub(
-			txt.iter().map(|span| span.content.len()).sum(),
-		);
+		let message_width =
+			width.saturating_sub(txt.iter().map(Span::width).sum());
 
 		// commit msg
 		txt.push(Span::styled(
@@ -570,6 +590,18 @@ impl CommitList {
 
 		let any_marked = !self.marked.is_empty();
 
+		let graph_offset =
+			self.items.index_offset() + self.scroll_top.get();
+		let graph_lanes = self
+			.graph
+			.iter()
+			.skip(graph_offset)
+			.take(height)
+			.map(|row| row.cells.len())
+			.max()
+			.unwrap_or_default()
+			.min(MAX_GRAPH_LANES);
+
 		for (idx, e) in self
 			.items
 			.iter()
@@ -602,9 +634,19 @@ impl CommitList {
 				None
 			};
 
+			let selected = idx + self.scroll_top.get() == selection;
+
+			let graph = self.graph_spans(
+				self.graph.get(graph_offset + idx),
+				graph_lanes,
+				selected,
+				!self.items.highlighting() || e.highlighted,
+			);
+
 			txt.push(self.get_entry_to_add(
 				e,
-				idx + self.scroll_top.get() == selection,
+				selected,
+				graph,
 				tags,
 				local_branches,
 				self.remote_branches_string(e),
@@ -618,6 +660,50 @@ impl CommitList {
 		txt
 	}
 
+	/// graph column of a single row padded to `lanes`
+	fn graph_spans(
+		&self,
+		row: Option<&GraphRow>,
+		lanes: usize,
+		selected: bool,
+		normal: bool,
+	) -> Vec<Span<'static>> {
+		let style = |color| {
+			if normal {
+				self.theme.commit_graph(color, selected)
+			} else {
+				self.theme.commit_unhighlighted()
+			}
+		};
+
+		let mut spans = Vec::with_capacity(lanes * 2);
+
+		for idx in 0..lanes {
+			let cell = row
+				.and_then(|row| row.cells.get(idx))
+				.copied()
+				.unwrap_or_default();
+
+			spans.push(Span::styled(
+				cell.symbol().to_string(),
+				style(cell.color),
+			));
+
+			if idx + 1 < lanes {
+				spans.push(Span::styled(
+					String::from(if cell.right() {
+						"─"
+					} else {
+						" "
+					}),
+					style(cell.link.unwrap_or_default()),
+				));
+			}
+		}
+
+		spans
+	}
+
 	fn remote_branches_string(&self, e: &LogEntry) -> Option<String> {
 		self.remote_branches.get(&e.id).and_then(|remote_branches| {
 			let filtered_branches: Vec<_> = remote_branches
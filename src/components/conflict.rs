@@ -0,0 +1,471 @@
+use super::{
+	string_width_align, CommandBlocking, DrawableComponent,
+	ScrollType, VerticalScroll,
+};
+use crate::{
+	app::Environment,
+	components::{CommandInfo, Component, EventState},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	string_utils::tabs_to_spaces,
+	strings, try_or_popup,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, ConflictResolution, ConflictSection, FileConflict,
+	RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::Rect,
+	style::Style,
+	text::{Line, Span},
+	widgets::{Block, Borders, Paragraph},
+	Frame,
+};
+use std::cell::Cell;
+
+/// single row of the side by side view
+struct Row {
+	ours: String,
+	theirs: String,
+	/// index of the conflict region this row belongs to
+	region: Option<usize>,
+	is_header: bool,
+}
+
+/// shows the conflicting regions of a file side by side
+/// (ours on the left, theirs on the right) and lets the user
+/// pick a resolution per region before staging the result
+pub struct ConflictComponent {
+	repo: RepoPathRef,
+	conflict: Option<FileConflict>,
+	resolutions: Vec<Option<ConflictResolution>>,
+	rows: Vec<Row>,
+	selection: usize,
+	current_height: Cell<u16>,
+	focused: bool,
+	scroll: VerticalScroll,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ConflictComponent {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			conflict: None,
+			resolutions: Vec::new(),
+			rows: Vec::new(),
+			selection: 0,
+			current_height: Cell::new(0),
+			focused: false,
+			scroll: VerticalScroll::new(),
+			queue: env.queue.clone(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// `true` if a conflicted file is loaded
+	pub const fn is_active(&self) -> bool {
+		self.conflict.is_some()
+	}
+
+	/// path of the loaded conflicted file
+	pub fn current(&self) -> Option<&str> {
+		self.conflict.as_ref().map(|c| c.path.as_str())
+	}
+
+	///
+	pub fn clear(&mut self) {
+		self.conflict = None;
+		self.resolutions.clear();
+		self.rows.clear();
+		self.selection = 0;
+	}
+
+	/// loads the conflict stages of `path` unless already shown
+	pub fn update(&mut self, path: &str) -> Result<()> {
+		if self.current() == Some(path) {
+			return Ok(());
+		}
+
+		let conflict =
+			sync::get_file_conflict(&self.repo.borrow(), path)?;
+
+		self.resolutions = vec![None; conflict.conflicts()];
+		self.conflict = Some(conflict);
+		self.selection = 0;
+		self.update_rows();
+
+		Ok(())
+	}
+
+	fn update_rows(&mut self) {
+		self.rows.clear();
+
+		let Some(conflict) = &self.conflict else {
+			return;
+		};
+
+		let mut region = 0;
+
+		for section in &conflict.sections {
+			match section {
+				ConflictSection::Resolved(lines) => {
+					self.rows.extend(lines.iter().map(|line| {
+						let line = tabs_to_spaces(
+							line.trim_end_matches('\n').to_string(),
+						);
+						Row {
+							ours: line.clone(),
+							theirs: line,
+							region: None,
+							is_header: false,
+						}
+					}));
+				}
+				ConflictSection::Conflict {
+					ours, theirs, ..
+				} => {
+					self.rows.push(Row {
+						ours: String::new(),
+						theirs: String::new(),
+						region: Some(region),
+						is_header: true,
+					});
+
+					let line = |lines: &[String], idx: usize| {
+						lines.get(idx).map_or_else(String::new, |l| {
+							tabs_to_spaces(
+								l.trim_end_matches('\n').to_string(),
+							)
+						})
+					};
+
+					self.rows.extend(
+						(0..ours.len().max(theirs.len())).map(
+							|idx| Row {
+								ours: line(ours, idx),
+								theirs: line(theirs, idx),
+								region: Some(region),
+								is_header: false,
+							},
+						),
+					);
+
+					region += 1;
+				}
+			}
+		}
+	}
+
+	/// region the selection is in or the closest one above it
+	fn selected_region(&self) -> Option<usize> {
+		self.rows
+			.iter()
+			.take(self.selection + 1)
+			.rev()
+			.find_map(|row| row.region)
+			.or_else(|| (!self.resolutions.is_empty()).then_some(0))
+	}
+
+	fn is_resolved(&self) -> bool {
+		self.conflict.is_some()
+			&& self.resolutions.iter().all(Option::is_some)
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let height = usize::from(self.current_height.get());
+		let max = self.rows.len().saturating_sub(1);
+
+		self.selection = match scroll {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			ScrollType::PageUp => {
+				self.selection.saturating_sub(height)
+			}
+			ScrollType::PageDown => {
+				self.selection.saturating_add(height)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max,
+		}
+		.min(max);
+	}
+
+	fn move_region(&mut self, next: bool) {
+		let mut headers =
+			self.rows.iter().enumerate().filter_map(|(idx, row)| {
+				row.is_header.then_some(idx)
+			});
+
+		let target = if next {
+			headers.find(|idx| *idx > self.selection)
+		} else {
+			headers.rfind(|idx| *idx < self.selection)
+		};
+
+		if let Some(target) = target {
+			self.selection = target;
+		}
+	}
+
+	fn pick(&mut self, resolution: ConflictResolution, file: bool) {
+		if file {
+			self.resolutions.fill(Some(resolution));
+		} else if let Some(region) = self.selected_region() {
+			if let Some(entry) = self.resolutions.get_mut(region) {
+				*entry = Some(resolution);
+			}
+		}
+	}
+
+	fn stage(&self) -> Result<()> {
+		let Some(conflict) = &self.conflict else {
+			return Ok(());
+		};
+
+		let resolutions = self
+			.resolutions
+			.iter()
+			.flatten()
+			.copied()
+			.collect::<Vec<_>>();
+
+		sync::resolve_file_conflict(
+			&self.repo.borrow(),
+			&conflict.path,
+			&resolutions,
+		)?;
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn header_text(&self, region: usize) -> String {
+		let state =
+			match self.resolutions.get(region).copied().flatten() {
+				Some(ConflictResolution::Ours) => "ours",
+				Some(ConflictResolution::Theirs) => "theirs",
+				Some(ConflictResolution::Both) => "both",
+				None => "unresolved",
+			};
+
+		format!(
+			"@@ conflict {}/{}: {state} @@",
+			region + 1,
+			self.resolutions.len()
+		)
+	}
+
+	/// styles of the left and right column of a conflict row
+	fn side_styles(
+		&self,
+		region: usize,
+		selected: bool,
+	) -> (Style, Style) {
+		let (ours, theirs) =
+			match self.resolutions.get(region).copied().flatten() {
+				Some(ConflictResolution::Ours) => (true, false),
+				Some(ConflictResolution::Theirs) => (false, true),
+				Some(ConflictResolution::Both) | None => (true, true),
+			};
+
+		(
+			self.theme.text(ours, selected),
+			self.theme.text(theirs, selected),
+		)
+	}
+
+	fn get_text(&self, width: usize, height: usize) -> Vec<Line> {
+		let column = width.saturating_sub(1) / 2;
+		let selected_region = self.selected_region();
+
+		self.rows
+			.iter()
+			.enumerate()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.map(|(idx, row)| {
+				let selected = idx == self.selection && self.focused;
+
+				if row.is_header {
+					let region = row.region.unwrap_or_default();
+					let current = selected_region == Some(region);
+
+					return Line::from(vec![Span::styled(
+						string_width_align(
+							&self.header_text(region),
+							width,
+						),
+						self.theme.diff_hunk_marker(current),
+					)]);
+				}
+
+				let (ours_style, theirs_style) =
+					row.region.map_or_else(
+						|| {
+							let style =
+								self.theme.text(false, selected);
+							(style, style)
+						},
+						|region| self.side_styles(region, selected),
+					);
+
+				Line::from(vec![
+					Span::styled(
+						string_width_align(&row.ours, column),
+						ours_style,
+					),
+					Span::styled(
+						String::from("│"),
+						self.theme.block(false),
+					),
+					Span::styled(
+						string_width_align(&row.theirs, column),
+						theirs_style,
+					),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for ConflictComponent {
+	fn draw(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		let width = usize::from(r.width.saturating_sub(2));
+		let height = usize::from(r.height.saturating_sub(2));
+		self.current_height.set(height.try_into()?);
+
+		self.scroll.update(self.selection, self.rows.len(), height);
+
+		let title = format!(
+			"{}{}",
+			strings::title_conflict(&self.key_config),
+			self.current().unwrap_or_default()
+		);
+
+		f.render_widget(
+			Paragraph::new(self.get_text(width, height)).block(
+				Block::default()
+					.title(Span::styled(
+						title.as_str(),
+						self.theme.title(self.focused),
+					))
+					.borders(Borders::ALL)
+					.border_style(self.theme.block(self.focused)),
+			),
+			r,
+		);
+
+		if self.focused {
+			self.scroll.draw(f, r, &self.theme);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ConflictComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		_force_all: bool,
+	) -> CommandBlocking {
+		let active = self.focused && self.is_active();
+
+		out.push(CommandInfo::new(
+			strings::commands::scroll(&self.key_config),
+			!self.rows.is_empty(),
+			active,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::conflict_region_next_prev(
+				&self.key_config,
+			),
+			!self.resolutions.is_empty(),
+			active,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::conflict_pick(&self.key_config),
+			!self.resolutions.is_empty(),
+			active,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::conflict_pick_file(&self.key_config),
+			!self.resolutions.is_empty(),
+			active,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::conflict_stage(&self.key_config),
+			self.is_resolved(),
+			active,
+		));
+
+		CommandBlocking::PassingOn
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.focused || !self.is_active() {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			let keys = &self.key_config.keys;
+
+			if key_match(e, keys.move_down) {
+				self.move_selection(ScrollType::Down);
+			} else if key_match(e, keys.move_up) {
+				self.move_selection(ScrollType::Up);
+			} else if key_match(e, keys.page_down) {
+				self.move_selection(ScrollType::PageDown);
+			} else if key_match(e, keys.page_up) {
+				self.move_selection(ScrollType::PageUp);
+			} else if key_match(e, keys.home) {
+				self.move_selection(ScrollType::Home);
+			} else if key_match(e, keys.end) {
+				self.move_selection(ScrollType::End);
+			} else if key_match(e, keys.diff_hunk_next) {
+				self.move_region(true);
+			} else if key_match(e, keys.diff_hunk_prev) {
+				self.move_region(false);
+			} else if key_match(e, keys.conflict_pick_ours) {
+				self.pick(ConflictResolution::Ours, false);
+			} else if key_match(e, keys.conflict_pick_theirs) {
+				self.pick(ConflictResolution::Theirs, false);
+			} else if key_match(e, keys.conflict_pick_both) {
+				self.pick(ConflictResolution::Both, false);
+			} else if key_match(e, keys.conflict_pick_ours_file) {
+				self.pick(ConflictResolution::Ours, true);
+			} else if key_match(e, keys.conflict_pick_theirs_file) {
+				self.pick(ConflictResolution::Theirs, true);
+			} else if key_match(e, keys.conflict_pick_both_file) {
+				self.pick(ConflictResolution::Both, true);
+			} else if key_match(e, keys.stage_unstage_item)
+				&& self.is_resolved()
+			{
+				try_or_popup!(self, "resolve error:", self.stage());
+			} else {
+				return Ok(EventState::NotConsumed);
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn focused(&self) -> bool {
+		self.focused
+	}
+
+	fn focus(&mut self, focus: bool) {
+		self.focused = focus;
+	}
+}
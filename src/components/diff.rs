@@ -12,10 +12,15 @@ use crate::{
 	string_utils::tabs_to_spaces,
 	string_utils::trim_offset,
 	strings, try_or_popup,
-	ui::style::SharedTheme,
+	ui::{
+		style::SharedTheme, AsyncDiffSyntaxJob, DiffSyntaxText,
+		SyntaxRanges,
+	},
+	AsyncAppNotification, AsyncNotification, SyntaxHighlightProgress,
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	hash,
 	sync::{self, diff::DiffLinePosition, RepoPathRef},
 	DiffLine, DiffLineType, FileDiff,
@@ -29,7 +34,11 @@ use ratatui::{
 	widgets::{Block, Borders, Paragraph},
 	Frame,
 };
-use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use std::{
+	borrow::Cow, cell::Cell, cmp, collections::HashSet, path::Path,
+};
+use unicode_truncate::UnicodeTruncateStr;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Default)]
 struct Current {
@@ -101,14 +110,128 @@ impl Selection {
 	}
 }
 
+/// row of the side-by-side view, indices into the lines of all hunks
+#[derive(Clone, Copy)]
+struct SplitRow {
+	old: Option<usize>,
+	new: Option<usize>,
+	hunk: usize,
+	end_of_hunk: bool,
+}
+
+impl SplitRow {
+	fn first_line(&self) -> Option<usize> {
+		match (self.old, self.new) {
+			(Some(old), Some(new)) => Some(old.min(new)),
+			(old, new) => old.or(new),
+		}
+	}
+}
+
+/// old and new lines of a diff aligned next to each other
+#[derive(Default)]
+struct SplitView {
+	rows: Vec<SplitRow>,
+	/// row of each line of all hunks
+	row_of_line: Vec<usize>,
+	/// width of the widest old or new line number
+	lineno_width: usize,
+}
+
+impl SplitView {
+	fn new(diff: &FileDiff) -> Self {
+		let mut view = Self::default();
+		let mut line_index = 0;
+		let mut max_lineno = 0;
+
+		for (hunk_index, hunk) in diff.hunks.iter().enumerate() {
+			let mut deleted = Vec::new();
+			let mut added = Vec::new();
+
+			for line in &hunk.lines {
+				max_lineno = max_lineno
+					.max(line.position.old_lineno.unwrap_or_default())
+					.max(
+						line.position.new_lineno.unwrap_or_default(),
+					);
+
+				match line.line_type {
+					DiffLineType::Delete => deleted.push(line_index),
+					DiffLineType::Add => added.push(line_index),
+					DiffLineType::Header | DiffLineType::None => {
+						view.push_changes(
+							hunk_index,
+							&mut deleted,
+							&mut added,
+						);
+						view.push(SplitRow {
+							old: Some(line_index),
+							new: Some(line_index),
+							hunk: hunk_index,
+							end_of_hunk: false,
+						});
+					}
+				}
+
+				line_index += 1;
+			}
+
+			view.push_changes(hunk_index, &mut deleted, &mut added);
+
+			if let Some(last) = view.rows.last_mut() {
+				last.end_of_hunk = true;
+			}
+		}
+
+		view.lineno_width = max_lineno.to_string().len();
+
+		view
+	}
+
+	/// pairs deleted lines with the lines added in their place
+	fn push_changes(
+		&mut self,
+		hunk: usize,
+		deleted: &mut Vec<usize>,
+		added: &mut Vec<usize>,
+	) {
+		for i in 0..deleted.len().max(added.len()) {
+			self.push(SplitRow {
+				old: deleted.get(i).copied(),
+				new: added.get(i).copied(),
+				hunk,
+				end_of_hunk: false,
+			});
+		}
+
+		deleted.clear();
+		added.clear();
+	}
+
+	fn push(&mut self, row: SplitRow) {
+		let index = self.rows.len();
+		for line in row.old.iter().chain(row.new.iter()) {
+			if self.row_of_line.len() <= *line {
+				self.row_of_line.resize(line + 1, 0);
+			}
+			self.row_of_line[*line] = index;
+		}
+		self.rows.push(row);
+	}
+}
+
 ///
 pub struct DiffComponent {
 	repo: RepoPathRef,
 	diff: Option<FileDiff>,
 	longest_line: usize,
+	split: SplitView,
+	syntax: AsyncSingleJob<AsyncDiffSyntaxJob>,
+	syntax_text: Option<DiffSyntaxText>,
 	pending: bool,
 	selection: Selection,
 	selected_hunk: Option<usize>,
+	marked_hunks: HashSet<u64>,
 	current_size: Cell<(u16, u16)>,
 	focused: bool,
 	current: Current,
@@ -130,8 +253,12 @@ impl DiffComponent {
 			current: Current::default(),
 			pending: false,
 			selected_hunk: None,
+			marked_hunks: HashSet::new(),
 			diff: None,
 			longest_line: 0,
+			split: SplitView::default(),
+			syntax: AsyncSingleJob::new(env.sender_app.clone()),
+			syntax_text: None,
 			current_size: Cell::new((0, 0)),
 			selection: Selection::Single(0),
 			vertical_scroll: VerticalScroll::new(),
@@ -151,11 +278,25 @@ impl DiffComponent {
 	pub fn current(&self) -> (String, bool) {
 		(self.current.path.clone(), self.current.is_stage)
 	}
+	/// header hash of the selected hunk
+	pub fn selected_hunk_hash(&self) -> Option<u64> {
+		let diff = self.diff.as_ref()?;
+
+		self.selected_hunk
+			.and_then(|hunk| diff.hunks.get(hunk))
+			.map(|hunk| hunk.header_hash)
+	}
+	/// hunks (by header hash) to draw with a mark
+	pub fn set_marked_hunks(&mut self, hunks: HashSet<u64>) {
+		self.marked_hunks = hunks;
+	}
 	///
 	pub fn clear(&mut self, pending: bool) {
 		self.current = Current::default();
 		self.diff = None;
 		self.longest_line = 0;
+		self.split = SplitView::default();
+		self.syntax_text = None;
 		self.vertical_scroll.reset();
 		self.horizontal_scroll.reset();
 		self.selection = Selection::Single(0);
@@ -176,12 +317,22 @@ impl DiffComponent {
 		if self.current.hash != hash {
 			let reset_selection = self.current.path != path;
 
+			self.syntax_text = None;
+			if !diff.hunks.is_empty() {
+				self.syntax.spawn(AsyncDiffSyntaxJob::new(
+					diff.clone(),
+					hash,
+					path.clone(),
+				));
+			}
+
 			self.current = Current {
 				path,
 				is_stage,
 				hash,
 			};
 
+			self.split = SplitView::new(&diff);
 			self.diff = Some(diff);
 
 			self.longest_line = self
@@ -217,9 +368,36 @@ impl DiffComponent {
 		}
 	}
 
+	///
+	pub fn update_syntax(&mut self, ev: AsyncNotification) {
+		if ev
+			== AsyncNotification::App(
+				AsyncAppNotification::SyntaxHighlighting(
+					SyntaxHighlightProgress::Done,
+				),
+			) {
+			if let Some(syntax) =
+				self.syntax.take_last().and_then(|job| job.result())
+			{
+				if syntax.hash() == self.current.hash {
+					self.syntax_text = Some(syntax);
+				}
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.syntax.is_pending()
+	}
+
+	fn syntax_line(&self, index: usize) -> Option<&SyntaxRanges> {
+		self.syntax_text.as_ref().and_then(|text| text.line(index))
+	}
+
 	fn move_selection(&mut self, move_type: ScrollType) {
-		if let Some(diff) = &self.diff {
-			let max = diff.lines.saturating_sub(1);
+		if self.diff.is_some() {
+			let max = self.lines_count().saturating_sub(1);
 
 			let new_start = match move_type {
 				ScrollType::Down => {
@@ -250,21 +428,81 @@ impl DiffComponent {
 
 	fn update_selection(&mut self, new_start: usize) {
 		if let Some(diff) = &self.diff {
-			let max = diff.lines.saturating_sub(1);
+			let max = self.lines_count().saturating_sub(1);
 			let new_start = cmp::min(max, new_start);
 			self.selection = Selection::Single(new_start);
-			self.selected_hunk =
-				Self::find_selected_hunk(diff, new_start);
+			self.selected_hunk = Self::find_selected_hunk(
+				diff,
+				self.position_line(new_start),
+			);
 		}
 	}
 
+	fn is_side_by_side(&self) -> bool {
+		self.options.borrow().diff_side_by_side()
+	}
+
+	fn toggle_side_by_side(&mut self) {
+		let line = self.position_line(self.selection.get_start());
+
+		self.options.borrow_mut().diff_toggle_side_by_side();
+
+		self.horizontal_scroll.reset();
+		self.update_selection(self.line_position(line));
+	}
+
+	/// number of selectable positions: lines, or rows when side-by-side
 	fn lines_count(&self) -> usize {
-		self.diff.as_ref().map_or(0, |diff| diff.lines)
+		if self.is_side_by_side() {
+			self.split.rows.len()
+		} else {
+			self.diff.as_ref().map_or(0, |diff| diff.lines)
+		}
+	}
+
+	/// first line shown at a selection position
+	fn position_line(&self, position: usize) -> usize {
+		if self.is_side_by_side() {
+			self.split
+				.rows
+				.get(position)
+				.and_then(SplitRow::first_line)
+				.unwrap_or(position)
+		} else {
+			position
+		}
+	}
+
+	/// selection position showing a line
+	fn line_position(&self, line: usize) -> usize {
+		if self.is_side_by_side() {
+			self.split.row_of_line.get(line).copied().unwrap_or(line)
+		} else {
+			line
+		}
+	}
+
+	fn is_line_selected(&self, line: usize) -> bool {
+		self.selection.contains(self.line_position(line))
+	}
+
+	/// widths of the line numbers and the content of each side
+	fn side_by_side_widths(&self, width: u16) -> (usize, usize) {
+		let lineno_width = self.split.lineno_width;
+		// hunk marker and the separator between both sides
+		let column = usize::from(width).saturating_sub(2) / 2;
+
+		(lineno_width, column.saturating_sub(lineno_width + 1))
 	}
 
 	fn max_scroll_right(&self) -> usize {
-		self.longest_line
-			.saturating_sub(self.current_size.get().0.into())
+		let width = if self.is_side_by_side() {
+			self.side_by_side_widths(self.current_size.get().0).1
+		} else {
+			self.current_size.get().0.into()
+		};
+
+		self.longest_line.saturating_sub(width)
 	}
 
 	fn modify_selection(&mut self, direction: Direction) {
@@ -281,7 +519,7 @@ impl DiffComponent {
 					.flat_map(|hunk| hunk.lines.iter())
 					.enumerate()
 					.filter_map(|(i, line)| {
-						if self.selection.contains(i) {
+						if self.is_line_selected(i) {
 							Some(line.content.trim_matches(|c| {
 								c == '\n' || c == '\r'
 							}))
@@ -328,6 +566,8 @@ impl DiffComponent {
 		if let Some(diff) = &self.diff {
 			return if diff.hunks.is_empty() {
 				self.get_text_binary(diff)
+			} else if self.is_side_by_side() {
+				self.get_text_side_by_side(diff, width, height)
 			} else {
 				let mut res: Vec<Line> = Vec::new();
 
@@ -342,6 +582,8 @@ impl DiffComponent {
 						&& self
 							.selected_hunk
 							.map_or(false, |s| s == i);
+					let hunk_marked =
+						self.marked_hunks.contains(&hunk.header_hash);
 
 					if lines_added >= height as usize {
 						break;
@@ -359,19 +601,28 @@ impl DiffComponent {
 							if line_cursor >= min
 								&& line_cursor <= max
 							{
-								res.push(Self::get_line_to_add(
-									width,
-									line,
-									self.focused()
-										&& self
-											.selection
-											.contains(line_cursor),
-									hunk_selected,
-									i == hunk_len - 1,
-									&self.theme,
-									self.horizontal_scroll
-										.get_right(),
-								));
+								let line_to_add =
+									Self::get_line_to_add(
+										width,
+										line,
+										self.syntax_line(line_cursor),
+										self.focused()
+											&& self
+												.selection
+												.contains(
+													line_cursor,
+												),
+										hunk_selected,
+										i == hunk_len - 1,
+										&self.theme,
+										self.horizontal_scroll
+											.get_right(),
+									);
+								res.push(if i == 0 && hunk_marked {
+									Self::mark_hunk(line_to_add)
+								} else {
+									line_to_add
+								});
 								lines_added += 1;
 							}
 
@@ -389,6 +640,146 @@ impl DiffComponent {
 		vec![]
 	}
 
+	fn get_text_side_by_side<'a>(
+		&'a self,
+		diff: &'a FileDiff,
+		width: u16,
+		height: u16,
+	) -> Vec<Line<'a>> {
+		let lines: Vec<&DiffLine> =
+			diff.hunks.iter().flat_map(|hunk| &hunk.lines).collect();
+		let (lineno_width, content_width) =
+			self.side_by_side_widths(width.saturating_sub(2));
+		let scrolled_right = self.horizontal_scroll.get_right();
+		let min = self.vertical_scroll.get_top();
+
+		self.split
+			.rows
+			.iter()
+			.enumerate()
+			.skip(min)
+			.take(usize::from(height) + 1)
+			.map(|(index, row)| {
+				let selected =
+					self.focused() && self.selection.contains(index);
+				let hunk_selected = self.focused()
+					&& self.selected_hunk == Some(row.hunk);
+
+				if let Some(header) =
+					row.old.map(|i| lines[i]).filter(|line| {
+						line.line_type == DiffLineType::Header
+					}) {
+					let line = Self::get_line_to_add(
+						width,
+						header,
+						None,
+						selected,
+						hunk_selected,
+						row.end_of_hunk,
+						&self.theme,
+						scrolled_right,
+					);
+
+					return if self
+						.marked_hunks
+						.contains(&diff.hunks[row.hunk].header_hash)
+					{
+						Self::mark_hunk(line)
+					} else {
+						line
+					};
+				}
+
+				let marker = if row.end_of_hunk {
+					symbols::line::BOTTOM_LEFT
+				} else {
+					symbols::line::VERTICAL
+				};
+
+				let mut spans = vec![Span::styled(
+					Cow::from(marker),
+					self.theme.diff_hunk_marker(hunk_selected),
+				)];
+				spans.extend(Self::get_side_spans(
+					row.old.map(|i| {
+						(
+							lines[i],
+							self.syntax_line(i),
+							lines[i].position.old_lineno,
+						)
+					}),
+					(lineno_width, content_width),
+					selected,
+					&self.theme,
+					scrolled_right,
+				));
+				spans.push(Span::styled(
+					Cow::from(symbols::line::VERTICAL),
+					self.theme.diff_hunk_marker(false),
+				));
+				spans.extend(Self::get_side_spans(
+					row.new.map(|i| {
+						(
+							lines[i],
+							self.syntax_line(i),
+							lines[i].position.new_lineno,
+						)
+					}),
+					(lineno_width, content_width),
+					selected,
+					&self.theme,
+					scrolled_right,
+				));
+				spans.push(Span::raw(Cow::from("\n")));
+
+				Line::from(spans)
+			})
+			.collect()
+	}
+
+	/// one side of a side-by-side row: line number and content,
+	/// padded to the width of the side
+	fn get_side_spans<'a>(
+		line: Option<(&DiffLine, Option<&SyntaxRanges>, Option<u32>)>,
+		(lineno_width, content_width): (usize, usize),
+		selected: bool,
+		theme: &SharedTheme,
+		scrolled_right: usize,
+	) -> Vec<Span<'a>> {
+		let Some((line, syntax, lineno)) = line else {
+			return vec![Span::styled(
+				Cow::from(
+					" ".repeat(lineno_width + 1 + content_width),
+				),
+				theme.text(false, selected),
+			)];
+		};
+
+		let lineno =
+			lineno.map(|n| n.to_string()).unwrap_or_default();
+
+		let mut spans = vec![Span::styled(
+			Cow::from(format!("{lineno:>lineno_width$} ")),
+			theme.text(false, selected),
+		)];
+
+		let (content, used_width) = Self::get_content_spans(
+			line,
+			syntax,
+			selected,
+			theme,
+			scrolled_right,
+			content_width,
+		);
+		spans.extend(content);
+		spans.push(Span::styled(
+			Cow::from(" ".repeat(content_width - used_width)),
+			theme.diff_line(line.line_type, selected),
+		));
+
+		spans
+	}
+
 	fn get_text_binary(&self, diff: &FileDiff) -> Vec<Line> {
 		let is_positive = diff.size_delta >= 0;
 		let delta_byte_size =
@@ -421,9 +812,20 @@ impl DiffComponent {
 		])]
 	}
 
+	/// replaces the hunk marker of a header line with a check mark
+	fn mark_hunk(mut line: Line) -> Line {
+		if let Some(marker) = line.spans.first_mut() {
+			marker.content = Cow::from(strings::symbol::CHECKMARK);
+		}
+
+		line
+	}
+
+	#[allow(clippy::too_many_arguments)]
 	fn get_line_to_add<'a>(
 		width: u16,
 		line: &'a DiffLine,
+		syntax: Option<&SyntaxRanges>,
 		selected: bool,
 		selected_hunk: bool,
 		end_of_hunk: bool,
@@ -450,6 +852,19 @@ impl DiffComponent {
 			}
 		};
 
+		if !line.emphasized.is_empty() || syntax.is_some() {
+			let mut spans = vec![left_side_of_line];
+			spans.extend(Self::get_emphasized_spans(
+				width,
+				line,
+				syntax,
+				selected,
+				theme,
+				scrolled_right,
+			));
+			return Line::from(spans);
+		}
+
 		let content =
 			if !is_content_line && line.content.as_ref().is_empty() {
 				theme.line_break()
@@ -475,6 +890,119 @@ impl DiffComponent {
 		])
 	}
 
+	/// content of a line with its changed words emphasized and its
+	/// syntax highlighted
+	fn get_emphasized_spans<'a>(
+		width: u16,
+		line: &DiffLine,
+		syntax: Option<&SyntaxRanges>,
+		selected: bool,
+		theme: &SharedTheme,
+		scrolled_right: usize,
+	) -> Vec<Span<'a>> {
+		let (mut spans, used_width) = Self::get_content_spans(
+			line,
+			syntax,
+			selected,
+			theme,
+			scrolled_right,
+			usize::MAX,
+		);
+
+		let fill = if selected {
+			(width as usize).saturating_sub(used_width)
+		} else {
+			0
+		};
+		spans.push(Span::styled(
+			Cow::from(format!("{:fill$}\n", "")),
+			theme.diff_line(line.line_type, selected),
+		));
+
+		spans
+	}
+
+	/// content of a line scrolled right and cut at `max_width`, with
+	/// its changed words emphasized and its syntax highlighted, and the
+	/// width it takes up
+	fn get_content_spans<'a>(
+		line: &DiffLine,
+		syntax: Option<&SyntaxRanges>,
+		selected: bool,
+		theme: &SharedTheme,
+		scrolled_right: usize,
+		max_width: usize,
+	) -> (Vec<Span<'a>>, usize) {
+		let content = line.content.as_ref();
+
+		let mut bounds = vec![0, content.len()];
+		for &(start, end) in &line.emphasized {
+			bounds.extend([start, end]);
+		}
+		for (_, range) in syntax.into_iter().flatten() {
+			bounds.extend([range.start, range.end]);
+		}
+		bounds.retain(|bound| *bound <= content.len());
+		bounds.sort_unstable();
+		bounds.dedup();
+
+		let segments = bounds.windows(2).map(|bounds| {
+			let (start, end) = (bounds[0], bounds[1]);
+			let emphasized = line
+				.emphasized
+				.iter()
+				.any(|&(s, e)| s <= start && end <= e);
+			let syntax_style = syntax.and_then(|syntax| {
+				syntax
+					.iter()
+					.find(|(_, range)| {
+						range.start <= start && end <= range.end
+					})
+					.map(|(style, _)| *style)
+			});
+
+			(&content[start..end], emphasized, syntax_style)
+		});
+
+		let mut offset = scrolled_right;
+		let mut used_width = 0;
+		let mut spans = Vec::new();
+
+		for (text, emphasized, syntax_style) in segments {
+			let text = tabs_to_spaces(text.to_string());
+			let text_width = text.width();
+			if offset >= text_width {
+				offset -= text_width;
+				continue;
+			}
+
+			let (text, text_width) = trim_offset(&text, offset)
+				.unicode_truncate(max_width - used_width);
+			offset = 0;
+			used_width += text_width;
+
+			let style = match syntax_style {
+				_ if emphasized => {
+					theme.diff_word(line.line_type, selected)
+				}
+				Some(style) if !selected => {
+					theme.diff_syntax(line.line_type, style)
+				}
+				_ => theme.diff_line(line.line_type, selected),
+			};
+			spans.push(Span::styled(
+				Cow::from(text.to_string()),
+				style,
+			));
+
+			if used_width >= max_width {
+				break;
+			}
+		}
+
+		(spans, used_width)
+	}
+
 	const fn hunk_visible(
 		hunk_min: usize,
 		hunk_max: usize,
@@ -567,24 +1095,21 @@ impl DiffComponent {
 	}
 
 	fn stage_lines(&self) {
-		if let Some(diff) = &self.diff {
-			//TODO: support untracked files as well
-			if !diff.untracked {
-				let selected_lines = self.selected_lines();
-
-				try_or_popup!(
-					self,
-					"(un)stage lines:",
-					sync::stage_lines(
-						&self.repo.borrow(),
-						&self.current.path,
-						self.is_stage(),
-						&selected_lines,
-					)
-				);
+		if self.diff.is_some() {
+			let selected_lines = self.selected_lines();
 
-				self.queue_update();
-			}
+			try_or_popup!(
+				self,
+				"(un)stage lines:",
+				sync::stage_lines(
+					&self.repo.borrow(),
+					&self.current.path,
+					self.is_stage(),
+					&selected_lines,
+				)
+			);
+
+			self.queue_update();
 		}
 	}
 
@@ -600,7 +1125,7 @@ impl DiffComponent {
 						let is_add_or_delete = line.line_type
 							== DiffLineType::Add
 							|| line.line_type == DiffLineType::Delete;
-						if self.selection.contains(i)
+						if self.is_line_selected(i)
 							&& is_add_or_delete
 						{
 							Some(line.position)
@@ -665,12 +1190,16 @@ impl DiffComponent {
 				.take(hunk_index)
 				.fold(0, |sum, hunk| sum + hunk.lines.len());
 			let hunk = &diff.hunks[hunk_index];
-			self.selection = Selection::Single(line_index);
+			let start = self.line_position(line_index);
+			let end = self.line_position(
+				line_index + hunk.lines.len().saturating_sub(1),
+			);
+			self.selection = Selection::Single(start);
 			self.selected_hunk = Some(hunk_index);
 			self.vertical_scroll.move_area_to_visible(
 				self.current_size.get().1 as usize,
-				line_index,
-				line_index.saturating_add(hunk.lines.len()),
+				start,
+				end.saturating_add(1),
 			);
 		}
 	}
@@ -696,10 +1225,14 @@ impl DrawableComponent for DiffComponent {
 			usize::from(current_height),
 		);
 
-		self.horizontal_scroll.update_no_selection(
-			self.longest_line,
-			current_width.into(),
-		);
+		let visual_width = if self.is_side_by_side() {
+			self.side_by_side_widths(current_width).1
+		} else {
+			current_width.into()
+		};
+
+		self.horizontal_scroll
+			.update_no_selection(self.longest_line, visual_width);
 
 		let title = format!(
 			"{}{}",
@@ -762,6 +1295,13 @@ impl Component for DiffComponent {
 			self.calc_hunk_move_target(-1) != self.selected_hunk,
 			self.focused(),
 		));
+		out.push(CommandInfo::new(
+			strings::commands::diff_toggle_side_by_side(
+				&self.key_config,
+			),
+			self.diff.is_some(),
+			self.focused(),
+		));
 		out.push(
 			CommandInfo::new(
 				strings::commands::diff_home_end(&self.key_config),
@@ -878,6 +1418,12 @@ impl Component for DiffComponent {
 				) {
 					self.diff_hunk_move_up_down(-1);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_toggle_side_by_side,
+				) {
+					self.toggle_side_by_side();
+					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
 					self.key_config.keys.stage_unstage_item,
@@ -948,16 +1494,112 @@ impl Component for DiffComponent {
 mod tests {
 	use super::*;
 	use crate::ui::style::Theme;
+	use asyncgit::sync::diff::Hunk;
 	use std::io::Write;
 	use std::rc::Rc;
 	use tempfile::NamedTempFile;
 
+	#[test]
+	fn test_split_view() {
+		let line = |line_type| DiffLine {
+			line_type,
+			..DiffLine::default()
+		};
+		let diff = FileDiff {
+			hunks: vec![Hunk {
+				header_hash: 0,
+				lines: vec![
+					line(DiffLineType::Header),
+					line(DiffLineType::Delete),
+					line(DiffLineType::Delete),
+					line(DiffLineType::Add),
+					line(DiffLineType::None),
+					line(DiffLineType::Add),
+				],
+			}],
+			lines: 6,
+			..FileDiff::default()
+		};
+
+		let view = SplitView::new(&diff);
+
+		let rows: Vec<_> =
+			view.rows.iter().map(|row| (row.old, row.new)).collect();
+		assert_eq!(
+			rows,
+			vec![
+				(Some(0), Some(0)),
+				(Some(1), Some(3)),
+				(Some(2), None),
+				(Some(4), Some(4)),
+				(None, Some(5)),
+			]
+		);
+		assert_eq!(view.row_of_line, vec![0, 1, 2, 1, 3, 4]);
+		assert!(view.rows[4].end_of_hunk);
+	}
+
+	#[test]
+	fn test_content_spans() {
+		use ratatui::style::{Color, Style};
+
+		let theme = Rc::new(Theme::default());
+		let line = DiffLine {
+			content: "let a = 1;".into(),
+			line_type: DiffLineType::Add,
+			emphasized: vec![(4, 5)],
+			..DiffLine::default()
+		};
+		let keyword = Style::default().fg(Color::Rgb(1, 2, 3));
+		let syntax: SyntaxRanges =
+			vec![(keyword, 0..3), (Style::default(), 3..10)];
+
+		let (spans, width) = DiffComponent::get_content_spans(
+			&line,
+			Some(&syntax),
+			false,
+			&theme,
+			0,
+			usize::MAX,
+		);
+
+		assert_eq!(width, 10);
+		assert_eq!(
+			spans,
+			vec![
+				Span::styled(
+					"let",
+					theme.diff_syntax(DiffLineType::Add, keyword)
+				),
+				Span::styled(
+					" ",
+					theme.diff_syntax(
+						DiffLineType::Add,
+						Style::default()
+					)
+				),
+				Span::styled(
+					"a",
+					theme.diff_word(DiffLineType::Add, false)
+				),
+				Span::styled(
+					" = 1;",
+					theme.diff_syntax(
+						DiffLineType::Add,
+						Style::default()
+					)
+				),
+			]
+		);
+	}
+
 	#[test]
 	fn test_line_break() {
 		let diff_line = DiffLine {
 			content: "".into(),
 			line_type: DiffLineType::Add,
 			position: Default::default(),
+			emphasized: Vec::new(),
 		};
 
 		{
@@ -967,6 +1609,7 @@ mod tests {
 				DiffComponent::get_line_to_add(
 					4,
 					&diff_line,
+					None,
 					false,
 					false,
 					false,
@@ -1002,7 +1645,8 @@ mod tests {
 
 			assert_eq!(
 				DiffComponent::get_line_to_add(
-					4, &diff_line, false, false, false, &theme, 0
+					4, &diff_line, None, false, false, false, &theme,
+					0
 				)
 				.spans
 				.last()
@@ -2,6 +2,7 @@ mod changes;
 mod command;
 mod commit_details;
 mod commitlist;
+mod conflict;
 mod cred;
 mod diff;
 mod revision_files;
@@ -15,6 +16,7 @@ pub use changes::ChangesComponent;
 pub use command::{CommandInfo, CommandText};
 pub use commit_details::CommitDetailsComponent;
 pub use commitlist::CommitList;
+pub use conflict::ConflictComponent;
 pub use cred::CredComponent;
 pub use diff::DiffComponent;
 pub use revision_files::RevisionFilesComponent;
@@ -18,7 +18,9 @@ use anyhow::Result;
 use asyncgit::{hash, sync::CommitId, StatusItem, StatusItemType};
 use crossterm::event::Event;
 use ratatui::{layout::Rect, text::Span, Frame};
-use std::{borrow::Cow, cell::Cell, path::Path};
+use std::{
+	borrow::Cow, cell::Cell, collections::HashSet, path::Path,
+};
 
 //TODO: use new `filetreelist` crate
 
@@ -37,6 +39,8 @@ pub struct StatusTreeComponent {
 	scroll_top: Cell<usize>,
 	visible: bool,
 	revision: Option<CommitId>,
+	marking: bool,
+	marked: HashSet<String>,
 }
 
 impl StatusTreeComponent {
@@ -55,9 +59,16 @@ impl StatusTreeComponent {
 			pending: true,
 			visible: false,
 			revision: None,
+			marking: false,
+			marked: HashSet::new(),
 		}
 	}
 
+	/// allow marking files and folders (see `marked_files`)
+	pub fn enable_marking(&mut self) {
+		self.marking = true;
+	}
+
 	pub fn set_commit(&mut self, revision: Option<CommitId>) {
 		self.revision = revision;
 	}
@@ -70,6 +81,12 @@ impl StatusTreeComponent {
 		if self.current_hash != new_hash {
 			self.tree.update(list)?;
 			self.current_hash = new_hash;
+
+			let paths = list
+				.iter()
+				.map(|item| item.path.as_str())
+				.collect::<HashSet<_>>();
+			self.marked.retain(|path| paths.contains(path.as_str()));
 		}
 
 		Ok(())
@@ -91,6 +108,61 @@ impl StatusTreeComponent {
 		})
 	}
 
+	/// paths of the marked files in tree order
+	pub fn marked_files(&self) -> Vec<String> {
+		self.tree
+			.tree
+			.items()
+			.iter()
+			.filter_map(|item| match &item.kind {
+				FileTreeItemKind::File(file)
+					if self.marked.contains(&file.path) =>
+				{
+					Some(file.path.clone())
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// marks the selected file or all files in the selected folder,
+	/// unmarks them if they are all marked already
+	fn toggle_mark(&mut self) {
+		let Some(selection) = self.selection() else {
+			return;
+		};
+
+		let files = match &selection.kind {
+			FileTreeItemKind::File(file) => vec![file.path.clone()],
+			FileTreeItemKind::Path(_) => {
+				let folder = Path::new(&selection.info.full_path);
+
+				self.tree
+					.tree
+					.items()
+					.iter()
+					.filter_map(|item| match &item.kind {
+						FileTreeItemKind::File(file)
+							if Path::new(&file.path)
+								.starts_with(folder) =>
+						{
+							Some(file.path.clone())
+						}
+						_ => None,
+					})
+					.collect()
+			}
+		};
+
+		if files.iter().all(|file| self.marked.contains(file)) {
+			for file in &files {
+				self.marked.remove(file);
+			}
+		} else {
+			self.marked.extend(files);
+		}
+	}
+
 	///
 	pub fn show_selection(&mut self, show: bool) {
 		self.show_selection = show;
@@ -296,6 +368,27 @@ impl StatusTreeComponent {
 		)
 	}
 
+	fn add_mark<'b>(
+		&self,
+		span: Span<'b>,
+		draw_text_info: &TextDrawInfo,
+	) -> Span<'b> {
+		if !self.marking {
+			return span;
+		}
+
+		let mark = match draw_text_info.item_kind {
+			FileTreeItemKind::File(file)
+				if self.marked.contains(&file.path) =>
+			{
+				strings::symbol::CHECKMARK
+			}
+			_ => strings::symbol::EMPTY_SPACE,
+		};
+
+		Span::styled(format!("{mark}{}", span.content), span.style)
+	}
+
 	// Copy the real path of selected file to clickboard
 	fn copy_file_path(&self) {
 		if let Some(item) = self.selection() {
@@ -371,6 +464,7 @@ impl DrawableComponent for StatusTreeComponent {
 						self.show_selection && select == index,
 						&self.theme,
 					)
+					.map(|span| self.add_mark(span, draw_text_info))
 				})
 				.skip(self.scroll_top.get());
 
@@ -403,6 +497,16 @@ impl Component for StatusTreeComponent {
 			.order(order::NAV),
 		);
 
+		if self.marking {
+			out.push(CommandInfo::new(
+				strings::commands::stashing_mark_item(
+					&self.key_config,
+				),
+				!self.is_empty(),
+				self.focused || force_all,
+			));
+		}
+
 		out.push(
 			CommandInfo::new(
 				strings::commands::blame_file(&self.key_config),
@@ -487,6 +591,13 @@ impl Component for StatusTreeComponent {
 				} else if key_match(e, self.key_config.keys.copy) {
 					self.copy_file_path();
 					Ok(EventState::Consumed)
+				} else if self.marking
+					&& key_match(
+						e,
+						self.key_config.keys.stashing_mark_item,
+					) {
+					self.toggle_mark();
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.move_down)
 				{
 					Ok(self
@@ -69,7 +69,11 @@ pub struct KeysList {
 	pub enter: GituiKeyEvent,
 	pub blame: GituiKeyEvent,
 	pub file_history: GituiKeyEvent,
+	pub blame_parent: GituiKeyEvent,
+	pub blame_ignore_commit: GituiKeyEvent,
+	pub blame_clear_ignored: GituiKeyEvent,
 	pub edit_file: GituiKeyEvent,
+	pub external_tool: GituiKeyEvent,
 	pub status_stage_all: GituiKeyEvent,
 	pub status_reset_item: GituiKeyEvent,
 	pub status_ignore_file: GituiKeyEvent,
@@ -78,15 +82,32 @@ pub struct KeysList {
 	pub stashing_save: GituiKeyEvent,
 	pub stashing_toggle_untracked: GituiKeyEvent,
 	pub stashing_toggle_index: GituiKeyEvent,
+	pub stashing_mark_item: GituiKeyEvent,
 	pub stash_apply: GituiKeyEvent,
 	pub stash_open: GituiKeyEvent,
 	pub stash_drop: GituiKeyEvent,
+	pub stash_branch: GituiKeyEvent,
+	pub stash_export: GituiKeyEvent,
 	pub cmd_bar_toggle: GituiKeyEvent,
 	pub log_tag_commit: GituiKeyEvent,
 	pub log_mark_commit: GituiKeyEvent,
 	pub log_checkout_commit: GituiKeyEvent,
 	pub log_reset_commit: GituiKeyEvent,
 	pub log_reword_commit: GituiKeyEvent,
+	pub log_rebase_interactive: GituiKeyEvent,
+	pub log_split_commit: GituiKeyEvent,
+	pub log_fixup_commit: GituiKeyEvent,
+	pub log_squash_commit: GituiKeyEvent,
+	pub log_amend_commit: GituiKeyEvent,
+	pub log_autosquash: GituiKeyEvent,
+	pub log_toggle_refs: GituiKeyEvent,
+	pub log_revspec: GituiKeyEvent,
+	pub log_toggle_first_parent: GituiKeyEvent,
+	pub log_toggle_no_merges: GituiKeyEvent,
+	pub log_export_patch: GituiKeyEvent,
+	pub log_apply_patch: GituiKeyEvent,
+	pub apply_patch_toggle_mode: GituiKeyEvent,
+	pub open_reflog: GituiKeyEvent,
 	pub log_find: GituiKeyEvent,
 	pub find_commit_sha: GituiKeyEvent,
 	pub commit_amend: GituiKeyEvent,
@@ -99,6 +120,7 @@ pub struct KeysList {
 	pub delete_branch: GituiKeyEvent,
 	pub merge_branch: GituiKeyEvent,
 	pub rebase_branch: GituiKeyEvent,
+	pub cherry_pick: GituiKeyEvent,
 	pub reset_branch: GituiKeyEvent,
 	pub compare_commits: GituiKeyEvent,
 	pub tags: GituiKeyEvent,
@@ -108,6 +130,7 @@ pub struct KeysList {
 	pub open_file_tree: GituiKeyEvent,
 	pub file_find: GituiKeyEvent,
 	pub branch_find: GituiKeyEvent,
+	pub branch_toggle_log: GituiKeyEvent,
 	pub force_push: GituiKeyEvent,
 	pub fetch: GituiKeyEvent,
 	pub pull: GituiKeyEvent,
@@ -115,18 +138,38 @@ pub struct KeysList {
 	pub undo_commit: GituiKeyEvent,
 	pub diff_hunk_next: GituiKeyEvent,
 	pub diff_hunk_prev: GituiKeyEvent,
+	pub diff_toggle_side_by_side: GituiKeyEvent,
 	pub stage_unstage_item: GituiKeyEvent,
 	pub tag_annotate: GituiKeyEvent,
 	pub view_submodules: GituiKeyEvent,
 	pub view_submodule_parent: GituiKeyEvent,
 	pub update_submodule: GituiKeyEvent,
+	pub view_worktrees: GituiKeyEvent,
+	pub open_absorb: GituiKeyEvent,
+	pub worktree_add: GituiKeyEvent,
+	pub worktree_lock: GituiKeyEvent,
+	pub worktree_prune: GituiKeyEvent,
+	pub worktree_remove: GituiKeyEvent,
 	pub commit_history_next: GituiKeyEvent,
 	pub commit: GituiKeyEvent,
 	pub newline: GituiKeyEvent,
+	pub rebase_pick: GituiKeyEvent,
+	pub rebase_reword: GituiKeyEvent,
+	pub rebase_edit: GituiKeyEvent,
+	pub rebase_squash: GituiKeyEvent,
+	pub rebase_fixup: GituiKeyEvent,
+	pub rebase_drop: GituiKeyEvent,
+	pub conflict_pick_ours: GituiKeyEvent,
+	pub conflict_pick_theirs: GituiKeyEvent,
+	pub conflict_pick_both: GituiKeyEvent,
+	pub conflict_pick_ours_file: GituiKeyEvent,
+	pub conflict_pick_theirs_file: GituiKeyEvent,
+	pub conflict_pick_both_file: GituiKeyEvent,
 }
 
 #[rustfmt::skip]
 impl Default for KeysList {
+	#[allow(clippy::too_many_lines)]
 	fn default() -> Self {
 		Self {
 			tab_status: GituiKeyEvent::new(KeyCode::Char('1'), KeyModifiers::empty()),
@@ -161,7 +204,11 @@ impl Default for KeysList {
 			enter: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			blame: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
 			file_history: GituiKeyEvent::new(KeyCode::Char('H'),  KeyModifiers::SHIFT),
+			blame_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			blame_ignore_commit: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			blame_clear_ignored: GituiKeyEvent::new(KeyCode::Char('I'),  KeyModifiers::SHIFT),
 			edit_file: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			external_tool: GituiKeyEvent::new(KeyCode::Char('E'),  KeyModifiers::SHIFT),
 			status_stage_all: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			status_reset_item: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			diff_reset_lines: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
@@ -170,15 +217,32 @@ impl Default for KeysList {
 			stashing_save: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
 			stashing_toggle_index: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			stashing_mark_item: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
 			stash_apply: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			stash_open: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			stash_drop: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			stash_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			stash_export: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
 			cmd_bar_toggle: GituiKeyEvent::new(KeyCode::Char('.'),  KeyModifiers::empty()),
 			log_tag_commit: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
 			log_mark_commit: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
 			log_checkout_commit: GituiKeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT },
 			log_reset_commit: GituiKeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT },
 			log_reword_commit: GituiKeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty() },
+			log_rebase_interactive: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			log_split_commit: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
+			log_fixup_commit: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			log_squash_commit: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
+			log_amend_commit: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			log_autosquash: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
+			log_toggle_refs: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
+			log_revspec: GituiKeyEvent::new(KeyCode::Char('V'),  KeyModifiers::SHIFT),
+			log_toggle_first_parent: GituiKeyEvent::new(KeyCode::Char('M'),  KeyModifiers::SHIFT),
+			log_toggle_no_merges: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
+			log_export_patch: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
+			log_apply_patch: GituiKeyEvent::new(KeyCode::Char('I'),  KeyModifiers::SHIFT),
+			apply_patch_toggle_mode: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::CONTROL),
+			open_reflog: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
 			log_find: GituiKeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty() },
 			find_commit_sha: GituiKeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
 			commit_amend: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
@@ -191,6 +255,7 @@ impl Default for KeysList {
 			delete_branch: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			merge_branch: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
 			rebase_branch: GituiKeyEvent::new(KeyCode::Char('R'),  KeyModifiers::SHIFT),
+			cherry_pick: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
 			reset_branch: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			compare_commits: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
 			tags: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
@@ -205,16 +270,36 @@ impl Default for KeysList {
 			open_file_tree: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
 			file_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			branch_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			branch_toggle_log: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
 			diff_hunk_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
 			diff_hunk_prev: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			diff_toggle_side_by_side: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
 			stage_unstage_item: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			tag_annotate: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
 			view_submodules: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
 			view_submodule_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			update_submodule: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			view_worktrees: GituiKeyEvent::new(KeyCode::Char('W'),  KeyModifiers::SHIFT),
+			open_absorb: GituiKeyEvent::new(KeyCode::Char('z'),  KeyModifiers::empty()),
+			worktree_add: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
+			worktree_lock: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::empty()),
+			worktree_prune: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			worktree_remove: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			commit_history_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::CONTROL),
 			commit: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::CONTROL),
 			newline: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
+			rebase_pick: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			rebase_reword: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			rebase_edit: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			rebase_squash: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			rebase_fixup: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			rebase_drop: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			conflict_pick_ours: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			conflict_pick_theirs: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			conflict_pick_both: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			conflict_pick_ours_file: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
+			conflict_pick_theirs_file: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
+			conflict_pick_both_file: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
 		}
 	}
 }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use asyncgit::sync::{
-	diff::DiffOptions, repo_dir, RepoPathRef,
+	diff::{DiffAlgorithm, DiffOptions},
+	repo_dir, LogWalkerOptions, RepoPathRef,
 	ShowUntrackedFilesConfig,
 };
 use ron::{
@@ -22,6 +23,10 @@ struct OptionsData {
 	pub diff: DiffOptions,
 	pub status_show_untracked: Option<ShowUntrackedFilesConfig>,
 	pub commit_msgs: Vec<String>,
+	#[serde(default)]
+	pub log: LogWalkerOptions,
+	#[serde(default)]
+	pub diff_side_by_side: bool,
 }
 
 const COMMIT_MSG_HISTORY_LENGTH: usize = 20;
@@ -107,6 +112,67 @@ impl Options {
 		self.save();
 	}
 
+	pub fn diff_toggle_whitespace_change(&mut self) {
+		self.data.diff.ignore_whitespace_change =
+			!self.data.diff.ignore_whitespace_change;
+
+		self.save();
+	}
+
+	pub fn diff_toggle_whitespace_eol(&mut self) {
+		self.data.diff.ignore_whitespace_eol =
+			!self.data.diff.ignore_whitespace_eol;
+
+		self.save();
+	}
+
+	pub fn diff_toggle_blank_lines(&mut self) {
+		self.data.diff.ignore_blank_lines =
+			!self.data.diff.ignore_blank_lines;
+
+		self.save();
+	}
+
+	pub fn diff_algorithm_change(&mut self, next: bool) {
+		self.data.diff.algorithm =
+			match (self.data.diff.algorithm, next) {
+				(DiffAlgorithm::Myers, true)
+				| (DiffAlgorithm::Patience, false) => DiffAlgorithm::Minimal,
+				(DiffAlgorithm::Minimal, true)
+				| (DiffAlgorithm::Myers, false) => DiffAlgorithm::Patience,
+				(DiffAlgorithm::Patience, true)
+				| (DiffAlgorithm::Minimal, false) => DiffAlgorithm::Myers,
+			};
+
+		self.save();
+	}
+
+	pub const fn diff_side_by_side(&self) -> bool {
+		self.data.diff_side_by_side
+	}
+
+	pub fn diff_toggle_side_by_side(&mut self) {
+		self.data.diff_side_by_side = !self.data.diff_side_by_side;
+
+		self.save();
+	}
+
+	pub const fn log_options(&self) -> LogWalkerOptions {
+		self.data.log
+	}
+
+	pub fn log_toggle_first_parent(&mut self) {
+		self.data.log.first_parent = !self.data.log.first_parent;
+
+		self.save();
+	}
+
+	pub fn log_toggle_no_merges(&mut self) {
+		self.data.log.no_merges = !self.data.log.no_merges;
+
+		self.save();
+	}
+
 	pub fn add_commit_msg(&mut self, msg: &str) {
 		self.data.commit_msgs.push(msg.to_owned());
 		while self.data.commit_msgs.len() > COMMIT_MSG_HISTORY_LENGTH
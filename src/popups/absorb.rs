@@ -0,0 +1,336 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, ScrollType, VerticalScroll,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, AbsorbHunk, AbsorbPlan, AbsorbTarget, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::cell::Cell;
+use unicode_truncate::UnicodeTruncateStr;
+
+/// previews which commits the staged hunks get absorbed into
+pub struct AbsorbPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	plan: Option<AbsorbPlan>,
+	visible: bool,
+	current_height: Cell<u16>,
+	selection: usize,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for AbsorbPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+
+			f.render_widget(
+				Block::default()
+					.title(strings::absorb_title(
+						self.absorbable(),
+						self.hunks().len(),
+					))
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			self.draw_list(f, area)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for AbsorbPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			let absorbable = self.absorbable() > 0;
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::absorb_confirm(&self.key_config),
+				absorbable,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::absorb_autosquash(
+					&self.key_config,
+				),
+				absorbable,
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if key_match(e, self.key_config.keys.move_down) {
+				self.move_selection(ScrollType::Down);
+			} else if key_match(e, self.key_config.keys.move_up) {
+				self.move_selection(ScrollType::Up);
+			} else if key_match(e, self.key_config.keys.page_down) {
+				self.move_selection(ScrollType::PageDown);
+			} else if key_match(e, self.key_config.keys.page_up) {
+				self.move_selection(ScrollType::PageUp);
+			} else if key_match(e, self.key_config.keys.home) {
+				self.move_selection(ScrollType::Home);
+			} else if key_match(e, self.key_config.keys.end) {
+				self.move_selection(ScrollType::End);
+			} else if key_match(e, self.key_config.keys.enter) {
+				self.absorb(false);
+			} else if key_match(
+				e,
+				self.key_config.keys.log_autosquash,
+			) {
+				self.absorb(true);
+			} else if key_match(
+				e,
+				self.key_config.keys.cmd_bar_toggle,
+			) {
+				//do not consume if its the more key
+				return Ok(EventState::NotConsumed);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl AbsorbPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			plan: None,
+			visible: false,
+			current_height: Cell::new(0),
+			selection: 0,
+			scroll: VerticalScroll::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// open with the targets of the currently staged hunks
+	pub fn open(&mut self) -> Result<()> {
+		self.plan = Some(sync::absorb_plan(&self.repo.borrow())?);
+		self.selection = 0;
+
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn hunks(&self) -> &[AbsorbHunk] {
+		self.plan.as_ref().map_or(&[], |plan| plan.hunks.as_slice())
+	}
+
+	fn absorbable(&self) -> usize {
+		self.plan.as_ref().map_or(0, AbsorbPlan::absorbable)
+	}
+
+	fn absorb(&mut self, autosquash: bool) {
+		let Some(plan) = self.plan.take() else {
+			return;
+		};
+
+		if plan.absorbable() == 0 {
+			self.plan = Some(plan);
+			return;
+		}
+
+		self.hide();
+
+		try_or_popup!(
+			self,
+			"absorb error:",
+			sync::absorb(&self.repo.borrow(), &plan)
+		);
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		if autosquash {
+			try_or_popup!(
+				self,
+				"autosquash error:",
+				sync::autosquash(&self.repo.borrow()).map(|state| {
+					self.queue
+						.push(InternalEvent::RebaseProgressed(state));
+				})
+			);
+		}
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let height = usize::from(self.current_height.get());
+		let max = self.hunks().len().saturating_sub(1);
+
+		self.selection = match scroll {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			ScrollType::PageUp => {
+				self.selection.saturating_sub(height)
+			}
+			ScrollType::PageDown => {
+				self.selection.saturating_add(height)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max,
+		}
+		.min(max);
+	}
+
+	fn target_span(
+		&self,
+		target: &AbsorbTarget,
+		selected: bool,
+	) -> Span<'static> {
+		match target {
+			AbsorbTarget::Commit(id, summary) => Span::styled(
+				format!("{} {summary}", id.get_short_string()),
+				self.theme.commit_hash(selected),
+			),
+			AbsorbTarget::Ambiguous => Span::styled(
+				strings::absorb_target_ambiguous(),
+				self.theme.text_danger(),
+			),
+			AbsorbTarget::Pushed(id) => Span::styled(
+				strings::absorb_target_pushed(*id),
+				self.theme.text_danger(),
+			),
+			AbsorbTarget::NotFound => Span::styled(
+				strings::absorb_target_none(),
+				self.theme.text(false, selected),
+			),
+		}
+	}
+
+	fn get_text(&self, width: usize, height: usize) -> Text {
+		let hunk_width = width / 2;
+
+		let lines = self
+			.hunks()
+			.iter()
+			.enumerate()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.map(|(idx, hunk)| {
+				let selected = idx == self.selection;
+
+				let location =
+					format!("{} {}", hunk.path, hunk.header);
+				let (location, _) =
+					location.unicode_truncate(hunk_width);
+
+				Line::from(vec![
+					Span::styled(
+						format!("{location:hunk_width$} → "),
+						self.theme.text(true, selected),
+					),
+					self.target_span(&hunk.target, selected),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		Text::from(lines)
+	}
+
+	fn draw_list(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection,
+			self.hunks().len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(
+				self.get_text(r.width as usize, height_in_lines),
+			)
+			.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,169 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, PatchApplyMode, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+use std::path::Path;
+
+/// input of a patch or mbox file to apply onto `HEAD`
+pub struct ApplyPatchPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	mode: PatchApplyMode,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ApplyPatchPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+		}
+		Ok(())
+	}
+}
+
+impl Component for ApplyPatchPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::apply_patch_confirm(
+					&self.key_config,
+				),
+				!self.input.get_text().trim().is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::apply_patch_toggle_mode(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(
+					e,
+					self.key_config.keys.apply_patch_toggle_mode,
+				) {
+					self.toggle_mode();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl ApplyPatchPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::apply_patch_popup_title(false),
+				&strings::apply_patch_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			mode: PatchApplyMode::WorkDir,
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.input.clear();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn toggle_mode(&mut self) {
+		self.mode = match self.mode {
+			PatchApplyMode::WorkDir => PatchApplyMode::Commits,
+			PatchApplyMode::Commits => PatchApplyMode::WorkDir,
+		};
+
+		self.input.set_title(strings::apply_patch_popup_title(
+			self.mode == PatchApplyMode::Commits,
+		));
+	}
+
+	/// paths are relative to the working directory
+	fn confirm(&mut self) {
+		let path = self.input.get_text().trim().to_string();
+
+		if path.is_empty() {
+			return;
+		}
+
+		let result = sync::apply_patch_file(
+			&self.repo.borrow(),
+			Path::new(&path),
+			self.mode,
+		);
+
+		match result {
+			Ok(_) => {
+				self.hide();
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("apply patch error:\n{e}"),
+				));
+			}
+		}
+	}
+}
@@ -16,7 +16,7 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
-	sync::{BlameHunk, CommitId, FileBlame, RepoPathRef},
+	sync::{self, BlameHunk, CommitId, FileBlame, RepoPathRef},
 	AsyncBlame, AsyncGitNotification, BlameParams,
 };
 use crossbeam_channel::Sender;
@@ -89,6 +89,10 @@ pub struct BlameFilePopup {
 	queue: Queue,
 	visible: bool,
 	open_request: Option<BlameFileOpen>,
+	/// blames navigated away from with `blame_parent`
+	back_stack: Vec<BlameFileOpen>,
+	/// commits ignored in this session on top of `blame.ignoreRevsFile`
+	ignored_commits: Vec<CommitId>,
 	params: Option<BlameParams>,
 	table_state: std::cell::Cell<TableState>,
 	key_config: SharedKeyConfig,
@@ -235,6 +239,42 @@ impl Component for BlameFilePopup {
 				)
 				.order(1),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_parent(&self.key_config),
+					self.selected_commit().is_some(),
+					has_result,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_back(&self.key_config),
+					!self.back_stack.is_empty(),
+					has_result,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_ignore_commit(
+						&self.key_config,
+					),
+					self.selected_commit().is_some(),
+					has_result,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_clear_ignored(
+						&self.key_config,
+					),
+					!self.ignored_commits.is_empty(),
+					has_result,
+				)
+				.order(1),
+			);
 		}
 
 		visibility_blocking(self)
@@ -308,6 +348,36 @@ impl Component for BlameFilePopup {
 							),
 						));
 					}
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_parent,
+				) {
+					self.blame_parent()?;
+				} else if key_match(
+					key,
+					self.key_config.keys.move_left,
+				) {
+					if let Some(previous) = self.back_stack.pop() {
+						self.open_blame(previous)?;
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_ignore_commit,
+				) {
+					if let Some(commit_id) = self.selected_commit() {
+						if !self.ignored_commits.contains(&commit_id)
+						{
+							self.ignored_commits.push(commit_id);
+						}
+						self.reblame()?;
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_clear_ignored,
+				) && !self.ignored_commits.is_empty()
+				{
+					self.ignored_commits.clear();
+					self.reblame()?;
 				}
 
 				return Ok(EventState::Consumed);
@@ -338,6 +408,8 @@ impl BlameFilePopup {
 			visible: false,
 			params: None,
 			open_request: None,
+			back_stack: Vec::new(),
+			ignored_commits: Vec::new(),
 			table_state: std::cell::Cell::new(TableState::default()),
 			key_config: env.key_config.clone(),
 			current_height: std::cell::Cell::new(0),
@@ -367,10 +439,21 @@ impl BlameFilePopup {
 
 	///
 	pub fn open(&mut self, open: BlameFileOpen) -> Result<()> {
+		self.back_stack.clear();
+
+		self.open_blame(open)
+	}
+
+	fn open_blame(&mut self, open: BlameFileOpen) -> Result<()> {
+		let mut ignore_revs =
+			sync::blame_ignore_revs(&self.repo.borrow())?;
+		ignore_revs.extend(self.ignored_commits.iter().copied());
+
 		self.open_request = Some(open.clone());
 		self.params = Some(BlameParams {
 			file_path: open.file_path,
 			commit_id: open.commit_id,
+			ignore_revs,
 		});
 		self.blame =
 			Some(BlameProcess::GettingBlame(AsyncBlame::new(
@@ -384,6 +467,72 @@ impl BlameFilePopup {
 		Ok(())
 	}
 
+	/// blame at the parent of the selected line's commit, keeping
+	/// the current blame on the back-stack
+	fn blame_parent(&mut self) -> Result<()> {
+		let (Some(hunk), Some(selection), Some(request)) = (
+			self.selected_hunk(),
+			self.get_selection(),
+			self.open_request.clone(),
+		) else {
+			return Ok(());
+		};
+
+		// the line as it is in the version of the hunk's commit
+		let line = hunk.orig_start_line
+			+ selection.saturating_sub(hunk.start_line);
+
+		let parent = sync::blame_parent(
+			&self.repo.borrow(),
+			hunk.commit_id,
+			&hunk.orig_path,
+			line,
+		);
+
+		match parent {
+			Ok(Some((parent, file_path, line))) => {
+				self.back_stack.push(BlameFileOpen {
+					selection: Some(selection),
+					..request
+				});
+				self.open_blame(BlameFileOpen {
+					file_path,
+					commit_id: Some(parent),
+					selection: Some(line),
+				})?;
+			}
+			Ok(None) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"no version of {} before commit {}",
+						hunk.orig_path,
+						hunk.commit_id.get_short_string()
+					),
+				));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("blame parent error:\n{e}"),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// blame the current file again, e.g. after the ignored commits
+	/// changed
+	fn reblame(&mut self) -> Result<()> {
+		if let Some(request) = self.open_request.clone() {
+			self.open_blame(BlameFileOpen {
+				selection: self.get_selection(),
+				..request
+			})?;
+		}
+
+		Ok(())
+	}
+
 	///
 	pub const fn any_work_pending(&self) -> bool {
 		self.blame.is_some()
@@ -508,8 +657,17 @@ impl BlameFilePopup {
 				)
 			}
 			(false, Some(params), Some(file_blame)) => {
+				let ignored = if params.ignore_revs.is_empty() {
+					String::new()
+				} else {
+					format!(
+						" ({} commits ignored)",
+						params.ignore_revs.len()
+					)
+				};
+
 				format!(
-					"{} -- {} -- {}",
+					"{} -- {} -- {}{ignored}",
 					self.title,
 					params.file_path,
 					file_blame.commit_id().get_short_string()
@@ -743,23 +901,24 @@ impl BlameFilePopup {
 	}
 
 	fn selected_commit(&self) -> Option<CommitId> {
+		self.selected_hunk().map(|hunk| hunk.commit_id)
+	}
+
+	fn selected_hunk(&self) -> Option<BlameHunk> {
 		self.blame
 			.as_ref()
 			.and_then(|blame| blame.result())
 			.and_then(|file_blame| {
 				let table_state = self.table_state.take();
 
-				let commit_id =
+				let hunk =
 					table_state.selected().and_then(|selected| {
-						file_blame.lines()[selected]
-							.0
-							.as_ref()
-							.map(|hunk| hunk.commit_id)
+						file_blame.lines()[selected].0.clone()
 					});
 
 				self.table_state.set(table_state);
 
-				commit_id
+				hunk
 			})
 	}
 }
@@ -44,6 +44,7 @@ use super::InspectCommitOpen;
 pub struct BranchListPopup {
 	repo: RepoPathRef,
 	branches: Vec<BranchInfo>,
+	log_refs: Vec<String>,
 	local: bool,
 	has_remotes: bool,
 	visible: bool,
@@ -102,6 +103,7 @@ impl DrawableComponent for BranchListPopup {
 }
 
 impl Component for BranchListPopup {
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -192,6 +194,14 @@ impl Component for BranchListPopup {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::branch_popup_cherry_pick(
+					&self.key_config,
+				),
+				!selection_is_cur_branch,
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::rename_branch_popup(
 					&self.key_config,
@@ -217,12 +227,20 @@ impl Component for BranchListPopup {
 				self.valid_selection(),
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::branch_popup_toggle_log(
+					&self.key_config,
+				),
+				self.valid_selection(),
+				true,
+			));
 		}
 		visibility_blocking(self)
 	}
 
 	//TODO: cleanup
-	#[allow(clippy::cognitive_complexity)]
+	#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if !self.visible {
 			return Ok(EventState::NotConsumed);
@@ -273,6 +291,15 @@ impl Component for BranchListPopup {
 					"rebase error:",
 					self.rebase_branch()
 				);
+			} else if key_match(e, self.key_config.keys.cherry_pick)
+				&& !selection_is_cur_branch
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"cherry-pick error:",
+					self.cherry_pick()
+				);
 			} else if key_match(e, self.key_config.keys.move_right)
 				&& self.valid_selection()
 			{
@@ -301,6 +328,12 @@ impl Component for BranchListPopup {
 						commit_id,
 					));
 				}
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_toggle_log,
+			) && self.valid_selection()
+			{
+				self.toggle_log_ref();
 			} else if key_match(
 				e,
 				self.key_config.keys.cmd_bar_toggle,
@@ -342,6 +375,7 @@ impl BranchListPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			branches: Vec::new(),
+			log_refs: Vec::new(),
 			local: true,
 			has_remotes: false,
 			visible: false,
@@ -390,8 +424,9 @@ impl BranchListPopup {
 		Ok(EventState::NotConsumed)
 	}
 
-	///
-	pub fn open(&mut self) -> Result<()> {
+	/// `log_refs` are the references currently shown in the log
+	pub fn open(&mut self, log_refs: Vec<String>) -> Result<()> {
+		self.log_refs = log_refs;
 		self.show()?;
 		self.update_branches()?;
 
@@ -478,6 +513,16 @@ impl BranchListPopup {
 		Ok(())
 	}
 
+	fn cherry_pick(&mut self) -> Result<()> {
+		if let Some(commit_id) = self.get_selected_commit() {
+			sync::cherry_pick(&self.repo.borrow(), &[commit_id])?;
+
+			self.hide_and_switch_tab()?;
+		}
+
+		Ok(())
+	}
+
 	fn inspect_head_of_branch(&mut self) {
 		if let Some(commit_id) = self.get_selected_commit() {
 			self.hide();
@@ -489,6 +534,24 @@ impl BranchListPopup {
 		}
 	}
 
+	fn toggle_log_ref(&mut self) {
+		let Some(branch) = self.branches.get(self.selection as usize)
+		else {
+			return;
+		};
+		let reference = branch.reference.clone();
+
+		if let Some(idx) =
+			self.log_refs.iter().position(|r| *r == reference)
+		{
+			self.log_refs.remove(idx);
+		} else {
+			self.log_refs.push(reference.clone());
+		}
+
+		self.queue.push(InternalEvent::ToggleLogRef(reference));
+	}
+
 	const fn get_branch_type(&self) -> BranchType {
 		if self.local {
 			BranchType::Local
@@ -578,11 +641,12 @@ impl BranchListPopup {
 		const UPSTREAM_SYMBOL: char = '\u{2191}';
 		const TRACKING_SYMBOL: char = '\u{2193}';
 		const HEAD_SYMBOL: char = '*';
+		const LOG_SYMBOL: char = '+';
 		const EMPTY_SYMBOL: char = ' ';
 		const THREE_DOTS: &str = "...";
 		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
 		const COMMIT_HASH_LENGTH: usize = 8;
-		const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
+		const IS_HEAD_STAR_LENGTH: usize = 4; // "*   "
 
 		let branch_name_length: usize =
 			width_available as usize * 40 / 100;
@@ -646,8 +710,17 @@ impl BranchListPopup {
 				_ => EMPTY_SYMBOL,
 			};
 
+			let in_log_str =
+				if self.log_refs.contains(&displaybranch.reference) {
+					LOG_SYMBOL
+				} else {
+					EMPTY_SYMBOL
+				};
+
 			let span_prefix = Span::styled(
-				format!("{is_head_str}{upstream_tracking_str} "),
+				format!(
+					"{is_head_str}{upstream_tracking_str}{in_log_str} "
+				),
 				theme.commit_author(selected),
 			);
 			let span_hash = Span::styled(
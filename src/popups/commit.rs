@@ -47,6 +47,7 @@ enum Mode {
 	Merge(Vec<CommitId>),
 	Revert,
 	Reword(CommitId),
+	RebaseReword,
 }
 
 pub struct CommitPopup {
@@ -293,6 +294,16 @@ impl CommitPopup {
 
 				commit
 			}
+			Mode::RebaseReword => {
+				let state = sync::continue_pending_rebase_reword(
+					&self.repo.borrow(),
+					msg,
+				)?;
+				self.queue
+					.push(InternalEvent::RebaseProgressed(state));
+
+				return Ok(());
+			}
 		};
 		Ok(())
 	}
@@ -453,6 +464,33 @@ impl CommitPopup {
 		Ok(())
 	}
 
+	/// open a regular commit prefilled with `msg`
+	pub fn open_with_message(&mut self, msg: String) -> Result<()> {
+		self.mode = Mode::Normal;
+		self.input.set_text(msg);
+
+		self.open(None)
+	}
+
+	/// open to provide the new message of a commit
+	/// an interactive rebase stopped at
+	pub fn open_rebase_reword(&mut self, id: CommitId) -> Result<()> {
+		self.mode = Mode::RebaseReword;
+
+		self.input.set_text(
+			sync::get_commit_details(&self.repo.borrow(), id)?
+				.message
+				.unwrap_or_default()
+				.combine(),
+		);
+		self.input.set_title(strings::commit_title_rebase_reword());
+
+		self.commit_msg_history_idx = 0;
+		self.input.show()?;
+
+		Ok(())
+	}
+
 	fn add_sign_off(&self, msg: &str) -> Result<String> {
 		const CONFIG_KEY_USER_NAME: &str = "user.name";
 		const CONFIG_KEY_USER_MAIL: &str = "user.email";
@@ -10,7 +10,7 @@ use crate::{
 	options::SharedOptions,
 	popups::InspectCommitOpen,
 	queue::{InternalEvent, Queue, StackablePopupOpen},
-	strings,
+	strings, AsyncNotification,
 };
 use anyhow::Result;
 use asyncgit::{
@@ -199,7 +199,14 @@ impl CompareCommitsPopup {
 
 	///
 	pub fn any_work_pending(&self) -> bool {
-		self.git_diff.is_pending() || self.details.any_work_pending()
+		self.git_diff.is_pending()
+			|| self.details.any_work_pending()
+			|| self.diff.any_work_pending()
+	}
+
+	///
+	pub fn update_syntax(&mut self, ev: AsyncNotification) {
+		self.diff.update_syntax(ev);
 	}
 
 	///
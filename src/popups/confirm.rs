@@ -201,6 +201,10 @@ impl ConfirmPopup {
 				Action::AbortRebase => (
                     strings::confirm_title_abortrebase(),
                     strings::confirm_msg_abortrebase(),
+                ),
+				Action::AbortCherryPick => (
+                    strings::confirm_title_abortcherrypick(),
+                    strings::confirm_msg_revertchanges(),
                 ),
 				Action::AbortRevert => (
                     strings::confirm_title_abortrevert(),
@@ -210,6 +214,10 @@ impl ConfirmPopup {
                     strings::confirm_title_undo_commit(),
                     strings::confirm_msg_undo_commit(),
                 ),
+                Action::RemoveWorktree(name, force) => (
+                    strings::confirm_title_remove_worktree(),
+                    strings::confirm_msg_remove_worktree(name, *force),
+                ),
             };
 		}
 
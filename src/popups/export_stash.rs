@@ -0,0 +1,161 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CommitId, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+use std::path::Path;
+
+/// input of the file to export a stash to as patch
+pub struct ExportStashPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	stash: Option<CommitId>,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ExportStashPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+		}
+		Ok(())
+	}
+}
+
+impl Component for ExportStashPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::export_stash_confirm(
+					&self.key_config,
+				),
+				!self.input.get_text().trim().is_empty(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl ExportStashPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::export_stash_popup_title(),
+				&strings::export_stash_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			stash: None,
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// suggests a file next to the working directory, so the patch
+	/// does not end up as untracked file in the repository
+	pub fn open(&mut self, id: CommitId) -> Result<()> {
+		self.stash = Some(id);
+
+		let file_name =
+			format!("stash-{}.patch", id.get_short_string());
+		let path = sync::utils::repo_work_dir(&self.repo.borrow())
+			.ok()
+			.and_then(|work_dir| {
+				Path::new(&work_dir)
+					.parent()
+					.map(|parent| parent.join(&file_name))
+			})
+			.map_or(file_name, |path| path.display().to_string());
+
+		self.input.set_text(path);
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// paths are relative to the working directory
+	fn confirm(&mut self) {
+		let path = self.input.get_text().trim().to_string();
+
+		let Some(id) = self.stash else {
+			return;
+		};
+
+		if path.is_empty() {
+			return;
+		}
+
+		let result = sync::export_stash_patch(
+			&self.repo.borrow(),
+			id,
+			Path::new(&path),
+		);
+
+		match result {
+			Ok(path) => {
+				self.hide();
+				self.queue.push(InternalEvent::ShowInfoMsg(format!(
+					"stash exported to:\n{}",
+					path.display()
+				)));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("stash export error:\n{e}"),
+				));
+			}
+		}
+	}
+}
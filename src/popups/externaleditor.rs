@@ -5,12 +5,13 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::SharedKeyConfig,
+	queue::ExternalTool,
 	strings,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::{anyhow, bail, Result};
 use asyncgit::sync::{
-	get_config_string, utils::repo_work_dir, RepoPath,
+	self, get_config_string, utils::repo_work_dir, RepoPath,
 };
 use crossterm::{
 	event::Event,
@@ -117,6 +118,28 @@ impl ExternalEditorPopup {
 
 		Ok(())
 	}
+
+	/// runs the external diff or merge `tool` and waits for it to
+	/// exit, returns whether a merge got resolved
+	pub fn open_external_tool(
+		repo: &RepoPath,
+		tool: &ExternalTool,
+	) -> Result<bool> {
+		io::stdout().execute(LeaveAlternateScreen)?;
+		defer! {
+			io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+		}
+
+		let resolved = match tool {
+			ExternalTool::Diff(path, target) => {
+				sync::difftool(repo, path, *target)?;
+				true
+			}
+			ExternalTool::Merge(path) => sync::mergetool(repo, path)?,
+		};
+
+		Ok(resolved)
+	}
 }
 
 impl DrawableComponent for ExternalEditorPopup {
@@ -10,13 +10,16 @@ use crate::{
 	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
 	strings,
 	ui::{draw_scrollbar, style::SharedTheme, Orientation},
+	AsyncNotification,
 };
 use anyhow::Result;
 use asyncgit::{
 	sync::{
-		diff_contains_file, get_commits_info, CommitId, RepoPathRef,
+		diff_contains_file_follow, get_commits_info, CommitId,
+		RepoPathRef, SharedFilePaths,
 	},
 	AsyncDiff, AsyncGitNotification, AsyncLog, DiffParams, DiffType,
+	FetchStatus,
 };
 use chrono::{DateTime, Local};
 use crossbeam_channel::Sender;
@@ -50,6 +53,7 @@ impl FileRevOpen {
 ///
 pub struct FileRevlogPopup {
 	git_log: Option<AsyncLog>,
+	file_paths: SharedFilePaths,
 	git_diff: AsyncDiff,
 	theme: SharedTheme,
 	queue: Queue,
@@ -76,6 +80,7 @@ impl FileRevlogPopup {
 			sender: env.sender_git.clone(),
 			diff: DiffComponent::new(env, true),
 			git_log: None,
+			file_paths: SharedFilePaths::default(),
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -101,12 +106,16 @@ impl FileRevlogPopup {
 	pub fn open(&mut self, open_request: FileRevOpen) -> Result<()> {
 		self.open_request = Some(open_request.clone());
 
-		let filter = diff_contains_file(open_request.file_path);
-		self.git_log = Some(AsyncLog::new(
+		let (filter, walk_start, file_paths) =
+			diff_contains_file_follow(open_request.file_path);
+		self.file_paths = file_paths;
+		let mut git_log = AsyncLog::new(
 			self.repo_path.borrow().clone(),
 			&self.sender,
 			Some(filter),
-		));
+		);
+		git_log.set_walk_start(walk_start);
+		self.git_log = Some(git_log);
 
 		self.items.clear();
 		self.set_selection(open_request.selection.unwrap_or(0));
@@ -128,12 +137,23 @@ impl FileRevlogPopup {
 				.git_log
 				.as_ref()
 				.map_or(false, AsyncLog::is_pending)
+			|| self.diff.any_work_pending()
+	}
+
+	///
+	pub fn update_syntax(&mut self, ev: AsyncNotification) {
+		self.diff.update_syntax(ev);
 	}
 
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if let Some(ref mut git_log) = self.git_log {
-			git_log.fetch()?;
+			git_log
+				.set_options(self.options.borrow().log_options())?;
+
+			if git_log.fetch()? == FetchStatus::Started {
+				self.items.clear();
+			}
 
 			self.fetch_commits_if_needed()?;
 			self.update_diff()?;
@@ -159,12 +179,48 @@ impl FileRevlogPopup {
 		Ok(())
 	}
 
+	/// path of the file in `commit_id`, it differs from the opened
+	/// path in commits before the file got renamed
+	fn file_path(&self, commit_id: CommitId) -> Option<String> {
+		let path = self
+			.file_paths
+			.lock()
+			.ok()
+			.and_then(|paths| paths.get(&commit_id).cloned());
+
+		path.or_else(|| {
+			self.open_request.as_ref().map(|r| r.file_path.clone())
+		})
+	}
+
+	fn open_blame(&mut self) {
+		let commit_id = self.selected_commit();
+		let file_path = match commit_id {
+			Some(id) => self.file_path(id),
+			None => self
+				.open_request
+				.as_ref()
+				.map(|r| r.file_path.clone()),
+		};
+
+		if let Some(file_path) = file_path {
+			self.hide_stacked(true);
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::BlameFile(BlameFileOpen {
+					file_path,
+					commit_id,
+					selection: None,
+				}),
+			));
+		}
+	}
+
 	pub fn update_diff(&mut self) -> Result<()> {
 		if self.is_visible() {
 			if let Some(commit_id) = self.selected_commit() {
-				if let Some(open_request) = &self.open_request {
+				if let Some(file_path) = self.file_path(commit_id) {
 					let diff_params = DiffParams {
-						path: open_request.file_path.clone(),
+						path: file_path.clone(),
 						diff_type: DiffType::Commit(commit_id),
 						options: self.options.borrow().diff_options(),
 					};
@@ -173,11 +229,7 @@ impl FileRevlogPopup {
 						self.git_diff.last()?
 					{
 						if params == diff_params {
-							self.diff.update(
-								open_request.file_path.to_string(),
-								false,
-								last,
-							);
+							self.diff.update(file_path, false, last);
 
 							return Ok(());
 						}
@@ -261,16 +313,20 @@ impl FileRevlogPopup {
 					&open_request.file_path,
 					selected,
 					revisions,
+					self.options.borrow().log_options(),
 				)
 			},
 		)
 	}
 
 	fn get_rows(&self, now: DateTime<Local>) -> Vec<Row> {
+		let open_path =
+			self.open_request.as_ref().map(|r| r.file_path.as_str());
+
 		self.items
 			.iter()
 			.map(|entry| {
-				let spans = Line::from(vec![
+				let mut spans = Line::from(vec![
 					Span::styled(
 						entry.hash_short.to_string(),
 						self.theme.commit_hash(false),
@@ -287,6 +343,18 @@ impl FileRevlogPopup {
 					),
 				]);
 
+				// path before the file got renamed
+				if let Some(path) = self
+					.file_path(entry.id)
+					.filter(|path| Some(path.as_str()) != open_path)
+				{
+					spans.spans.push(Span::raw(" "));
+					spans.spans.push(Span::styled(
+						path,
+						self.theme.text(true, false),
+					));
+				}
+
 				let mut text = Text::from(spans);
 				text.extend(Text::raw(entry.msg.to_string()));
 
@@ -527,20 +595,23 @@ impl Component for FileRevlogPopup {
 						));
 					};
 				} else if key_match(key, self.key_config.keys.blame) {
-					if let Some(open_request) =
-						self.open_request.clone()
-					{
-						self.hide_stacked(true);
-						self.queue.push(InternalEvent::OpenPopup(
-							StackablePopupOpen::BlameFile(
-								BlameFileOpen {
-									file_path: open_request.file_path,
-									commit_id: self.selected_commit(),
-									selection: None,
-								},
-							),
-						));
-					}
+					self.open_blame();
+				} else if key_match(
+					key,
+					self.key_config.keys.log_toggle_first_parent,
+				) {
+					self.options
+						.borrow_mut()
+						.log_toggle_first_parent();
+					self.set_selection(0);
+					self.update()?;
+				} else if key_match(
+					key,
+					self.key_config.keys.log_toggle_no_merges,
+				) {
+					self.options.borrow_mut().log_toggle_no_merges();
+					self.set_selection(0);
+					self.update()?;
 				} else if key_match(key, self.key_config.keys.move_up)
 				{
 					self.move_selection(ScrollType::Up)?;
@@ -615,6 +686,21 @@ impl Component for FileRevlogPopup {
 				.order(1),
 			);
 
+			out.push(CommandInfo::new(
+				strings::commands::log_toggle_first_parent(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::log_toggle_no_merges(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::diff_focus_right(&self.key_config),
 				self.can_focus_diff(),
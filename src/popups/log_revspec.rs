@@ -0,0 +1,130 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{CommitId, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+/// input of the revspec the log shows (like `main..feature`)
+pub struct LogRevspecPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for LogRevspecPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+		}
+		Ok(())
+	}
+}
+
+impl Component for LogRevspecPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::log_revspec_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl LogRevspecPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::log_revspec_popup_title(),
+				&strings::log_revspec_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// `revspec` is the one the log currently shows
+	pub fn open(&mut self, revspec: String) -> Result<()> {
+		self.input.set_text(revspec);
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// an empty revspec shows the log of `HEAD` again
+	fn confirm(&mut self) {
+		let revspec = self.input.get_text().trim().to_string();
+
+		if !revspec.is_empty() {
+			if let Err(e) = CommitId::from_revision_range(
+				&self.repo.borrow(),
+				&revspec,
+			) {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("revspec error:\n{e}"),
+				));
+				return;
+			}
+		}
+
+		self.hide();
+		self.queue.push(InternalEvent::LogRevspec(revspec));
+	}
+}
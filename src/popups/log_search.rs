@@ -6,13 +6,13 @@ use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, Queue},
-	strings::{self, POPUP_COMMIT_SHA_INVALID},
+	strings::{self, POPUP_COMMIT_SHA_INVALID, POPUP_REGEX_INVALID},
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
 use asyncgit::sync::{
-	CommitId, LogFilterSearchOptions, RepoPathRef, SearchFields,
-	SearchOptions,
+	CommitId, DateRange, LogFilterSearchOptions, RepoPathRef,
+	SearchFields, SearchOptions,
 };
 use crossterm::event::Event;
 use easy_cast::Cast;
@@ -29,10 +29,14 @@ enum Selection {
 	EnterText,
 	FuzzyOption,
 	CaseOption,
+	RegexOption,
 	SummarySearch,
 	MessageBodySearch,
 	FilenameSearch,
 	AuthorsSearch,
+	HashesSearch,
+	DatesSearch,
+	DiffsSearch,
 }
 
 enum PopupMode {
@@ -51,6 +55,7 @@ pub struct LogSearchPopupPopup {
 	options: (SearchFields, SearchOptions),
 	theme: SharedTheme,
 	jump_commit_id: Option<CommitId>,
+	pattern_error: Option<String>,
 }
 
 impl LogSearchPopupPopup {
@@ -76,6 +81,7 @@ impl LogSearchPopupPopup {
 			find_text,
 			selection: Selection::EnterText,
 			jump_commit_id: None,
+			pattern_error: None,
 		}
 	}
 
@@ -93,6 +99,7 @@ impl LogSearchPopupPopup {
 
 	fn set_mode(&mut self, mode: &PopupMode) {
 		self.find_text.set_text(String::new());
+		self.pattern_error = None;
 
 		match mode {
 			PopupMode::Search => {
@@ -123,14 +130,7 @@ impl LogSearchPopupPopup {
 		match self.mode {
 			PopupMode::Search => {
 				self.queue.push(InternalEvent::CommitSearch(
-					LogFilterSearchOptions {
-						fields: self.options.0,
-						options: self.options.1,
-						search_pattern: self
-							.find_text
-							.get_text()
-							.to_string(),
-					},
+					self.search_options(),
 				));
 			}
 			PopupMode::JumpCommitSha => {
@@ -143,10 +143,44 @@ impl LogSearchPopupPopup {
 		}
 	}
 
+	fn search_options(&self) -> LogFilterSearchOptions {
+		LogFilterSearchOptions {
+			fields: self.options.0,
+			options: self.options.1,
+			search_pattern: self.find_text.get_text().to_string(),
+		}
+	}
+
+	/// compiles the pattern in regex mode to show errors right away
+	/// instead of failing the search
+	fn validate_pattern(&mut self) {
+		self.pattern_error = if self.find_text.get_text().is_empty() {
+			None
+		} else {
+			self.search_options().regex().err().map(|e| {
+				// the last line of regex errors is the actual reason
+				let e = e.to_string();
+				let reason = e
+					.lines()
+					.last()
+					.unwrap_or_default()
+					.trim_start_matches("error: ")
+					.to_string();
+				format!("{POPUP_REGEX_INVALID}: {reason}")
+			})
+		};
+	}
+
 	fn is_valid(&self) -> bool {
 		match self.mode {
 			PopupMode::Search => {
-				!self.find_text.get_text().trim().is_empty()
+				let text = self.find_text.get_text();
+
+				// searching only dates needs a valid range
+				!text.trim().is_empty()
+					&& self.pattern_error.is_none()
+					&& (self.options.0 != SearchFields::DATES
+						|| DateRange::parse(text).is_some())
 			}
 			PopupMode::JumpCommitSha => self.jump_commit_id.is_some(),
 		}
@@ -164,106 +198,77 @@ impl LogSearchPopupPopup {
 		}
 	}
 
-	fn get_text_options(&self) -> Vec<Line> {
-		let x_summary =
-			if self.options.0.contains(SearchFields::MESSAGE_SUMMARY)
-			{
-				"X"
-			} else {
-				" "
-			};
-
-		let x_body =
-			if self.options.0.contains(SearchFields::MESSAGE_BODY) {
-				"X"
-			} else {
-				" "
-			};
-
-		let x_files =
-			if self.options.0.contains(SearchFields::FILENAMES) {
-				"X"
-			} else {
-				" "
-			};
-
-		let x_authors =
-			if self.options.0.contains(SearchFields::AUTHORS) {
-				"X"
-			} else {
-				" "
-			};
-
-		let x_opt_fuzzy =
-			if self.options.1.contains(SearchOptions::FUZZY_SEARCH) {
-				"X"
-			} else {
-				" "
-			};
+	fn option_line(
+		&self,
+		checked: bool,
+		label: &str,
+		selected: bool,
+	) -> Line {
+		let x = if checked { "X" } else { " " };
+
+		Line::from(vec![Span::styled(
+			format!("[{x}] {label}"),
+			self.theme.text(selected, false),
+		)])
+	}
 
-		let x_opt_casesensitive =
-			if self.options.1.contains(SearchOptions::CASE_SENSITIVE)
-			{
-				"X"
-			} else {
-				" "
-			};
+	fn get_text_options(&self) -> Vec<Line> {
+		let (fields, options) = self.options;
 
 		vec![
-			Line::from(vec![Span::styled(
-				format!("[{x_opt_fuzzy}] fuzzy search"),
-				self.theme.text(
-					matches!(self.selection, Selection::FuzzyOption),
-					false,
-				),
-			)]),
-			Line::from(vec![Span::styled(
-				format!("[{x_opt_casesensitive}] case sensitive"),
-				self.theme.text(
-					matches!(self.selection, Selection::CaseOption),
-					false,
-				),
-			)]),
-			Line::from(vec![Span::styled(
-				format!("[{x_summary}] summary",),
-				self.theme.text(
-					matches!(
-						self.selection,
-						Selection::SummarySearch
-					),
-					false,
-				),
-			)]),
-			Line::from(vec![Span::styled(
-				format!("[{x_body}] message body",),
-				self.theme.text(
-					matches!(
-						self.selection,
-						Selection::MessageBodySearch
-					),
-					false,
-				),
-			)]),
-			Line::from(vec![Span::styled(
-				format!("[{x_files}] committed files",),
-				self.theme.text(
-					matches!(
-						self.selection,
-						Selection::FilenameSearch
-					),
-					false,
-				),
-			)]),
-			Line::from(vec![Span::styled(
-				format!("[{x_authors}] authors",),
-				self.theme.text(
-					matches!(
-						self.selection,
-						Selection::AuthorsSearch
-					),
-					false,
+			self.option_line(
+				options.contains(SearchOptions::FUZZY_SEARCH),
+				"fuzzy search",
+				matches!(self.selection, Selection::FuzzyOption),
+			),
+			self.option_line(
+				options.contains(SearchOptions::CASE_SENSITIVE),
+				"case sensitive",
+				matches!(self.selection, Selection::CaseOption),
+			),
+			self.option_line(
+				options.contains(SearchOptions::REGEX),
+				"regex",
+				matches!(self.selection, Selection::RegexOption),
+			),
+			self.option_line(
+				fields.contains(SearchFields::MESSAGE_SUMMARY),
+				"summary",
+				matches!(self.selection, Selection::SummarySearch),
+			),
+			self.option_line(
+				fields.contains(SearchFields::MESSAGE_BODY),
+				"message body",
+				matches!(
+					self.selection,
+					Selection::MessageBodySearch
 				),
-			)]),
+			),
+			self.option_line(
+				fields.contains(SearchFields::FILENAMES),
+				"committed files",
+				matches!(self.selection, Selection::FilenameSearch),
+			),
+			self.option_line(
+				fields.contains(SearchFields::AUTHORS),
+				"authors",
+				matches!(self.selection, Selection::AuthorsSearch),
+			),
+			self.option_line(
+				fields.contains(SearchFields::COMMIT_HASHES),
+				"commit hashes",
+				matches!(self.selection, Selection::HashesSearch),
+			),
+			self.option_line(
+				fields.contains(SearchFields::DATES),
+				"dates (YYYY-MM-DD..YYYY-MM-DD)",
+				matches!(self.selection, Selection::DatesSearch),
+			),
+			self.option_line(
+				fields.contains(SearchFields::DIFFS),
+				"diff lines added/removed",
+				matches!(self.selection, Selection::DiffsSearch),
+			),
 		]
 	}
 
@@ -276,10 +281,15 @@ impl LogSearchPopupPopup {
 			Selection::EnterText => (),
 			Selection::FuzzyOption => {
 				self.options.1.toggle(SearchOptions::FUZZY_SEARCH);
+				self.options.1.remove(SearchOptions::REGEX);
 			}
 			Selection::CaseOption => {
 				self.options.1.toggle(SearchOptions::CASE_SENSITIVE);
 			}
+			Selection::RegexOption => {
+				self.options.1.toggle(SearchOptions::REGEX);
+				self.options.1.remove(SearchOptions::FUZZY_SEARCH);
+			}
 			Selection::SummarySearch => {
 				self.options.0.toggle(SearchFields::MESSAGE_SUMMARY);
 
@@ -306,6 +316,29 @@ impl LogSearchPopupPopup {
 			Selection::AuthorsSearch => {
 				self.options.0.toggle(SearchFields::AUTHORS);
 
+				if self.options.0.is_empty() {
+					self.options
+						.0
+						.set(SearchFields::COMMIT_HASHES, true);
+				}
+			}
+			Selection::HashesSearch => {
+				self.options.0.toggle(SearchFields::COMMIT_HASHES);
+
+				if self.options.0.is_empty() {
+					self.options.0.set(SearchFields::DATES, true);
+				}
+			}
+			Selection::DatesSearch => {
+				self.options.0.toggle(SearchFields::DATES);
+
+				if self.options.0.is_empty() {
+					self.options.0.set(SearchFields::DIFFS, true);
+				}
+			}
+			Selection::DiffsSearch => {
+				self.options.0.toggle(SearchFields::DIFFS);
+
 				if self.options.0.is_empty() {
 					self.options
 						.0
@@ -319,10 +352,11 @@ impl LogSearchPopupPopup {
 		if arg {
 			//up
 			self.selection = match self.selection {
-				Selection::EnterText => Selection::AuthorsSearch,
+				Selection::EnterText => Selection::DiffsSearch,
 				Selection::FuzzyOption => Selection::EnterText,
 				Selection::CaseOption => Selection::FuzzyOption,
-				Selection::SummarySearch => Selection::CaseOption,
+				Selection::RegexOption => Selection::CaseOption,
+				Selection::SummarySearch => Selection::RegexOption,
 				Selection::MessageBodySearch => {
 					Selection::SummarySearch
 				}
@@ -330,12 +364,16 @@ impl LogSearchPopupPopup {
 					Selection::MessageBodySearch
 				}
 				Selection::AuthorsSearch => Selection::FilenameSearch,
+				Selection::HashesSearch => Selection::AuthorsSearch,
+				Selection::DatesSearch => Selection::HashesSearch,
+				Selection::DiffsSearch => Selection::DatesSearch,
 			};
 		} else {
 			self.selection = match self.selection {
 				Selection::EnterText => Selection::FuzzyOption,
 				Selection::FuzzyOption => Selection::CaseOption,
-				Selection::CaseOption => Selection::SummarySearch,
+				Selection::CaseOption => Selection::RegexOption,
+				Selection::RegexOption => Selection::SummarySearch,
 				Selection::SummarySearch => {
 					Selection::MessageBodySearch
 				}
@@ -343,7 +381,10 @@ impl LogSearchPopupPopup {
 					Selection::FilenameSearch
 				}
 				Selection::FilenameSearch => Selection::AuthorsSearch,
-				Selection::AuthorsSearch => Selection::EnterText,
+				Selection::AuthorsSearch => Selection::HashesSearch,
+				Selection::HashesSearch => Selection::DatesSearch,
+				Selection::DatesSearch => Selection::DiffsSearch,
+				Selection::DiffsSearch => Selection::EnterText,
 			};
 		}
 
@@ -356,14 +397,20 @@ impl LogSearchPopupPopup {
 		f: &mut Frame,
 		area: Rect,
 	) -> Result<()> {
-		const SIZE: (u16, u16) = (60, 10);
+		const SIZE: (u16, u16) = (60, 14);
 		let area = ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
+		let mut block_style = self.theme.title(true);
+
+		if self.pattern_error.is_some() {
+			block_style = block_style.patch(self.theme.text_danger());
+		}
+
 		f.render_widget(Clear, area);
 		f.render_widget(
 			Block::default()
 				.borders(Borders::all())
-				.style(self.theme.title(true))
+				.style(block_style)
 				.title(Span::styled(
 					strings::POPUP_TITLE_LOG_SEARCH,
 					self.theme.title(true),
@@ -395,6 +442,10 @@ impl LogSearchPopupPopup {
 			chunks[1],
 		);
 
+		if let Some(error) = &self.pattern_error {
+			self.draw_input_error(f, error);
+		}
+
 		Ok(())
 	}
 
@@ -438,16 +489,15 @@ impl LogSearchPopupPopup {
 		self.find_text.draw(f, chunks[0])?;
 
 		if show_invalid {
-			self.draw_invalid_sha(f);
+			self.draw_input_error(f, POPUP_COMMIT_SHA_INVALID);
 		}
 
 		Ok(())
 	}
 
-	fn draw_invalid_sha(&self, f: &mut Frame) {
-		let msg_length: u16 = POPUP_COMMIT_SHA_INVALID.len().cast();
-		let w = Paragraph::new(POPUP_COMMIT_SHA_INVALID)
-			.style(self.theme.text_danger());
+	fn draw_input_error(&self, f: &mut Frame, msg: &str) {
+		let msg_length: u16 = msg.len().cast();
+		let w = Paragraph::new(msg).style(self.theme.text_danger());
 
 		let rect = {
 			let mut rect = self.find_text.get_area();
@@ -491,8 +541,11 @@ impl LogSearchPopupPopup {
 			) && self.option_selected()
 			{
 				self.toggle_option();
-			} else if !self.option_selected() {
-				self.find_text.event(event)?;
+				self.validate_pattern();
+			} else if !self.option_selected()
+				&& self.find_text.event(event)?.is_consumed()
+			{
+				self.validate_pattern();
 			}
 		}
 
@@ -1,21 +1,27 @@
+mod absorb;
+mod apply_patch;
 mod blame_file;
 mod branchlist;
 mod commit;
 mod compare_commits;
 mod confirm;
 mod create_branch;
+mod export_stash;
 mod externaleditor;
 mod fetch;
 mod file_revlog;
 mod fuzzy_find;
 mod help;
 mod inspect_commit;
+mod log_revspec;
 mod log_search;
 mod msg;
 mod options;
 mod pull;
 mod push;
 mod push_tags;
+mod rebase_interactive;
+mod reflog;
 mod rename_branch;
 mod reset;
 mod revision_files;
@@ -23,25 +29,32 @@ mod stashmsg;
 mod submodules;
 mod tag_commit;
 mod taglist;
+mod worktrees;
 
+pub use absorb::AbsorbPopup;
+pub use apply_patch::ApplyPatchPopup;
 pub use blame_file::{BlameFileOpen, BlameFilePopup};
 pub use branchlist::BranchListPopup;
 pub use commit::CommitPopup;
 pub use compare_commits::CompareCommitsPopup;
 pub use confirm::ConfirmPopup;
 pub use create_branch::CreateBranchPopup;
+pub use export_stash::ExportStashPopup;
 pub use externaleditor::ExternalEditorPopup;
 pub use fetch::FetchPopup;
 pub use file_revlog::{FileRevOpen, FileRevlogPopup};
 pub use fuzzy_find::FuzzyFindPopup;
 pub use help::HelpPopup;
 pub use inspect_commit::{InspectCommitOpen, InspectCommitPopup};
+pub use log_revspec::LogRevspecPopup;
 pub use log_search::LogSearchPopupPopup;
 pub use msg::MsgPopup;
 pub use options::{AppOption, OptionsPopup};
 pub use pull::PullPopup;
 pub use push::PushPopup;
 pub use push_tags::PushTagsPopup;
+pub use rebase_interactive::InteractiveRebasePopup;
+pub use reflog::ReflogPopup;
 pub use rename_branch::RenameBranchPopup;
 pub use reset::ResetPopup;
 pub use revision_files::{FileTreeOpen, RevisionFilesPopup};
@@ -49,6 +62,7 @@ pub use stashmsg::StashMsgPopup;
 pub use submodules::SubmodulesListPopup;
 pub use tag_commit::TagCommitPopup;
 pub use taglist::TagListPopup;
+pub use worktrees::WorktreesListPopup;
 
 use crate::ui::style::Theme;
 use ratatui::{
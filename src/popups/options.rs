@@ -11,7 +11,7 @@ use crate::{
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::sync::ShowUntrackedFilesConfig;
+use asyncgit::sync::{diff::DiffAlgorithm, ShowUntrackedFilesConfig};
 use crossterm::event::Event;
 use ratatui::{
 	layout::{Alignment, Rect},
@@ -25,8 +25,13 @@ use ratatui::{
 pub enum AppOption {
 	StatusShowUntracked,
 	DiffIgnoreWhitespaces,
+	DiffIgnoreWhitespaceChange,
+	DiffIgnoreWhitespaceEol,
+	DiffIgnoreBlankLines,
+	DiffAlgorithm,
 	DiffContextLines,
 	DiffInterhunkLines,
+	DiffSideBySide,
 }
 
 pub struct OptionsPopup {
@@ -85,6 +90,38 @@ impl OptionsPopup {
 			&diff.ignore_whitespace.to_string(),
 			self.is_select(AppOption::DiffIgnoreWhitespaces),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Ignore whitespace change",
+			&diff.ignore_whitespace_change.to_string(),
+			self.is_select(AppOption::DiffIgnoreWhitespaceChange),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Ignore whitespace at eol",
+			&diff.ignore_whitespace_eol.to_string(),
+			self.is_select(AppOption::DiffIgnoreWhitespaceEol),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Ignore blank lines",
+			&diff.ignore_blank_lines.to_string(),
+			self.is_select(AppOption::DiffIgnoreBlankLines),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Algorithm",
+			match diff.algorithm {
+				DiffAlgorithm::Myers => "Myers",
+				DiffAlgorithm::Minimal => "Minimal",
+				DiffAlgorithm::Patience => "Patience",
+			},
+			self.is_select(AppOption::DiffAlgorithm),
+		);
 		self.add_entry(
 			txt,
 			width,
@@ -99,6 +136,13 @@ impl OptionsPopup {
 			&diff.interhunk_lines.to_string(),
 			self.is_select(AppOption::DiffInterhunkLines),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Side-by-side",
+			&self.options.borrow().diff_side_by_side().to_string(),
+			self.is_select(AppOption::DiffSideBySide),
+		);
 	}
 
 	fn is_select(&self, kind: AppOption) -> bool {
@@ -138,17 +182,32 @@ impl OptionsPopup {
 		if up {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
-					AppOption::DiffInterhunkLines
+					AppOption::DiffSideBySide
 				}
 				AppOption::DiffIgnoreWhitespaces => {
 					AppOption::StatusShowUntracked
 				}
-				AppOption::DiffContextLines => {
+				AppOption::DiffIgnoreWhitespaceChange => {
 					AppOption::DiffIgnoreWhitespaces
 				}
+				AppOption::DiffIgnoreWhitespaceEol => {
+					AppOption::DiffIgnoreWhitespaceChange
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					AppOption::DiffIgnoreWhitespaceEol
+				}
+				AppOption::DiffAlgorithm => {
+					AppOption::DiffIgnoreBlankLines
+				}
+				AppOption::DiffContextLines => {
+					AppOption::DiffAlgorithm
+				}
 				AppOption::DiffInterhunkLines => {
 					AppOption::DiffContextLines
 				}
+				AppOption::DiffSideBySide => {
+					AppOption::DiffInterhunkLines
+				}
 			};
 		} else {
 			self.selection = match self.selection {
@@ -156,12 +215,27 @@ impl OptionsPopup {
 					AppOption::DiffIgnoreWhitespaces
 				}
 				AppOption::DiffIgnoreWhitespaces => {
+					AppOption::DiffIgnoreWhitespaceChange
+				}
+				AppOption::DiffIgnoreWhitespaceChange => {
+					AppOption::DiffIgnoreWhitespaceEol
+				}
+				AppOption::DiffIgnoreWhitespaceEol => {
+					AppOption::DiffIgnoreBlankLines
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					AppOption::DiffAlgorithm
+				}
+				AppOption::DiffAlgorithm => {
 					AppOption::DiffContextLines
 				}
 				AppOption::DiffContextLines => {
 					AppOption::DiffInterhunkLines
 				}
 				AppOption::DiffInterhunkLines => {
+					AppOption::DiffSideBySide
+				}
+				AppOption::DiffSideBySide => {
 					AppOption::StatusShowUntracked
 				}
 			};
@@ -197,6 +271,26 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_toggle_whitespace();
 				}
+				AppOption::DiffIgnoreWhitespaceChange => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_whitespace_change();
+				}
+				AppOption::DiffIgnoreWhitespaceEol => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_whitespace_eol();
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_blank_lines();
+				}
+				AppOption::DiffAlgorithm => {
+					self.options
+						.borrow_mut()
+						.diff_algorithm_change(true);
+				}
 				AppOption::DiffContextLines => {
 					self.options
 						.borrow_mut()
@@ -207,6 +301,11 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_hunk_lines_change(true);
 				}
+				AppOption::DiffSideBySide => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_side_by_side();
+				}
 			};
 		} else {
 			match self.selection {
@@ -236,6 +335,26 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_toggle_whitespace();
 				}
+				AppOption::DiffIgnoreWhitespaceChange => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_whitespace_change();
+				}
+				AppOption::DiffIgnoreWhitespaceEol => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_whitespace_eol();
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_blank_lines();
+				}
+				AppOption::DiffAlgorithm => {
+					self.options
+						.borrow_mut()
+						.diff_algorithm_change(false);
+				}
 				AppOption::DiffContextLines => {
 					self.options
 						.borrow_mut()
@@ -246,6 +365,11 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_hunk_lines_change(false);
 				}
+				AppOption::DiffSideBySide => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_side_by_side();
+				}
 			};
 		}
 
@@ -257,7 +381,7 @@ impl OptionsPopup {
 impl DrawableComponent for OptionsPopup {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (50, 10);
+			const SIZE: (u16, u16) = (50, 15);
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
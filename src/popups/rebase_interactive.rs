@@ -0,0 +1,353 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, ScrollType, VerticalScroll,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	rebase_interactive, rebase_interactive_plan, CommitId,
+	RebaseAction, RebasePlanEntry, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::cell::Cell;
+use unicode_truncate::UnicodeTruncateStr;
+
+/// lets the user edit the todo list of an interactive rebase
+pub struct InteractiveRebasePopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	base: Option<CommitId>,
+	plan: Vec<RebasePlanEntry>,
+	visible: bool,
+	current_height: Cell<u16>,
+	selection: usize,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for InteractiveRebasePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+
+			f.render_widget(
+				Block::default()
+					.title(strings::rebase_interactive_title(
+						self.base.unwrap_or_default(),
+					))
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			self.draw_list(f, area)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for InteractiveRebasePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_move(
+					&self.key_config,
+				),
+				self.plan.len() > 1,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_action(
+					&self.key_config,
+				),
+				!self.plan.is_empty(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_start(
+					&self.key_config,
+				),
+				!self.plan.is_empty(),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if key_match(e, self.key_config.keys.move_down) {
+				self.move_selection(ScrollType::Down);
+			} else if key_match(e, self.key_config.keys.move_up) {
+				self.move_selection(ScrollType::Up);
+			} else if key_match(e, self.key_config.keys.page_down) {
+				self.move_selection(ScrollType::PageDown);
+			} else if key_match(e, self.key_config.keys.page_up) {
+				self.move_selection(ScrollType::PageUp);
+			} else if key_match(e, self.key_config.keys.home) {
+				self.move_selection(ScrollType::Home);
+			} else if key_match(e, self.key_config.keys.end) {
+				self.move_selection(ScrollType::End);
+			} else if key_match(e, self.key_config.keys.shift_down) {
+				self.move_entry(false);
+			} else if key_match(e, self.key_config.keys.shift_up) {
+				self.move_entry(true);
+			} else if key_match(e, self.key_config.keys.rebase_pick) {
+				self.set_action(RebaseAction::Pick);
+			} else if key_match(e, self.key_config.keys.rebase_reword)
+			{
+				self.set_action(RebaseAction::Reword);
+			} else if key_match(e, self.key_config.keys.rebase_edit) {
+				self.set_action(RebaseAction::Edit);
+			} else if key_match(e, self.key_config.keys.rebase_squash)
+			{
+				self.set_action(RebaseAction::Squash);
+			} else if key_match(e, self.key_config.keys.rebase_fixup)
+			{
+				self.set_action(RebaseAction::Fixup);
+			} else if key_match(e, self.key_config.keys.rebase_drop) {
+				self.set_action(RebaseAction::Drop);
+			} else if key_match(e, self.key_config.keys.enter) {
+				self.start();
+			} else if key_match(
+				e,
+				self.key_config.keys.cmd_bar_toggle,
+			) {
+				//do not consume if its the more key
+				return Ok(EventState::NotConsumed);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl InteractiveRebasePopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			base: None,
+			plan: Vec::new(),
+			visible: false,
+			current_height: Cell::new(0),
+			selection: 0,
+			scroll: VerticalScroll::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// open with all commits after `base` up to HEAD
+	pub fn open(&mut self, base: CommitId) -> Result<()> {
+		self.plan =
+			rebase_interactive_plan(&self.repo.borrow(), base)?;
+		self.base = Some(base);
+		self.selection = 0;
+
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn start(&mut self) {
+		let Some(base) = self.base else {
+			return;
+		};
+
+		self.hide();
+
+		try_or_popup!(
+			self,
+			"rebase failed:",
+			rebase_interactive(&self.repo.borrow(), base, &self.plan)
+				.map(|state| {
+					self.queue
+						.push(InternalEvent::RebaseProgressed(state));
+				})
+		);
+	}
+
+	fn set_action(&mut self, action: RebaseAction) {
+		if let Some(entry) = self.plan.get_mut(self.selection) {
+			entry.action = action;
+		}
+	}
+
+	fn move_entry(&mut self, up: bool) {
+		let target = if up {
+			self.selection.checked_sub(1)
+		} else {
+			Some(self.selection.saturating_add(1))
+				.filter(|idx| *idx < self.plan.len())
+		};
+
+		if let Some(target) = target {
+			self.plan.swap(self.selection, target);
+			self.selection = target;
+		}
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let height = usize::from(self.current_height.get());
+		let max = self.plan.len().saturating_sub(1);
+
+		self.selection = match scroll {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			ScrollType::PageUp => {
+				self.selection.saturating_sub(height)
+			}
+			ScrollType::PageDown => {
+				self.selection.saturating_add(height)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max,
+		}
+		.min(max);
+	}
+
+	fn get_text(&self, width: usize, height: usize) -> Text {
+		const ACTION_LENGTH: usize = 7;
+		const COMMIT_HASH_LENGTH: usize = 8;
+
+		let summary_length = width
+			.saturating_sub(ACTION_LENGTH)
+			.saturating_sub(COMMIT_HASH_LENGTH)
+			.saturating_sub(2);
+
+		let lines = self
+			.plan
+			.iter()
+			.enumerate()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.map(|(idx, entry)| {
+				let selected = idx == self.selection;
+				let enabled = entry.action != RebaseAction::Drop;
+
+				let (summary, _) =
+					entry.summary.unicode_truncate(summary_length);
+
+				Line::from(vec![
+					Span::styled(
+						format!(
+							"{:ACTION_LENGTH$} ",
+							entry.action.as_str()
+						),
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						format!("{} ", entry.id.get_short_string()),
+						self.theme.commit_hash(selected),
+					),
+					Span::styled(
+						summary.to_string(),
+						self.theme.text(enabled, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		Text::from(lines)
+	}
+
+	fn draw_list(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection,
+			self.plan.len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(
+				self.get_text(r.width as usize, height_in_lines),
+			)
+			.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,397 @@
+use crate::{
+	app::Environment,
+	components::{
+		time_to_string, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+		ScrollType, VerticalScroll,
+	},
+	keys::{key_match, SharedKeyConfig},
+	popups::InspectCommitOpen,
+	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, checkout_commit, CommitId, ReflogEntry, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::cell::Cell;
+use unicode_truncate::UnicodeTruncateStr;
+
+/// lists reflog entries of `HEAD` and the local branches
+pub struct ReflogPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	refs: Vec<String>,
+	selected_ref: usize,
+	entries: Vec<ReflogEntry>,
+	visible: bool,
+	current_height: Cell<u16>,
+	selection: usize,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ReflogPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+
+			f.render_widget(
+				Block::default()
+					.title(strings::reflog_title(
+						self.current_ref().unwrap_or_default(),
+						self.selected_ref,
+						self.refs.len(),
+					))
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			self.draw_list(f, area)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ReflogPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			let selected = self.selected_entry().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::reflog_switch_ref(
+					&self.key_config,
+				),
+				self.refs.len() > 1,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::commit_details_open(
+					&self.key_config,
+				),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::log_checkout_commit(
+					&self.key_config,
+				),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::log_reset_commit(&self.key_config),
+				selected,
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if key_match(e, self.key_config.keys.move_down) {
+				self.move_selection(ScrollType::Down);
+			} else if key_match(e, self.key_config.keys.move_up) {
+				self.move_selection(ScrollType::Up);
+			} else if key_match(e, self.key_config.keys.page_down) {
+				self.move_selection(ScrollType::PageDown);
+			} else if key_match(e, self.key_config.keys.page_up) {
+				self.move_selection(ScrollType::PageUp);
+			} else if key_match(e, self.key_config.keys.home) {
+				self.move_selection(ScrollType::Home);
+			} else if key_match(e, self.key_config.keys.end) {
+				self.move_selection(ScrollType::End);
+			} else if key_match(e, self.key_config.keys.tab_toggle) {
+				self.switch_ref(false)?;
+			} else if key_match(
+				e,
+				self.key_config.keys.tab_toggle_reverse,
+			) {
+				self.switch_ref(true)?;
+			} else if key_match(e, self.key_config.keys.enter)
+				|| key_match(e, self.key_config.keys.move_right)
+			{
+				self.inspect();
+			} else if key_match(
+				e,
+				self.key_config.keys.log_checkout_commit,
+			) {
+				self.checkout();
+			} else if key_match(
+				e,
+				self.key_config.keys.log_reset_commit,
+			) {
+				if let Some(id) = self.selected_commit() {
+					self.hide();
+					self.queue
+						.push(InternalEvent::OpenResetPopup(id));
+				}
+			} else if key_match(
+				e,
+				self.key_config.keys.cmd_bar_toggle,
+			) {
+				//do not consume if its the more key
+				return Ok(EventState::NotConsumed);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl ReflogPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			refs: Vec::new(),
+			selected_ref: 0,
+			entries: Vec::new(),
+			visible: false,
+			current_height: Cell::new(0),
+			selection: 0,
+			scroll: VerticalScroll::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// open showing the reflog of `HEAD`
+	pub fn open(&mut self) -> Result<()> {
+		self.refs = sync::get_reflog_refs(&self.repo.borrow())?;
+		self.selected_ref = 0;
+		self.update_entries()?;
+
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn current_ref(&self) -> Option<&str> {
+		self.refs.get(self.selected_ref).map(String::as_str)
+	}
+
+	fn update_entries(&mut self) -> Result<()> {
+		self.entries = self.current_ref().map_or_else(
+			|| Ok(Vec::new()),
+			|reference| {
+				sync::get_reflog(&self.repo.borrow(), reference)
+			},
+		)?;
+		self.selection = 0;
+
+		Ok(())
+	}
+
+	fn switch_ref(&mut self, reverse: bool) -> Result<()> {
+		if self.refs.is_empty() {
+			return Ok(());
+		}
+
+		self.selected_ref = if reverse {
+			self.selected_ref
+				.checked_sub(1)
+				.unwrap_or(self.refs.len() - 1)
+		} else {
+			(self.selected_ref + 1) % self.refs.len()
+		};
+
+		self.update_entries()
+	}
+
+	fn selected_entry(&self) -> Option<&ReflogEntry> {
+		self.entries.get(self.selection)
+	}
+
+	fn selected_commit(&self) -> Option<CommitId> {
+		self.selected_entry()
+			.map(|entry| entry.new_id)
+			.filter(|id| *id != CommitId::default())
+	}
+
+	fn inspect(&mut self) {
+		if let Some(id) = self.selected_commit() {
+			self.hide();
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::InspectCommit(
+					InspectCommitOpen::new(id),
+				),
+			));
+		}
+	}
+
+	fn checkout(&mut self) {
+		let Some(id) = self.selected_commit() else {
+			return;
+		};
+
+		self.hide();
+
+		try_or_popup!(
+			self,
+			"checkout error:",
+			checkout_commit(&self.repo.borrow(), id)
+		);
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let height = usize::from(self.current_height.get());
+		let max = self.entries.len().saturating_sub(1);
+
+		self.selection = match scroll {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			ScrollType::PageUp => {
+				self.selection.saturating_sub(height)
+			}
+			ScrollType::PageDown => {
+				self.selection.saturating_add(height)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max,
+		}
+		.min(max);
+	}
+
+	fn get_text(&self, width: usize, height: usize) -> Text {
+		const COMMIT_HASH_LENGTH: usize = 8;
+		const TIME_LENGTH: usize = 20;
+
+		let message_length = width
+			.saturating_sub(2 * COMMIT_HASH_LENGTH + 3)
+			.saturating_sub(TIME_LENGTH);
+
+		let lines = self
+			.entries
+			.iter()
+			.enumerate()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.map(|(idx, entry)| {
+				let selected = idx == self.selection;
+
+				let (message, _) =
+					entry.message.unicode_truncate(message_length);
+
+				Line::from(vec![
+					Span::styled(
+						format!(
+							"{} → {} ",
+							entry.old_id.get_short_string(),
+							entry.new_id.get_short_string()
+						),
+						self.theme.commit_hash(selected),
+					),
+					Span::styled(
+						format!(
+							"{:TIME_LENGTH$}",
+							time_to_string(entry.time, false)
+						),
+						self.theme.commit_time(selected),
+					),
+					Span::styled(
+						message.to_string(),
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		Text::from(lines)
+	}
+
+	fn draw_list(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection,
+			self.entries.len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(
+				self.get_text(r.width as usize, height_in_lines),
+			)
+			.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+}
@@ -10,13 +10,17 @@ use crate::{
 	tabs::StashingOptions,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::sync::{
+	self, diff::DiffOptions, CommitId, RepoPathRef, StashFile,
+};
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
 
 pub struct StashMsgPopup {
 	repo: RepoPathRef,
 	options: StashingOptions,
+	files: Vec<StashFile>,
+	diff_options: DiffOptions,
 	input: TextInputComponent,
 	queue: Queue,
 	key_config: SharedKeyConfig,
@@ -59,16 +63,7 @@ impl Component for StashMsgPopup {
 
 			if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.enter) {
-					let result = sync::stash_save(
-						&self.repo.borrow(),
-						if self.input.get_text().is_empty() {
-							None
-						} else {
-							Some(self.input.get_text())
-						},
-						self.options.stash_untracked,
-						self.options.keep_index,
-					);
+					let result = self.stash();
 					match result {
 						Ok(_) => {
 							self.input.clear();
@@ -124,6 +119,8 @@ impl StashMsgPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			options: StashingOptions::default(),
+			files: Vec::new(),
+			diff_options: DiffOptions::default(),
 			queue: env.queue.clone(),
 			input: TextInputComponent::new(
 				env,
@@ -137,8 +134,44 @@ impl StashMsgPopup {
 		}
 	}
 
-	///
-	pub fn options(&mut self, options: StashingOptions) {
+	/// stash only `files` unless empty, `diff_options` are the
+	/// ones the hunks of `files` were selected in
+	pub fn options(
+		&mut self,
+		options: StashingOptions,
+		files: Vec<StashFile>,
+		diff_options: DiffOptions,
+	) {
 		self.options = options;
+		self.files = files;
+		self.diff_options = diff_options;
+	}
+
+	fn stash(&self) -> Result<CommitId> {
+		let message = if self.input.get_text().is_empty() {
+			None
+		} else {
+			Some(self.input.get_text())
+		};
+
+		let id = if self.files.is_empty() {
+			sync::stash_save(
+				&self.repo.borrow(),
+				message,
+				self.options.stash_untracked,
+				self.options.keep_index,
+			)?
+		} else {
+			sync::stash_save_files(
+				&self.repo.borrow(),
+				message,
+				self.options.stash_untracked,
+				self.options.keep_index,
+				&self.files,
+				Some(self.diff_options),
+			)?
+		};
+
+		Ok(id)
 	}
 }
@@ -0,0 +1,581 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, ScrollType,
+		TextInputComponent, VerticalScroll,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	add_worktree, get_worktrees, is_worktree_clean, lock_worktree,
+	prune_worktrees, unlock_worktree, RepoPathRef, WorktreeInfo,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{
+		Alignment, Constraint, Direction, Layout, Margin, Rect,
+	},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::{cell::Cell, path::Path};
+use ui::style::SharedTheme;
+use unicode_truncate::UnicodeTruncateStr;
+
+/// lists the main and linked worktrees of the repository
+pub struct WorktreesListPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	worktrees: Vec<WorktreeInfo>,
+	input: TextInputComponent,
+	/// path entered in the first step of adding a worktree, the
+	/// input asks for the branch then
+	add_path: Option<String>,
+	visible: bool,
+	current_height: Cell<u16>,
+	selection: usize,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for WorktreesListPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+
+			f.render_widget(
+				Block::default()
+					.title(strings::POPUP_TITLE_WORKTREES)
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[Constraint::Min(30), Constraint::Length(50)]
+						.as_ref(),
+				)
+				.split(area);
+
+			self.draw_list(f, chunks[0])?;
+			self.draw_info(f, chunks[1]);
+
+			self.input.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for WorktreesListPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			if self.input.is_visible() {
+				self.input.commands(out, force_all);
+
+				out.push(CommandInfo::new(
+					strings::commands::add_worktree_confirm(
+						&self.key_config,
+					),
+					self.add_path.is_some()
+						|| !self.input.get_text().is_empty(),
+					true,
+				));
+
+				return visibility_blocking(self);
+			}
+
+			let selected = self.selected_entry();
+			let linked = selected.is_some_and(|w| !w.is_main);
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::open_worktree(&self.key_config),
+				selected.is_some_and(|w| w.is_valid && !w.is_current),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::add_worktree(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::lock_worktree(
+					&self.key_config,
+					selected.is_some_and(|w| w.is_locked),
+				),
+				linked,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::prune_worktrees(&self.key_config),
+				self.worktrees.iter().any(|w| w.is_prunable),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::remove_worktree(&self.key_config),
+				linked
+					&& selected.is_some_and(|w| {
+						!w.is_locked && !w.is_current
+					}),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if self.input.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.add();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if key_match(e, self.key_config.keys.move_down) {
+				self.move_selection(ScrollType::Down);
+			} else if key_match(e, self.key_config.keys.move_up) {
+				self.move_selection(ScrollType::Up);
+			} else if key_match(e, self.key_config.keys.page_down) {
+				self.move_selection(ScrollType::PageDown);
+			} else if key_match(e, self.key_config.keys.page_up) {
+				self.move_selection(ScrollType::PageUp);
+			} else if key_match(e, self.key_config.keys.home) {
+				self.move_selection(ScrollType::Home);
+			} else if key_match(e, self.key_config.keys.end) {
+				self.move_selection(ScrollType::End);
+			} else if key_match(e, self.key_config.keys.enter) {
+				if let Some(worktree) = self.selected_entry() {
+					if worktree.is_valid && !worktree.is_current {
+						self.queue.push(InternalEvent::OpenRepo {
+							path: worktree.path.clone(),
+						});
+					}
+				}
+			} else if key_match(e, self.key_config.keys.worktree_add)
+			{
+				self.open_add_input()?;
+			} else if key_match(e, self.key_config.keys.worktree_lock)
+			{
+				self.toggle_lock()?;
+			} else if key_match(
+				e,
+				self.key_config.keys.worktree_prune,
+			) {
+				try_or_popup!(
+					self,
+					"prune worktrees:",
+					prune_worktrees(&self.repo.borrow())
+				);
+				self.update_worktrees()?;
+			} else if key_match(
+				e,
+				self.key_config.keys.worktree_remove,
+			) {
+				self.remove_selected();
+			} else if key_match(
+				e,
+				self.key_config.keys.cmd_bar_toggle,
+			) {
+				//do not consume if its the more key
+				return Ok(EventState::NotConsumed);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl WorktreesListPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			worktrees: Vec::new(),
+			input: TextInputComponent::new(
+				env,
+				&strings::worktree_add_popup_title(&env.key_config),
+				&strings::worktree_add_popup_msg(&env.key_config),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			add_path: None,
+			visible: false,
+			current_height: Cell::new(0),
+			selection: 0,
+			scroll: VerticalScroll::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.show()?;
+		self.update_worktrees()?;
+
+		Ok(())
+	}
+
+	///
+	pub fn update_worktrees(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.worktrees = get_worktrees(&self.repo.borrow())?;
+			self.selection = self
+				.selection
+				.min(self.worktrees.len().saturating_sub(1));
+		}
+
+		Ok(())
+	}
+
+	fn selected_entry(&self) -> Option<&WorktreeInfo> {
+		self.worktrees.get(self.selection)
+	}
+
+	fn open_add_input(&mut self) -> Result<()> {
+		self.add_path = None;
+		self.input.set_title(strings::worktree_add_popup_title(
+			&self.key_config,
+		));
+		self.input.set_default_msg(strings::worktree_add_popup_msg(
+			&self.key_config,
+		));
+		self.input.clear();
+		self.input.show()
+	}
+
+	/// first asks for the path, then for the branch to check out
+	fn add(&mut self) {
+		let text = self.input.get_text().trim().to_string();
+
+		self.input.clear();
+
+		let Some(path) = self.add_path.take() else {
+			if text.is_empty() {
+				self.input.hide();
+			} else {
+				self.input.set_title(
+					strings::worktree_add_branch_popup_title(&text),
+				);
+				self.input.set_default_msg(
+					strings::worktree_add_branch_popup_msg(),
+				);
+				self.add_path = Some(text);
+			}
+			return;
+		};
+
+		self.input.hide();
+
+		let branch = Some(text.as_str()).filter(|b| !b.is_empty());
+
+		try_or_popup!(
+			self,
+			"add worktree:",
+			add_worktree(
+				&self.repo.borrow(),
+				Path::new(&path),
+				branch
+			)
+		);
+
+		try_or_popup!(
+			self,
+			"update worktrees:",
+			self.update_worktrees()
+		);
+
+		self.queue.push(InternalEvent::Update(
+			NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+		));
+	}
+
+	/// asks for confirmation, with a warning if the worktree has
+	/// changes that would be lost
+	fn remove_selected(&self) {
+		let Some(worktree) = self.selected_entry() else {
+			return;
+		};
+
+		if worktree.is_main
+			|| worktree.is_locked
+			|| worktree.is_current
+		{
+			return;
+		}
+
+		let clean =
+			is_worktree_clean(&self.repo.borrow(), &worktree.name);
+
+		match clean {
+			Ok(clean) => {
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::RemoveWorktree(
+						worktree.name.clone(),
+						!clean,
+					),
+				));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("remove worktree:\n{e}"),
+				));
+			}
+		}
+	}
+
+	fn toggle_lock(&mut self) -> Result<()> {
+		let Some(worktree) = self.selected_entry() else {
+			return Ok(());
+		};
+
+		if worktree.is_main {
+			return Ok(());
+		}
+
+		let name = worktree.name.clone();
+
+		if worktree.is_locked {
+			try_or_popup!(
+				self,
+				"unlock worktree:",
+				unlock_worktree(&self.repo.borrow(), &name)
+			);
+		} else {
+			try_or_popup!(
+				self,
+				"lock worktree:",
+				lock_worktree(&self.repo.borrow(), &name, None)
+			);
+		}
+
+		self.update_worktrees()
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let height = usize::from(self.current_height.get());
+		let max = self.worktrees.len().saturating_sub(1);
+
+		self.selection = match scroll {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			ScrollType::PageUp => {
+				self.selection.saturating_sub(height)
+			}
+			ScrollType::PageDown => {
+				self.selection.saturating_add(height)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max,
+		}
+		.min(max);
+	}
+
+	fn get_text(&self, width: usize, height: usize) -> Text {
+		const FLAGS_LENGTH: usize = 3;
+
+		let name_length = width.saturating_sub(FLAGS_LENGTH);
+
+		let lines = self
+			.worktrees
+			.iter()
+			.enumerate()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.map(|(idx, worktree)| {
+				let selected = idx == self.selection;
+
+				let flags = format!(
+					"{}{} ",
+					if worktree.is_current { '*' } else { ' ' },
+					if worktree.is_locked {
+						'L'
+					} else if !worktree.is_valid {
+						'!'
+					} else {
+						' '
+					},
+				);
+
+				let (name, _) =
+					worktree.name.unicode_truncate(name_length);
+
+				Line::from(vec![
+					Span::styled(
+						flags,
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						format!("{name:name_length$}"),
+						self.theme.text(worktree.is_valid, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		Text::from(lines)
+	}
+
+	fn get_info_text(&self) -> Text {
+		self.selected_entry()
+			.map_or_else(Text::default, |worktree| {
+				let title = |title: &'static str| {
+					Line::from(vec![Span::styled(
+						title,
+						self.theme.text(false, false),
+					)])
+				};
+				let value = |value: String| {
+					Line::from(vec![Span::styled(
+						value,
+						self.theme.text(true, false),
+					)])
+				};
+
+				let mut lines = vec![
+					title("Path:"),
+					value(
+						worktree.path.to_string_lossy().to_string(),
+					),
+					Line::from(vec![]),
+					title("Branch:"),
+					value(worktree.branch.clone().unwrap_or_else(
+						|| String::from("(detached)"),
+					)),
+				];
+
+				if worktree.is_locked {
+					lines.extend([
+						Line::from(vec![]),
+						title("Locked:"),
+						value(
+							worktree
+								.lock_reason
+								.clone()
+								.unwrap_or_default(),
+						),
+					]);
+				}
+
+				if !worktree.is_valid {
+					lines.extend([
+						Line::from(vec![]),
+						title("Status:"),
+						value(String::from(
+							"working directory missing",
+						)),
+					]);
+				}
+
+				Text::from(lines)
+			})
+	}
+
+	fn draw_list(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection,
+			self.worktrees.len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(
+				self.get_text(r.width as usize, height_in_lines),
+			)
+			.block(Block::default().borders(Borders::RIGHT))
+			.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+
+	fn draw_info(&self, f: &mut Frame, r: Rect) {
+		f.render_widget(
+			Paragraph::new(self.get_info_text())
+				.alignment(Alignment::Left),
+			r,
+		);
+	}
+}
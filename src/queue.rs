@@ -8,7 +8,9 @@ use crate::{
 };
 use asyncgit::{
 	sync::{
-		diff::DiffLinePosition, CommitId, LogFilterSearchOptions,
+		diff::{DiffLinePosition, DiffOptions},
+		CommitId, DiffToolTarget, LogFilterSearchOptions,
+		RebaseState, StashFile,
 	},
 	PushType,
 };
@@ -49,11 +51,17 @@ pub enum Action {
 	DeleteTag(String),
 	DeleteRemoteTag(String, String),
 	ForcePush(String, bool),
-	PullMerge { incoming: usize, rebase: bool },
+	PullMerge {
+		incoming: usize,
+		rebase: bool,
+	},
 	AbortMerge,
 	AbortRebase,
 	AbortRevert,
+	AbortCherryPick,
 	UndoCommit,
+	/// name of the worktree and whether to discard its changes
+	RemoveWorktree(String, bool),
 }
 
 #[derive(Debug)]
@@ -70,6 +78,15 @@ pub enum StackablePopupOpen {
 	CompareCommits(InspectCommitOpen),
 }
 
+/// external tool to open a file in
+#[derive(Clone)]
+pub enum ExternalTool {
+	/// `diff.tool` comparing two versions of the file
+	Diff(String, DiffToolTarget),
+	/// `merge.tool` resolving the conflicted file
+	Merge(String),
+}
+
 pub enum AppTabs {
 	Status,
 	Log,
@@ -94,8 +111,12 @@ pub enum InternalEvent {
 	StatusLastFileMoved,
 	/// open commit msg input
 	OpenCommit,
+	/// open commit popup prefilled with this message
+	OpenCommitWithMessage(String),
 	///
-	PopupStashing(StashingOptions),
+	/// options, marked files (stashes all changes if empty) and the
+	/// diff options the marked hunks are based on
+	PopupStashing(StashingOptions, Vec<StashFile>, DiffOptions),
 	///
 	TabSwitchStatus,
 	///
@@ -109,11 +130,17 @@ pub enum InternalEvent {
 	///
 	CreateBranch,
 	///
+	StashBranch(CommitId),
+	///
+	ExportStash(CommitId),
+	///
 	RenameBranch(String, String),
 	///
 	SelectBranch,
 	///
 	OpenExternalEditor(Option<String>),
+	/// suspends the ui to run the external diff or merge tool
+	OpenExternalTool(ExternalTool),
 	///
 	Push(String, PushType, bool, bool),
 	///
@@ -139,6 +166,8 @@ pub enum InternalEvent {
 	///
 	ViewSubmodules,
 	///
+	ViewWorktrees,
+	///
 	OpenRepo { path: PathBuf },
 	///
 	OpenResetPopup(CommitId),
@@ -146,6 +175,22 @@ pub enum InternalEvent {
 	RewordCommit(CommitId),
 	///
 	CommitSearch(LogFilterSearchOptions),
+	///
+	OpenInteractiveRebase(CommitId),
+	///
+	OpenReflog,
+	/// add/remove the reference from the refs shown in the log
+	ToggleLogRef(String),
+	/// show the commits of the revspec in the log (`HEAD` if empty)
+	LogRevspec(String),
+	///
+	OpenLogRevspec,
+	///
+	OpenAbsorb,
+	///
+	OpenApplyPatch,
+	/// a rebase was started or continued and stopped/finished in this state
+	RebaseProgressed(RebaseState),
 }
 
 /// single threaded simple queue for components to communicate with each other
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use asyncgit::sync::CommitId;
+use asyncgit::sync::{CommitId, LogRefs, LogWalkerOptions};
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
@@ -30,12 +30,14 @@ pub static PUSH_TAGS_STATES_PUSHING: &str = "pushing";
 pub static PUSH_TAGS_STATES_DONE: &str = "done";
 
 pub static POPUP_TITLE_SUBMODULES: &str = "Submodules";
+pub static POPUP_TITLE_WORKTREES: &str = "Worktrees";
 pub static POPUP_TITLE_FUZZY_FIND: &str = "Fuzzy Finder";
 pub static POPUP_TITLE_LOG_SEARCH: &str = "Search";
 
 pub static POPUP_FAIL_COPY: &str = "Failed to copy text";
 pub static POPUP_SUCCESS_COPY: &str = "Copied Text";
 pub static POPUP_COMMIT_SHA_INVALID: &str = "Invalid commit sha";
+pub static POPUP_REGEX_INVALID: &str = "Invalid regex";
 
 pub mod symbol {
 	pub const CHECKMARK: &str = "\u{2713}"; //✓
@@ -59,6 +61,9 @@ pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
 	"Diff: ".to_string()
 }
+pub fn title_conflict(_key_config: &SharedKeyConfig) -> String {
+	"Conflict (ours | theirs): ".to_string()
+}
 pub fn title_index(_key_config: &SharedKeyConfig) -> String {
 	"Staged Changes".to_string()
 }
@@ -110,6 +115,50 @@ pub fn commit_title() -> String {
 pub fn commit_reword_title() -> String {
 	"Reword Commit".to_string()
 }
+pub fn commit_title_rebase_reword() -> String {
+	"Reword Commit (Rebase)".to_string()
+}
+pub fn rebase_interactive_title(base: CommitId) -> String {
+	format!("Interactive Rebase onto {}", base.get_short_string())
+}
+pub fn reflog_title(
+	reference: &str,
+	idx: usize,
+	count: usize,
+) -> String {
+	format!("Reflog: {reference} ({}/{count})", idx + 1)
+}
+pub fn absorb_title(absorbable: usize, count: usize) -> String {
+	format!("Absorb staged hunks ({absorbable}/{count} absorbable)")
+}
+pub fn absorb_target_ambiguous() -> String {
+	"ambiguous: lines of multiple commits".to_string()
+}
+pub fn absorb_target_pushed(id: CommitId) -> String {
+	format!("{} already pushed", id.get_short_string())
+}
+pub fn absorb_target_none() -> String {
+	"no target commit".to_string()
+}
+pub fn rebase_interactive_stopped_edit(id: CommitId) -> String {
+	format!(
+		"Stopped at {} for amending.\nStage your changes and continue the rebase when done.",
+		id.get_short_string()
+	)
+}
+
+pub fn rebase_interactive_stopped_split(id: CommitId) -> String {
+	format!(
+		"Stopped to split {}, its changes are unstaged now.\nStage and commit them in parts and continue the rebase when done.",
+		id.get_short_string()
+	)
+}
+
+pub fn msg_merge_tool_unresolved(path: &str) -> String {
+	format!(
+		"{path} seems unchanged or the merge tool failed,\nit stays conflicted."
+	)
+}
 
 pub fn commit_title_merge() -> String {
 	"Commit (Merge)".to_string()
@@ -190,6 +239,9 @@ pub fn confirm_msg_revertchanges() -> String {
 	"This will revert all uncommitted changes. Are you sure?"
 		.to_string()
 }
+pub fn confirm_title_abortcherrypick() -> String {
+	"Abort cherry-pick?".to_string()
+}
 pub fn confirm_title_abortrebase() -> String {
 	"Abort rebase?".to_string()
 }
@@ -268,6 +320,23 @@ pub fn confirm_msg_delete_tag(
 ) -> String {
 	format!("Confirm deleting Tag: '{tag_name}' ?")
 }
+pub fn confirm_title_remove_worktree() -> String {
+	"Remove Worktree".to_string()
+}
+pub fn confirm_msg_remove_worktree(
+	name: &str,
+	dirty: bool,
+) -> String {
+	if dirty {
+		format!(
+			"Worktree '{name}' has uncommitted or untracked changes.\nConfirm removing it including its working directory and these changes?"
+		)
+	} else {
+		format!(
+			"Confirm removing worktree '{name}' including its working directory?"
+		)
+	}
+}
 pub fn confirm_title_delete_tag_remote() -> String {
 	"Delete Tag (remote)".to_string()
 }
@@ -290,12 +359,83 @@ pub fn confirm_msg_force_push(
 pub fn log_title(_key_config: &SharedKeyConfig) -> String {
 	"Commit".to_string()
 }
+fn log_walker_modes(options: LogWalkerOptions) -> Vec<String> {
+	[
+		(options.first_parent, "first-parent"),
+		(options.no_merges, "no merges"),
+	]
+	.into_iter()
+	.filter(|(enabled, _)| *enabled)
+	.map(|(_, mode)| mode.to_string())
+	.collect()
+}
+pub fn log_mode_title(
+	refs: &LogRefs,
+	options: LogWalkerOptions,
+) -> String {
+	let mut modes = match refs {
+		LogRefs::Head => Vec::new(),
+		LogRefs::All => vec!["all refs".to_string()],
+		LogRefs::Local => vec!["local branches".to_string()],
+		LogRefs::Remotes => vec!["remote branches".to_string()],
+		LogRefs::Revspec(revspec) => vec![revspec.clone()],
+		LogRefs::Selected(refs) => refs
+			.iter()
+			.map(|r| {
+				r.strip_prefix("refs/heads/")
+					.or_else(|| r.strip_prefix("refs/remotes/"))
+					.unwrap_or(r)
+					.to_string()
+			})
+			.collect(),
+	};
+	modes.extend(log_walker_modes(options));
+
+	if modes.is_empty() {
+		"Commit".to_string()
+	} else {
+		format!("Commit [{}]", modes.join(", "))
+	}
+}
+pub fn log_revspec_popup_title() -> String {
+	"Log Revspec".to_string()
+}
+pub fn log_revspec_popup_msg() -> String {
+	"e.g. main..feature, v1.0...HEAD, HEAD~50 (empty for HEAD)"
+		.to_string()
+}
+pub fn apply_patch_popup_title(as_commits: bool) -> String {
+	if as_commits {
+		"Apply Patch (as commits)".to_string()
+	} else {
+		"Apply Patch (to working tree)".to_string()
+	}
+}
+pub fn apply_patch_popup_msg() -> String {
+	"path of a patch or mbox file".to_string()
+}
+pub fn export_stash_popup_title() -> String {
+	"Export Stash".to_string()
+}
+pub fn export_stash_popup_msg() -> String {
+	"path of the patch file to write".to_string()
+}
 pub fn file_log_title(
 	file_path: &str,
 	selected: usize,
 	revisions: usize,
+	options: LogWalkerOptions,
 ) -> String {
-	format!("Revisions of '{file_path}' ({selected}/{revisions})")
+	let modes = log_walker_modes(options);
+
+	if modes.is_empty() {
+		format!("Revisions of '{file_path}' ({selected}/{revisions})")
+	} else {
+		format!(
+			"Revisions of '{file_path}' ({selected}/{revisions}) [{}]",
+			modes.join(", ")
+		)
+	}
 }
 pub fn blame_title(_key_config: &SharedKeyConfig) -> String {
 	"Blame".to_string()
@@ -334,11 +474,33 @@ pub fn create_branch_popup_title(
 ) -> String {
 	"Branch".to_string()
 }
+pub fn stash_branch_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Branch from stash".to_string()
+}
 pub fn create_branch_popup_msg(
 	_key_config: &SharedKeyConfig,
 ) -> String {
 	"type branch name".to_string()
 }
+pub fn worktree_add_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Add Worktree".to_string()
+}
+pub fn worktree_add_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"path (relative to the working directory)".to_string()
+}
+pub fn worktree_add_branch_popup_title(path: &str) -> String {
+	format!("Add Worktree '{path}': Branch")
+}
+pub fn worktree_add_branch_popup_msg() -> String {
+	"existing local branch (empty: named like the worktree)"
+		.to_string()
+}
 pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
 	"Username".to_string()
 }
@@ -636,6 +798,66 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
+	pub fn conflict_region_next_prev(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next/Prev conflict [{}{}]",
+				key_config.get_hint(key_config.keys.diff_hunk_next),
+				key_config.get_hint(key_config.keys.diff_hunk_prev),
+			),
+			"move cursor to next or previous conflict",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn conflict_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ours/Theirs/Both [{}{}{}]",
+				key_config
+					.get_hint(key_config.keys.conflict_pick_ours),
+				key_config
+					.get_hint(key_config.keys.conflict_pick_theirs),
+				key_config
+					.get_hint(key_config.keys.conflict_pick_both),
+			),
+			"resolve conflict using our, their or both versions",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn conflict_pick_file(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"File Ours/Theirs/Both [{}{}{}]",
+				key_config
+					.get_hint(key_config.keys.conflict_pick_ours_file),
+				key_config
+					.get_hint(key_config.keys.conflict_pick_theirs_file),
+				key_config
+					.get_hint(key_config.keys.conflict_pick_both_file),
+			),
+			"resolve all conflicts of the file using our, their or both versions",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn conflict_stage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage resolved [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"write resolved file and stage it",
+			CMD_GROUP_DIFF,
+		)
+	}
 	pub fn diff_hunk_prev(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -648,6 +870,20 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
+	pub fn diff_toggle_side_by_side(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Side-by-side [{}]",
+				key_config.get_hint(
+					key_config.keys.diff_toggle_side_by_side
+				),
+			),
+			"toggle between unified and side-by-side diff",
+			CMD_GROUP_DIFF,
+		)
+	}
 	pub fn diff_home_end(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -870,6 +1106,131 @@ pub mod commands {
 		)
 	}
 
+	pub fn open_absorb(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Absorb [{}]",
+				key_config.get_hint(key_config.keys.open_absorb),
+			),
+			"absorb staged hunks into the commits that last changed those lines",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn absorb_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Create fixups [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"commit absorbable hunks as 'fixup!' commits of their targets",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn absorb_autosquash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rewrite [{}]",
+				key_config.get_hint(key_config.keys.log_autosquash),
+			),
+			"create 'fixup!' commits and autosquash them right away",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn view_worktrees(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Worktrees [{}]",
+				key_config.get_hint(key_config.keys.view_worktrees),
+			),
+			"open worktree view",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn open_worktree(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Open [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"open worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn add_worktree(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add [{}]",
+				key_config.get_hint(key_config.keys.worktree_add),
+			),
+			"add worktree checking out a chosen branch or the branch named like it",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn add_worktree_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add worktree [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"add worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn lock_worktree(
+		key_config: &SharedKeyConfig,
+		locked: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if locked { "Unlock" } else { "Lock" },
+				key_config.get_hint(key_config.keys.worktree_lock),
+			),
+			"lock or unlock worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn prune_worktrees(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Prune [{}]",
+				key_config.get_hint(key_config.keys.worktree_prune),
+			),
+			"prune worktrees whose directory is gone",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn remove_worktree(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remove [{}]",
+				key_config.get_hint(key_config.keys.worktree_remove),
+			),
+			"remove worktree and its working directory",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn continue_rebase(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -883,6 +1244,32 @@ pub mod commands {
 		)
 	}
 
+	pub fn continue_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"commit resolved cherry-pick and pick remaining commits",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn abort_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"abort ongoing cherry-pick",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn abort_rebase(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1036,6 +1423,30 @@ pub mod commands {
 			CMD_GROUP_CHANGES,
 		)
 	}
+	pub fn open_difftool(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Difftool [{}]",
+				key_config.get_hint(key_config.keys.external_tool),
+			),
+			"compare the selected file in the configured diff.tool",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn open_mergetool(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Mergetool [{}]",
+				key_config.get_hint(key_config.keys.external_tool),
+			),
+			"resolve the selected conflicted file in the configured merge.tool",
+			CMD_GROUP_CHANGES,
+		)
+	}
 	pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1172,6 +1583,32 @@ pub mod commands {
 			CMD_GROUP_STASHING,
 		)
 	}
+	pub fn stashing_mark_item(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Mark [{}]",
+				key_config
+					.get_hint(key_config.keys.stashing_mark_item),
+			),
+			"mark file or folder to stash only marked files",
+			CMD_GROUP_STASHING,
+		)
+	}
+	pub fn stashing_mark_hunk(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Mark hunk [{}]",
+				key_config
+					.get_hint(key_config.keys.stashing_mark_item),
+			),
+			"mark hunk to stash only marked files and hunks",
+			CMD_GROUP_STASHING,
+		)
+	}
 	pub fn stashing_toggle_untracked(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1252,6 +1689,30 @@ pub mod commands {
 			CMD_GROUP_STASHES,
 		)
 	}
+	pub fn stashlist_branch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Branch [{}]",
+				key_config.get_hint(key_config.keys.stash_branch),
+			),
+			"create a branch at the stash base and pop the stash onto it",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stashlist_export(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Export [{}]",
+				key_config.get_hint(key_config.keys.stash_export),
+			),
+			"write stash as patch file",
+			CMD_GROUP_STASHES,
+		)
+	}
 	pub fn log_details_toggle(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1300,6 +1761,52 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn blame_parent(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Blame parent [{}]",
+				key_config.get_hint(key_config.keys.blame_parent),
+			),
+			"blame the file at the parent of the selected line's commit",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn blame_back(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Back [{}]",
+				key_config.get_hint(key_config.keys.move_left),
+			),
+			"return to the previous blame",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn blame_ignore_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ignore commit [{}]",
+				key_config
+					.get_hint(key_config.keys.blame_ignore_commit),
+			),
+			"attribute lines of the selected commit to earlier commits",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn blame_clear_ignored(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clear ignored [{}]",
+				key_config
+					.get_hint(key_config.keys.blame_clear_ignored),
+			),
+			"stop ignoring commits ignored in this session",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn log_tag_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1375,6 +1882,279 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_split_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Split [{}]",
+				key_config.get_hint(key_config.keys.log_split_commit),
+			),
+			"split commit into multiple commits",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_fixup_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fixup [{}]",
+				key_config.get_hint(key_config.keys.log_fixup_commit),
+			),
+			"commit staged changes as 'fixup!' of selected commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_squash_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Squash [{}]",
+				key_config
+					.get_hint(key_config.keys.log_squash_commit),
+			),
+			"commit staged changes as 'squash!' of selected commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_amend_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Amend [{}]",
+				key_config.get_hint(key_config.keys.log_amend_commit),
+			),
+			"commit staged changes as 'amend!' of selected commit (allows to change its message)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_autosquash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Autosquash [{}]",
+				key_config.get_hint(key_config.keys.log_autosquash),
+			),
+			"meld all fixup!/squash!/amend! commits into their targets",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_toggle_refs(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Refs [{}]",
+				key_config.get_hint(key_config.keys.log_toggle_refs),
+			),
+			"cycle showing commits of HEAD, all refs, local or remote branches",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_toggle_first_parent(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"First-parent [{}]",
+				key_config.get_hint(
+					key_config.keys.log_toggle_first_parent
+				),
+			),
+			"toggle following only the first parent of merge commits",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_toggle_no_merges(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"No merges [{}]",
+				key_config
+					.get_hint(key_config.keys.log_toggle_no_merges),
+			),
+			"toggle hiding merge commits",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_revspec(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Revspec [{}]",
+				key_config.get_hint(key_config.keys.log_revspec),
+			),
+			"show the commits of a revspec like main..feature",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_revspec_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Show [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"show the commits of the revspec in the log",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_export_patch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Export patch [{}]",
+				key_config.get_hint(key_config.keys.log_export_patch),
+			),
+			"export selected or marked commits as patch files (like format-patch)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_apply_patch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply patch [{}]",
+				key_config.get_hint(key_config.keys.log_apply_patch),
+			),
+			"apply a patch or mbox file onto HEAD",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn apply_patch_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"apply the patch file",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn export_stash_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Export [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"write the stash to the patch file",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn apply_patch_toggle_mode(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Toggle mode [{}]",
+				key_config.get_hint(
+					key_config.keys.apply_patch_toggle_mode
+				),
+			),
+			"apply to the working tree or as commits (like git am)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.cherry_pick),
+			),
+			"cherry-pick selected or marked commits onto HEAD",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_rebase_interactive(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rebase [{}]",
+				key_config
+					.get_hint(key_config.keys.log_rebase_interactive),
+			),
+			"interactively rebase commits after the selected one",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn open_reflog(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reflog [{}]",
+				key_config.get_hint(key_config.keys.open_reflog),
+			),
+			"open reflog of HEAD and branches",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn reflog_switch_ref(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Switch ref [{}]",
+				key_config.get_hint(key_config.keys.tab_toggle),
+			),
+			"show reflog of next reference",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn rebase_interactive_move(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Move [{}{}]",
+				key_config.get_hint(key_config.keys.shift_up),
+				key_config.get_hint(key_config.keys.shift_down),
+			),
+			"move selected commit up or down",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn rebase_interactive_action(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pick/Reword/Edit/Squash/Fixup/Drop [{}{}{}{}{}{}]",
+				key_config.get_hint(key_config.keys.rebase_pick),
+				key_config.get_hint(key_config.keys.rebase_reword),
+				key_config.get_hint(key_config.keys.rebase_edit),
+				key_config.get_hint(key_config.keys.rebase_squash),
+				key_config.get_hint(key_config.keys.rebase_fixup),
+				key_config.get_hint(key_config.keys.rebase_drop),
+			),
+			"set rebase action of selected commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn rebase_interactive_start(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Start [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"start interactive rebase",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn log_find_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1552,6 +2332,32 @@ pub mod commands {
 		)
 	}
 
+	pub fn branch_popup_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.cherry_pick),
+			),
+			"cherry-pick head commit of selected branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn branch_popup_toggle_log(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Show in log [{}]",
+				key_config.get_hint(key_config.keys.branch_toggle_log),
+			),
+			"toggle showing the commits of the selected branch in the log",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
 	pub fn compare_with_head(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
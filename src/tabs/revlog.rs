@@ -6,8 +6,9 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	popups::{FileTreeOpen, InspectCommitOpen},
-	queue::{InternalEvent, Queue, StackablePopupOpen},
+	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
 	strings::{self, order},
 	try_or_popup,
 	ui::style::{SharedTheme, Theme},
@@ -16,15 +17,16 @@ use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
-		self, filter_commit_by_search, CommitId, LogFilterSearch,
-		LogFilterSearchOptions, RepoPathRef,
+		self, filter_commit_by_search, CommitId, FixupKind,
+		LogFilterSearch, LogFilterSearchOptions, LogRefs,
+		RepoPathRef, RepoState,
 	},
 	AsyncBranchesJob, AsyncCommitFilterJob, AsyncGitNotification,
 	AsyncLog, AsyncTags, CommitFilesParams, FetchStatus,
 	ProgressPercent,
 };
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use indexmap::IndexSet;
 use ratatui::{
 	layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -33,6 +35,7 @@ use ratatui::{
 	Frame,
 };
 use std::{
+	path::Path,
 	rc::Rc,
 	sync::{
 		atomic::{AtomicBool, Ordering},
@@ -72,6 +75,7 @@ pub struct Revlog {
 	queue: Queue,
 	visible: bool,
 	key_config: SharedKeyConfig,
+	options: SharedOptions,
 	sender: Sender<AsyncGitNotification>,
 	theme: SharedTheme,
 }
@@ -105,6 +109,7 @@ impl Revlog {
 			),
 			visible: false,
 			key_config: env.key_config.clone(),
+			options: env.options.clone(),
 			sender: env.sender_git.clone(),
 			theme: env.theme.clone(),
 		}
@@ -127,12 +132,20 @@ impl Revlog {
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
+			let options = self.options.borrow().log_options();
+			self.git_log.set_options(options)?;
+			self.list.set_title(&strings::log_mode_title(
+				self.git_log.refs(),
+				options,
+			));
+
 			if self.git_log.fetch()? == FetchStatus::Started {
 				self.list.clear();
 			}
 
 			self.list
 				.refresh_extend_data(self.git_log.extract_items()?);
+			self.list.extend_graph(self.git_log.extract_graph()?);
 
 			self.git_tags.request(Duration::from_secs(3), false)?;
 
@@ -229,6 +242,122 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// marked commits (oldest first) or the selected one
+	fn marked_or_selected_commits(&self) -> Vec<CommitId> {
+		if self.list.marked_count() > 0 {
+			let mut marked = self.list.marked().to_vec();
+			// oldest first
+			marked.sort_by_key(|(idx, _)| std::cmp::Reverse(*idx));
+			marked.into_iter().map(|(_, id)| id).collect()
+		} else {
+			self.selected_commit().into_iter().collect()
+		}
+	}
+
+	fn cherry_pick(&mut self) -> Result<()> {
+		let commits = self.marked_or_selected_commits();
+
+		if commits.is_empty() {
+			return Ok(());
+		}
+
+		sync::cherry_pick(&self.repo.borrow(), &commits)?;
+
+		self.list.clear_marked();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		if sync::repo_state(&self.repo.borrow())? != RepoState::Clean
+		{
+			self.queue.push(InternalEvent::TabSwitchStatus);
+		}
+
+		Ok(())
+	}
+
+	/// writes the selected or marked commits (oldest first) as patch
+	/// files into the working directory
+	fn export_patches(&self) -> Result<()> {
+		let commits = self.marked_or_selected_commits();
+
+		if commits.is_empty() {
+			return Ok(());
+		}
+
+		let files = sync::export_patches(
+			&self.repo.borrow(),
+			&commits,
+			Path::new(""),
+		)?;
+
+		self.queue.push(InternalEvent::ShowInfoMsg(format!(
+			"patches exported to:\n{}",
+			files
+				.iter()
+				.map(|file| file.display().to_string())
+				.collect::<Vec<_>>()
+				.join("\n")
+		)));
+
+		Ok(())
+	}
+
+	fn split_commit(&self) -> Result<()> {
+		if let Some(id) = self.selected_commit() {
+			let state = sync::split_commit(&self.repo.borrow(), id)?;
+
+			self.queue.push(InternalEvent::RebaseProgressed(state));
+		}
+
+		Ok(())
+	}
+
+	fn fixup_commit(&self, kind: FixupKind) -> Result<()> {
+		if let Some(id) = self.selected_commit() {
+			if kind == FixupKind::Fixup {
+				sync::commit_fixup(&self.repo.borrow(), id, kind)?;
+
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			} else {
+				let msg = sync::fixup_message(
+					&self.repo.borrow(),
+					id,
+					kind,
+				)?;
+
+				self.queue
+					.push(InternalEvent::OpenCommitWithMessage(msg));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn fixup_kind(&self, k: &KeyEvent) -> Option<FixupKind> {
+		if self.is_search_pending() {
+			None
+		} else if key_match(k, self.key_config.keys.log_fixup_commit)
+		{
+			Some(FixupKind::Fixup)
+		} else if key_match(k, self.key_config.keys.log_squash_commit)
+		{
+			Some(FixupKind::Squash)
+		} else if key_match(k, self.key_config.keys.log_amend_commit)
+		{
+			Some(FixupKind::Amend)
+		} else {
+			None
+		}
+	}
+
+	fn autosquash(&self) -> Result<()> {
+		let state = sync::autosquash(&self.repo.borrow())?;
+
+		self.queue.push(InternalEvent::RebaseProgressed(state));
+
+		Ok(())
+	}
+
 	fn inspect_commit(&self) {
 		if let Some(commit_id) = self.selected_commit() {
 			let tags = self.selected_commit_tags(&Some(commit_id));
@@ -240,6 +369,96 @@ impl Revlog {
 		}
 	}
 
+	/// references shown in addition to `HEAD`, see `toggle_log_ref`
+	pub fn selected_log_refs(&self) -> Vec<String> {
+		match self.git_log.refs() {
+			LogRefs::Selected(refs) => refs.clone(),
+			_ => Vec::new(),
+		}
+	}
+
+	/// shows the commits of `reference` in the log (or stops showing
+	/// them if they already are)
+	pub fn toggle_log_ref(
+		&mut self,
+		reference: String,
+	) -> Result<()> {
+		let mut refs = self.selected_log_refs();
+
+		if let Some(idx) = refs.iter().position(|r| *r == reference) {
+			refs.remove(idx);
+		} else {
+			refs.push(reference);
+		}
+
+		self.set_log_refs(if refs.is_empty() {
+			LogRefs::Head
+		} else {
+			LogRefs::Selected(refs)
+		})
+	}
+
+	/// revspec the log shows, empty if it shows refs
+	pub fn log_revspec(&self) -> String {
+		match self.git_log.refs() {
+			LogRefs::Revspec(revspec) => revspec.clone(),
+			_ => String::new(),
+		}
+	}
+
+	/// shows the commits of `revspec` (`HEAD` if empty)
+	pub fn set_log_revspec(&mut self, revspec: String) -> Result<()> {
+		self.set_log_refs(if revspec.is_empty() {
+			LogRefs::Head
+		} else {
+			LogRefs::Revspec(revspec)
+		})
+	}
+
+	fn cycle_log_refs(&mut self) -> Result<()> {
+		let refs = match self.git_log.refs() {
+			LogRefs::Head => LogRefs::All,
+			LogRefs::All => LogRefs::Local,
+			LogRefs::Local => LogRefs::Remotes,
+			LogRefs::Remotes
+			| LogRefs::Selected(_)
+			| LogRefs::Revspec(_) => LogRefs::Head,
+		};
+
+		self.set_log_refs(refs)
+	}
+
+	fn set_log_refs(&mut self, refs: LogRefs) -> Result<()> {
+		self.reset_search();
+		self.git_log.set_refs(refs)?;
+
+		self.update()
+	}
+
+	fn toggle_first_parent(&mut self) -> Result<()> {
+		self.reset_search();
+		self.options.borrow_mut().log_toggle_first_parent();
+
+		self.update()
+	}
+
+	fn toggle_no_merges(&mut self) -> Result<()> {
+		self.reset_search();
+		self.options.borrow_mut().log_toggle_no_merges();
+
+		self.update()
+	}
+
+	/// search results refer to the commits of the log before it is
+	/// walked differently
+	fn reset_search(&mut self) {
+		if self.is_search_pending() {
+			self.cancel_search();
+		}
+		self.search = LogSearch::Off;
+		self.list.set_highlighting(None);
+	}
+
 	pub fn search(&mut self, options: LogFilterSearchOptions) {
 		if !self.can_start_search() {
 			return;
@@ -540,6 +759,12 @@ impl Component for Revlog {
 				} else if key_match(k, self.key_config.keys.tags) {
 					self.queue.push(InternalEvent::Tags);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.open_reflog,
+				) {
+					self.queue.push(InternalEvent::OpenReflog);
+					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
 					self.key_config.keys.log_reset_commit,
@@ -568,6 +793,108 @@ impl Component for Revlog {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					k,
+					self.key_config.keys.cherry_pick,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"cherry-pick error:",
+						self.cherry_pick()
+					);
+
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_rebase_interactive,
+				) && !self.is_search_pending()
+				{
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::OpenInteractiveRebase(
+									id,
+								),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_split_commit,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"split commit error:",
+						self.split_commit()
+					);
+
+					return Ok(EventState::Consumed);
+				} else if let Some(kind) = self.fixup_kind(k) {
+					try_or_popup!(
+						self,
+						"fixup commit error:",
+						self.fixup_commit(kind)
+					);
+
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_autosquash,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"autosquash error:",
+						self.autosquash()
+					);
+
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_refs,
+				) {
+					self.cycle_log_refs()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_first_parent,
+				) {
+					self.toggle_first_parent()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_no_merges,
+				) {
+					self.toggle_no_merges()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_export_patch,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"export patch error:",
+						self.export_patches()
+					);
+
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_apply_patch,
+				) {
+					self.queue.push(InternalEvent::OpenApplyPatch);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_revspec,
+				) {
+					self.queue.push(InternalEvent::OpenLogRevspec);
+					return Ok(EventState::Consumed);
 				} else if key_match(k, self.key_config.keys.log_find)
 					&& self.can_start_search()
 				{
@@ -611,6 +938,7 @@ impl Component for Revlog {
 		Ok(EventState::NotConsumed)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -723,6 +1051,82 @@ impl Component for Revlog {
 			self.selected_commit().is_some(),
 			(self.visible && !self.is_search_pending()) || force_all,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::log_cherry_pick(&self.key_config),
+			self.selected_commit().is_some()
+				|| self.list.marked_count() > 0,
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_export_patch(&self.key_config),
+			self.selected_commit().is_some()
+				|| self.list.marked_count() > 0,
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_apply_patch(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_rebase_interactive(
+				&self.key_config,
+			),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_split_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_fixup_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_squash_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_amend_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_autosquash(&self.key_config),
+			true,
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_toggle_refs(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_toggle_first_parent(
+				&self.key_config,
+			),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_toggle_no_merges(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_revspec(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::open_reflog(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::log_find_commit(&self.key_config),
 			self.can_start_search(),
@@ -3,18 +3,21 @@ use crate::{
 	app::Environment,
 	components::{
 		command_pump, event_pump, visibility_blocking,
-		CommandBlocking, CommandInfo, Component, DrawableComponent,
-		EventState, StatusTreeComponent,
+		CommandBlocking, CommandInfo, Component, DiffComponent,
+		DrawableComponent, EventState, StatusTreeComponent,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{InternalEvent, Queue},
 	strings,
 	ui::style::SharedTheme,
+	AsyncNotification,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, status::StatusType, RepoPathRef},
-	AsyncGitNotification, AsyncStatus, StatusParams,
+	sync::{self, status::StatusType, RepoPathRef, StashFile},
+	AsyncDiff, AsyncGitNotification, AsyncStatus, DiffParams,
+	DiffType, StatusParams,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -22,7 +25,10 @@ use ratatui::{
 	text::{Line, Span},
 	widgets::{Block, Borders, Paragraph},
 };
-use std::borrow::Cow;
+use std::{
+	borrow::Cow,
+	collections::{HashMap, HashSet},
+};
 
 #[derive(Default, Clone, Copy, Debug)]
 pub struct StashingOptions {
@@ -33,26 +39,36 @@ pub struct StashingOptions {
 pub struct Stashing {
 	repo: RepoPathRef,
 	index: StatusTreeComponent,
+	diff: DiffComponent,
+	/// marked hunks (by header hash) per file
+	marked_hunks: HashMap<String, HashSet<u64>>,
 	visible: bool,
 	options: StashingOptions,
 	theme: SharedTheme,
 	git_status: AsyncStatus,
+	git_diff: AsyncDiff,
 	queue: Queue,
 	key_config: SharedKeyConfig,
+	app_options: SharedOptions,
 }
 
 impl Stashing {
-	accessors!(self, [index]);
+	accessors!(self, [index, diff]);
 
 	///
 	pub fn new(env: &Environment) -> Self {
+		let mut index = StatusTreeComponent::new(
+			env,
+			&strings::stashing_files_title(&env.key_config),
+			true,
+		);
+		index.enable_marking();
+
 		Self {
 			repo: env.repo.clone(),
-			index: StatusTreeComponent::new(
-				env,
-				&strings::stashing_files_title(&env.key_config),
-				true,
-			),
+			index,
+			diff: DiffComponent::new(env, true),
+			marked_hunks: HashMap::new(),
 			visible: false,
 			options: StashingOptions {
 				keep_index: false,
@@ -63,8 +79,13 @@ impl Stashing {
 				env.repo.borrow().clone(),
 				env.sender_git.clone(),
 			),
+			git_diff: AsyncDiff::new(
+				env.repo.borrow().clone(),
+				&env.sender_git,
+			),
 			queue: env.queue.clone(),
 			key_config: env.key_config.clone(),
+			app_options: env.options.clone(),
 		}
 	}
 
@@ -82,6 +103,13 @@ impl Stashing {
 	///
 	pub fn anything_pending(&self) -> bool {
 		self.git_status.is_pending()
+			|| self.git_diff.is_pending()
+			|| self.diff.any_work_pending()
+	}
+
+	///
+	pub fn update_syntax(&mut self, ev: AsyncNotification) {
+		self.diff.update_syntax(ev);
 	}
 
 	///
@@ -89,15 +117,126 @@ impl Stashing {
 		&mut self,
 		ev: AsyncGitNotification,
 	) -> Result<()> {
-		if self.is_visible() && ev == AsyncGitNotification::Status {
-			let status = self.git_status.last()?;
-			self.index.show()?;
-			self.index.update(&status.items)?;
+		if self.is_visible() {
+			if ev == AsyncGitNotification::Status {
+				let status = self.git_status.last()?;
+				self.index.show()?;
+				self.index.update(&status.items)?;
+
+				self.marked_hunks.retain(|path, _| {
+					status.items.iter().any(|item| &item.path == path)
+				});
+				self.update_diff()?;
+			} else if ev == AsyncGitNotification::Diff {
+				self.update_diff()?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// called when any tree component changed selection
+	pub fn update_diff(&mut self) -> Result<()> {
+		if !self.is_visible() {
+			return Ok(());
+		}
+
+		if let Some(file) = self.index.selection_file() {
+			let diff_params = DiffParams {
+				path: file.path.clone(),
+				diff_type: DiffType::WorkDir,
+				options: self.app_options.borrow().diff_options(),
+			};
+
+			if let Some((params, last)) = self.git_diff.last()? {
+				if params == diff_params {
+					// forget marks of hunks that changed
+					if let Some(hunks) =
+						self.marked_hunks.get_mut(&file.path)
+					{
+						hunks.retain(|hash| {
+							last.hunks
+								.iter()
+								.any(|hunk| hunk.header_hash == *hash)
+						});
+					}
+
+					self.diff.set_marked_hunks(
+						self.marked_hunks
+							.get(&file.path)
+							.cloned()
+							.unwrap_or_default(),
+					);
+					self.diff.update(file.path, false, last);
+					return Ok(());
+				}
+			}
+
+			self.git_diff.request(diff_params)?;
+			self.diff.clear(true);
+			return Ok(());
 		}
 
+		self.diff.clear(false);
+
 		Ok(())
 	}
 
+	fn can_focus_diff(&self) -> bool {
+		self.index.selection_file().is_some()
+	}
+
+	fn focus_diff(&mut self, focus: bool) {
+		self.index.focus(!focus);
+		self.diff.focus(focus);
+	}
+
+	/// marks the selected hunk of the diff, unmarks it if it is
+	/// marked already
+	fn toggle_hunk_mark(&mut self) {
+		let (Some(file), Some(hunk)) = (
+			self.index.selection_file(),
+			self.diff.selected_hunk_hash(),
+		) else {
+			return;
+		};
+
+		let hunks = self.marked_hunks.entry(file.path).or_default();
+		if !hunks.remove(&hunk) {
+			hunks.insert(hunk);
+		}
+
+		self.diff.set_marked_hunks(hunks.clone());
+	}
+
+	/// marked files and files with marked hunks, empty to stash
+	/// all changes
+	fn stash_files(&self) -> Vec<StashFile> {
+		let marked = self.index.marked_files();
+
+		let mut files = marked
+			.iter()
+			.map(|path| StashFile {
+				path: path.clone(),
+				hunks: None,
+			})
+			.collect::<Vec<_>>();
+
+		files.extend(
+			self.marked_hunks
+				.iter()
+				.filter(|(path, hunks)| {
+					!hunks.is_empty() && !marked.contains(path)
+				})
+				.map(|(path, hunks)| StashFile {
+					path: path.clone(),
+					hunks: Some(hunks.iter().copied().collect()),
+				}),
+		);
+
+		files
+	}
+
 	fn get_option_text(&self) -> Vec<Line> {
 		let bracket_open = Span::raw(Cow::from("["));
 		let bracket_close = Span::raw(Cow::from("]"));
@@ -141,7 +280,18 @@ impl DrawableComponent for Stashing {
 		let chunks = Layout::default()
 			.direction(Direction::Horizontal)
 			.constraints(
-				[Constraint::Min(1), Constraint::Length(22)].as_ref(),
+				if self.diff.focused() {
+					[
+						Constraint::Percentage(0),
+						Constraint::Percentage(100),
+					]
+				} else {
+					[
+						Constraint::Percentage(50),
+						Constraint::Percentage(50),
+					]
+				}
+				.as_ref(),
 			)
 			.split(rect);
 
@@ -162,6 +312,7 @@ impl DrawableComponent for Stashing {
 		);
 
 		self.index.draw(f, chunks[0])?;
+		self.diff.draw(f, right_chunks[1])?;
 
 		Ok(())
 	}
@@ -180,6 +331,24 @@ impl Component for Stashing {
 				self.components().as_slice(),
 			);
 
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_right(&self.key_config),
+				self.can_focus_diff(),
+				(self.visible && !self.diff.focused()) || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				(self.visible && self.diff.focused()) || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stashing_mark_hunk(
+					&self.key_config,
+				),
+				self.diff.selected_hunk_hash().is_some(),
+				(self.visible && self.diff.focused()) || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::stashing_save(&self.key_config),
 				self.visible && !self.index.is_empty(),
@@ -223,8 +392,32 @@ impl Component for Stashing {
 				{
 					self.queue.push(InternalEvent::PopupStashing(
 						self.options,
+						self.stash_files(),
+						self.app_options.borrow().diff_options(),
 					));
 
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.move_right,
+				) && !self.diff.focused()
+					&& self.can_focus_diff()
+				{
+					self.focus_diff(true);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.exit_popup,
+				) && self.diff.focused()
+				{
+					self.focus_diff(false);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.stashing_mark_item,
+				) && self.diff.focused()
+				{
+					self.toggle_hunk_mark();
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
@@ -267,6 +460,7 @@ impl Component for Stashing {
 			!config_untracked_files.include_none();
 
 		self.index.show()?;
+		self.focus_diff(false);
 		self.visible = true;
 		self.update()?;
 		Ok(())
@@ -82,6 +82,18 @@ impl StashList {
 		}
 	}
 
+	fn branch_stash(&mut self) {
+		if let Some(e) = self.list.selected_entry() {
+			self.queue.push(InternalEvent::StashBranch(e.id));
+		}
+	}
+
+	fn export_stash(&mut self) {
+		if let Some(e) = self.list.selected_entry() {
+			self.queue.push(InternalEvent::ExportStash(e.id));
+		}
+	}
+
 	fn inspect(&mut self) {
 		if let Some(e) = self.list.selected_entry() {
 			self.queue.push(InternalEvent::OpenPopup(
@@ -182,6 +194,16 @@ impl Component for StashList {
 				selection_valid,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_branch(&self.key_config),
+				selection_valid,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_export(&self.key_config),
+				selection_valid,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -214,6 +236,16 @@ impl Component for StashList {
 					self.key_config.keys.stash_open,
 				) {
 					self.inspect();
+				} else if key_match(
+					k,
+					self.key_config.keys.stash_branch,
+				) {
+					self.branch_stash();
+				} else if key_match(
+					k,
+					self.key_config.keys.stash_export,
+				) {
+					self.export_stash();
 				}
 			}
 		}
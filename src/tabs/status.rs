@@ -4,14 +4,15 @@ use crate::{
 	components::{
 		command_pump, event_pump, visibility_blocking,
 		ChangesComponent, CommandBlocking, CommandInfo, Component,
-		DiffComponent, DrawableComponent, EventState,
-		FileTreeItemKind,
+		ConflictComponent, DiffComponent, DrawableComponent,
+		EventState, FileTreeItemKind,
 	},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
 	strings, try_or_popup,
 	ui::style::Theme,
+	AsyncNotification,
 };
 use anyhow::Result;
 use asyncgit::{
@@ -21,7 +22,7 @@ use asyncgit::{
 	},
 	sync::{BranchCompare, CommitId},
 	AsyncDiff, AsyncGitNotification, AsyncStatus, DiffParams,
-	DiffType, PushType, StatusItem, StatusParams,
+	DiffType, PushType, StatusItem, StatusItemType, StatusParams,
 };
 use crossterm::event::Event;
 use itertools::Itertools;
@@ -70,9 +71,11 @@ pub struct Status {
 	index: ChangesComponent,
 	index_wd: ChangesComponent,
 	diff: DiffComponent,
+	conflict: ConflictComponent,
 	remotes: RemoteStatus,
 	git_diff: AsyncDiff,
 	git_state: RepoState,
+	rebase_stopped_for_commit: bool,
 	git_status_workdir: AsyncStatus,
 	git_status_stage: AsyncStatus,
 	git_branch_state: Option<BranchCompare>,
@@ -140,7 +143,11 @@ impl DrawableComponent for Status {
 
 		self.index_wd.draw(f, left_chunks[0])?;
 		self.index.draw(f, left_chunks[1])?;
-		self.diff.draw(f, chunks[1])?;
+		if self.conflict.is_active() {
+			self.conflict.draw(f, chunks[1])?;
+		} else {
+			self.diff.draw(f, chunks[1])?;
+		}
 		self.draw_branch_state(f, &left_chunks);
 
 		if repo_unclean {
@@ -152,7 +159,7 @@ impl DrawableComponent for Status {
 }
 
 impl Status {
-	accessors!(self, [index, index_wd, diff]);
+	accessors!(self, [index, index_wd, diff, conflict]);
 
 	///
 	pub fn new(env: &Environment) -> Self {
@@ -165,6 +172,7 @@ impl Status {
 				has_remote_for_push: false,
 			},
 			git_state: RepoState::Clean,
+			rebase_stopped_for_commit: false,
 			focus: Focus::WorkDir,
 			diff_target: DiffTarget::WorkingDir,
 			index_wd: ChangesComponent::new(
@@ -180,6 +188,7 @@ impl Status {
 				false,
 			),
 			diff: DiffComponent::new(env, false),
+			conflict: ConflictComponent::new(env),
 			git_diff: AsyncDiff::new(
 				repo_clone.clone(),
 				&env.sender_git,
@@ -280,6 +289,16 @@ impl Status {
 						.unwrap_or_default(),
 				)
 			}
+			RepoState::CherryPick => {
+				format!(
+					"Cherry-pick {}",
+					sync::cherry_pick_head(repo)
+						.ok()
+						.as_ref()
+						.map(CommitId::get_short_string)
+						.unwrap_or_default(),
+				)
+			}
 			_ => format!("{state:?}"),
 		}
 	}
@@ -336,21 +355,18 @@ impl Status {
 			match self.focus {
 				Focus::WorkDir => {
 					self.set_diff_target(DiffTarget::WorkingDir);
-					self.diff.focus(false);
 				}
 				Focus::Stage => {
 					self.set_diff_target(DiffTarget::Stage);
-					self.diff.focus(false);
 				}
 				Focus::Diff => {
 					self.index.focus(false);
 					self.index_wd.focus(false);
-
-					self.diff.focus(true);
 				}
 			};
 
 			self.update_diff()?;
+			self.update_diff_focus();
 
 			return Ok(true);
 		}
@@ -358,6 +374,16 @@ impl Status {
 		Ok(false)
 	}
 
+	/// focuses either the conflict view or the diff
+	/// depending on which one is shown
+	fn update_diff_focus(&mut self) {
+		let focus_on_diff = self.is_focus_on_diff();
+		let conflict = self.conflict.is_active();
+
+		self.diff.focus(focus_on_diff && !conflict);
+		self.conflict.focus(focus_on_diff && conflict);
+	}
+
 	fn set_diff_target(&mut self, target: DiffTarget) {
 		self.diff_target = target;
 		let is_stage = self.diff_target == DiffTarget::Stage;
@@ -380,6 +406,35 @@ impl Status {
 		None
 	}
 
+	/// path of the selected file if it is conflicted
+	fn selected_conflict(&self) -> Option<String> {
+		let idx = match self.diff_target {
+			DiffTarget::Stage => &self.index,
+			DiffTarget::WorkingDir => &self.index_wd,
+		};
+
+		idx.selection().and_then(|item| match item.kind {
+			FileTreeItemKind::File(i)
+				if i.status == StatusItemType::Conflicted =>
+			{
+				Some(i.path)
+			}
+			_ => None,
+		})
+	}
+
+	fn update_conflict(&mut self) {
+		let loaded = self
+			.selected_conflict()
+			.is_some_and(|path| self.conflict.update(&path).is_ok());
+
+		if !loaded {
+			self.conflict.clear();
+		}
+
+		self.update_diff_focus();
+	}
+
 	///
 	pub fn update(&mut self) -> Result<()> {
 		self.git_branch_name.lookup().map(Some).unwrap_or(None);
@@ -400,6 +455,11 @@ impl Status {
 
 			self.git_state = sync::repo_state(&self.repo.borrow())
 				.unwrap_or(RepoState::Clean);
+			self.rebase_stopped_for_commit = self.pending_rebase()
+				&& sync::rebase_stopped_for_commit(
+					&self.repo.borrow(),
+				)
+				.unwrap_or_default();
 
 			self.branch_compare();
 		}
@@ -412,6 +472,12 @@ impl Status {
 		self.git_diff.is_pending()
 			|| self.git_status_stage.is_pending()
 			|| self.git_status_workdir.is_pending()
+			|| self.diff.any_work_pending()
+	}
+
+	///
+	pub fn update_syntax(&mut self, ev: AsyncNotification) {
+		self.diff.update_syntax(ev);
 	}
 
 	fn check_remotes(&mut self) {
@@ -482,6 +548,8 @@ impl Status {
 
 	///
 	pub fn update_diff(&mut self) -> Result<()> {
+		self.update_conflict();
+
 		if let Some((path, is_stage)) = self.selected_path() {
 			let diff_type = if is_stage {
 				DiffType::Stage
@@ -632,6 +700,10 @@ impl Status {
 		self.git_state == RepoState::Revert
 	}
 
+	fn pending_cherry_pick(&self) -> bool {
+		self.git_state == RepoState::CherryPick
+	}
+
 	pub fn revert_pending_state(&self) {
 		try_or_popup!(
 			self,
@@ -648,11 +720,32 @@ impl Status {
 		);
 	}
 
+	pub fn abort_cherry_pick(&self) {
+		try_or_popup!(
+			self,
+			"abort cherry-pick",
+			sync::abort_cherry_pick(&self.repo.borrow())
+		);
+	}
+
+	fn continue_cherry_pick(&self) {
+		try_or_popup!(
+			self,
+			"continue cherry-pick",
+			sync::continue_cherry_pick(&self.repo.borrow())
+		);
+	}
+
 	fn continue_rebase(&self) {
 		try_or_popup!(
 			self,
 			"continue rebase",
-			sync::continue_pending_rebase(&self.repo.borrow())
+			sync::continue_pending_rebase(&self.repo.borrow()).map(
+				|state| {
+					self.queue
+						.push(InternalEvent::RebaseProgressed(state));
+				}
+			)
 		);
 	}
 
@@ -703,11 +796,14 @@ impl Status {
 	fn can_commit(&self) -> bool {
 		self.index.focused()
 			&& !self.index.is_empty()
-			&& !self.pending_rebase()
+			&& (!self.pending_rebase()
+				|| self.rebase_stopped_for_commit)
+			&& !self.pending_cherry_pick()
 	}
 }
 
 impl Component for Status {
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -794,11 +890,39 @@ impl Component for Status {
 				self.pending_revert() || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::continue_cherry_pick(
+					&self.key_config,
+				),
+				true,
+				self.pending_cherry_pick() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::abort_cherry_pick(
+					&self.key_config,
+				),
+				true,
+				self.pending_cherry_pick() || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::view_submodules(&self.key_config),
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::open_absorb(&self.key_config),
+				!self.index.is_empty(),
+				self.visible || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::view_worktrees(&self.key_config),
+				true,
+				true,
+			));
 		}
 
 		self.commands_nav(out, force_all);
@@ -923,6 +1047,12 @@ impl Component for Status {
 								Action::AbortRevert,
 							),
 						);
+					} else if self.pending_cherry_pick() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::AbortCherryPick,
+							),
+						);
 					}
 
 					Ok(EventState::Consumed)
@@ -936,12 +1066,35 @@ impl Component for Status {
 						NeedsUpdate::ALL,
 					));
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.rebase_branch,
+				) && self.pending_cherry_pick()
+				{
+					self.continue_cherry_pick();
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL,
+					));
+					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.view_submodules,
 				) {
 					self.queue.push(InternalEvent::ViewSubmodules);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.view_worktrees,
+				) {
+					self.queue.push(InternalEvent::ViewWorktrees);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.open_absorb,
+				) && !self.index.is_empty()
+				{
+					self.queue.push(InternalEvent::OpenAbsorb);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
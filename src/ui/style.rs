@@ -20,6 +20,10 @@ pub struct Theme {
 	disabled_fg: Color,
 	diff_line_add: Color,
 	diff_line_delete: Color,
+	diff_word_add: Color,
+	diff_word_delete: Color,
+	diff_line_add_bg: Color,
+	diff_line_delete_bg: Color,
 	diff_file_added: Color,
 	diff_file_removed: Color,
 	diff_file_moved: Color,
@@ -34,6 +38,7 @@ pub struct Theme {
 	branch_fg: Color,
 	line_break: String,
 	block_title_focused: Color,
+	graph_lanes: Vec<Color>,
 }
 
 impl Theme {
@@ -196,6 +201,46 @@ impl Theme {
 		self.apply_select(style, selected)
 	}
 
+	/// style of the changed words within an added or deleted line
+	pub fn diff_word(
+		&self,
+		typ: DiffLineType,
+		selected: bool,
+	) -> Style {
+		let style = match typ {
+			DiffLineType::Add => Style::default()
+				.fg(Color::Black)
+				.bg(self.diff_word_add),
+			DiffLineType::Delete => Style::default()
+				.fg(Color::Black)
+				.bg(self.diff_word_delete),
+			DiffLineType::Header | DiffLineType::None => {
+				return self.diff_line(typ, selected);
+			}
+		};
+
+		if selected {
+			style.add_modifier(Modifier::BOLD)
+		} else {
+			style
+		}
+	}
+
+	/// syntax highlighting `style` on the background of the line type
+	pub const fn diff_syntax(
+		&self,
+		typ: DiffLineType,
+		style: Style,
+	) -> Style {
+		match typ {
+			DiffLineType::Add => style.bg(self.diff_line_add_bg),
+			DiffLineType::Delete => {
+				style.bg(self.diff_line_delete_bg)
+			}
+			DiffLineType::Header | DiffLineType::None => style,
+		}
+	}
+
 	pub fn text_danger(&self) -> Style {
 		Style::default().fg(self.danger_fg)
 	}
@@ -228,6 +273,21 @@ impl Theme {
 		Style::default().fg(self.disabled_fg)
 	}
 
+	pub fn commit_graph(&self, lane: u8, selected: bool) -> Style {
+		let style = if self.graph_lanes.is_empty() {
+			Style::default()
+		} else {
+			let idx = usize::from(lane) % self.graph_lanes.len();
+			Style::default().fg(self.graph_lanes[idx])
+		};
+
+		if selected {
+			style.bg(self.selection_bg)
+		} else {
+			style
+		}
+	}
+
 	pub fn log_marker(&self, selected: bool) -> Style {
 		let mut style = Style::default()
 			.fg(self.commit_author)
@@ -339,6 +399,10 @@ impl Default for Theme {
 			disabled_fg: Color::DarkGray,
 			diff_line_add: Color::Green,
 			diff_line_delete: Color::Red,
+			diff_word_add: Color::LightGreen,
+			diff_word_delete: Color::LightRed,
+			diff_line_add_bg: Color::Rgb(20, 50, 20),
+			diff_line_delete_bg: Color::Rgb(60, 20, 20),
 			diff_file_added: Color::LightGreen,
 			diff_file_removed: Color::LightRed,
 			diff_file_moved: Color::LightMagenta,
@@ -353,6 +417,14 @@ impl Default for Theme {
 			branch_fg: Color::LightYellow,
 			line_break: "¶".to_string(),
 			block_title_focused: Color::Reset,
+			graph_lanes: vec![
+				Color::LightBlue,
+				Color::LightGreen,
+				Color::LightYellow,
+				Color::LightMagenta,
+				Color::LightCyan,
+				Color::LightRed,
+			],
 		}
 	}
 }
@@ -1,6 +1,6 @@
 use asyncgit::{
 	asyncjob::{AsyncJob, RunParams},
-	ProgressPercent,
+	DiffLineType, FileDiff, ProgressPercent,
 };
 use once_cell::sync::Lazy;
 use ratatui::text::{Line, Span};
@@ -189,6 +189,107 @@ impl<'a> From<&'a SyntaxText> for ratatui::text::Text<'a> {
 	}
 }
 
+/// syntax styles and byte ranges of a line
+pub type SyntaxRanges = Vec<(ratatui::style::Style, Range<usize>)>;
+
+/// syntax highlighting of the lines of a diff, the old and new
+/// versions of the changed lines are highlighted separately
+pub struct DiffSyntaxText {
+	/// per line of all hunks, `None` for hunk headers
+	lines: Vec<Option<SyntaxRanges>>,
+	hash: u64,
+}
+
+impl DiffSyntaxText {
+	pub fn new(
+		diff: &FileDiff,
+		hash: u64,
+		file_path: &Path,
+		params: &RunParams<AsyncAppNotification, ProgressPercent>,
+	) -> asyncgit::Result<Self> {
+		scope_time!("diff_syntax_highlighting");
+
+		// text and number of lines of each version
+		let mut old = (String::new(), 0);
+		let mut new = (String::new(), 0);
+		// side (`true` for new) and line in it of each diff line
+		let mut sides = Vec::with_capacity(diff.lines);
+
+		for line in diff.hunks.iter().flat_map(|hunk| &hunk.lines) {
+			let side = match line.line_type {
+				DiffLineType::Header => None,
+				DiffLineType::Delete => {
+					Some((false, push_line(&mut old, &line.content)))
+				}
+				DiffLineType::Add => {
+					Some((true, push_line(&mut new, &line.content)))
+				}
+				// context lines are part of both versions to keep
+				// the state of the highlighter (e.g. open comments)
+				// right on either side, they are shown as in the
+				// new version
+				DiffLineType::None => {
+					push_line(&mut old, &line.content);
+					Some((true, push_line(&mut new, &line.content)))
+				}
+			};
+
+			sides.push(side);
+		}
+
+		let old = SyntaxText::new(old.0, file_path, params)?;
+		let new = SyntaxText::new(new.0, file_path, params)?;
+
+		let lines = sides
+			.into_iter()
+			.map(|side| {
+				side.map(|(is_new, index)| {
+					let text = if is_new { &new } else { &old };
+
+					text.lines
+						.get(index)
+						.map(|line| {
+							line.items
+								.iter()
+								.map(|(style, _, range)| {
+									(
+										syntact_style_to_tui(style),
+										range.clone(),
+									)
+								})
+								.collect()
+						})
+						.unwrap_or_default()
+				})
+			})
+			.collect();
+
+		Ok(Self { lines, hash })
+	}
+
+	/// hash of the `FileDiff` this was created for
+	pub const fn hash(&self) -> u64 {
+		self.hash
+	}
+
+	/// styles of line `index` of all hunks
+	pub fn line(&self, index: usize) -> Option<&SyntaxRanges> {
+		self.lines.get(index).and_then(Option::as_ref)
+	}
+}
+
+/// appends `content` to the text of a version of the diff,
+/// returns its line index
+fn push_line(
+	(text, count): &mut (String, usize),
+	content: &str,
+) -> usize {
+	text.push_str(content);
+	text.push('\n');
+	*count += 1;
+	*count - 1
+}
+
 fn syntact_style_to_tui(style: &Style) -> ratatui::style::Style {
 	let mut res = ratatui::style::Style::default().fg(
 		ratatui::style::Color::Rgb(
@@ -274,3 +375,70 @@ impl AsyncJob for AsyncSyntaxJob {
 		))
 	}
 }
+
+enum DiffJobState {
+	Request((FileDiff, u64, String)),
+	Response(DiffSyntaxText),
+}
+
+#[derive(Clone, Default)]
+pub struct AsyncDiffSyntaxJob {
+	state: Arc<Mutex<Option<DiffJobState>>>,
+}
+
+impl AsyncDiffSyntaxJob {
+	pub fn new(diff: FileDiff, hash: u64, path: String) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(DiffJobState::Request(
+				(diff, hash, path),
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<DiffSyntaxText> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					DiffJobState::Request(_) => None,
+					DiffJobState::Response(text) => Some(text),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncDiffSyntaxJob {
+	type Notification = AsyncAppNotification;
+	type Progress = ProgressPercent;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> asyncgit::Result<Self::Notification> {
+		let mut state_mutex = self.state.lock()?;
+
+		if let Some(state) = state_mutex.take() {
+			*state_mutex = Some(match state {
+				DiffJobState::Request((diff, hash, path)) => {
+					let syntax = DiffSyntaxText::new(
+						&diff,
+						hash,
+						Path::new(&path),
+						&params,
+					)?;
+					DiffJobState::Response(syntax)
+				}
+				DiffJobState::Response(res) => {
+					DiffJobState::Response(res)
+				}
+			});
+		}
+
+		Ok(AsyncAppNotification::SyntaxHighlighting(
+			SyntaxHighlightProgress::Done,
+		))
+	}
+}